@@ -0,0 +1,265 @@
+// ACTUS PAM schedule and payoff for the zero coupon bonds.
+// No ledger access here: time is always passed in, so the same code runs in the
+// blueprints and in the native simulator.
+// Decimal is the radix-common one scrypto re-exports, so the blueprints can pass theirs straight in.
+pub use radix_common::math::Decimal;
+
+pub const SECONDS_IN_YEAR: u64 = 365 * 24 * 60 * 60;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContractEventType {
+    // holder pays the price for the bonds
+    InitialExchange,
+    // issuer pays a sinking fund instalment
+    PrincipalRedemption,
+    // issuer pays whatever is left of principal plus interest
+    Maturity,
+}
+
+impl ContractEventType {
+    // ACTUS event type codes
+    pub fn code(&self) -> &'static str {
+        match self {
+            ContractEventType::InitialExchange => "IED",
+            ContractEventType::PrincipalRedemption => "PR",
+            ContractEventType::Maturity => "MD",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ContractEvent {
+    pub time: u64,
+    pub event_type: ContractEventType,
+    // seen from the holder: negative when the holder pays
+    pub payoff: Decimal,
+}
+
+#[derive(Clone, Debug)]
+pub struct ZeroCouponTerms {
+    pub contract_identifier: String,
+    pub initial_exchange_date: u64,
+    pub maturity_date: u64,
+    // price of one bond
+    pub price: Decimal,
+    // percent of the price paid as interest at maturity
+    pub nominal_interest_rate: Decimal,
+    pub number_of_bonds: Decimal,
+    // (due date, amount) for the whole issue
+    pub sinking_fund: Vec<(u64, Decimal)>,
+}
+
+pub fn interest_amount(price: Decimal, nominal_interest_rate: Decimal) -> Decimal {
+    (nominal_interest_rate / 100) * price
+}
+
+// principal plus interest owed on one bond at maturity
+pub fn redemption_value(price: Decimal, nominal_interest_rate: Decimal) -> Decimal {
+    price + interest_amount(price, nominal_interest_rate)
+}
+
+// interest earned on one bond by `time`, accrued linearly from issue to maturity
+pub fn accrued_interest(
+    price: Decimal,
+    nominal_interest_rate: Decimal,
+    initial_exchange_date: u64,
+    maturity_date: u64,
+    time: u64,
+) -> Decimal {
+    let interest = interest_amount(price, nominal_interest_rate);
+    let term = maturity_date.saturating_sub(initial_exchange_date);
+
+    if term == 0 {
+        return interest;
+    }
+
+    let elapsed = time.saturating_sub(initial_exchange_date).min(term);
+
+    interest * Decimal::from(elapsed) / Decimal::from(term)
+}
+
+impl ZeroCouponTerms {
+    pub fn redemption_value_per_bond(&self) -> Decimal {
+        redemption_value(self.price, self.nominal_interest_rate)
+    }
+
+    pub fn accrued_interest_per_bond(&self, time: u64) -> Decimal {
+        accrued_interest(
+            self.price,
+            self.nominal_interest_rate,
+            self.initial_exchange_date,
+            self.maturity_date,
+            time,
+        )
+    }
+
+    // contractual events for `quantity` bonds, in time order
+    pub fn schedule(&self, quantity: Decimal) -> Vec<ContractEvent> {
+        let share = if self.number_of_bonds == Decimal::zero() {
+            Decimal::zero()
+        } else {
+            quantity / self.number_of_bonds
+        };
+
+        let mut events = vec![ContractEvent {
+            time: self.initial_exchange_date,
+            event_type: ContractEventType::InitialExchange,
+            payoff: -self.price * quantity,
+        }];
+
+        let mut redeemed = Decimal::zero();
+
+        for (due_date, amount) in &self.sinking_fund {
+            let payoff = *amount * share;
+            redeemed += payoff;
+
+            events.push(ContractEvent {
+                time: *due_date,
+                event_type: ContractEventType::PrincipalRedemption,
+                payoff,
+            });
+        }
+
+        let total_owed = self.redemption_value_per_bond() * quantity;
+
+        events.push(ContractEvent {
+            time: self.maturity_date,
+            event_type: ContractEventType::Maturity,
+            payoff: (total_owed - redeemed).max(Decimal::zero()),
+        });
+
+        events.sort_by_key(|event| event.time);
+        events
+    }
+
+    // events still to come after `time`
+    pub fn events_after(&self, quantity: Decimal, time: u64) -> Vec<ContractEvent> {
+        self.schedule(quantity)
+            .into_iter()
+            .filter(|event| event.time > time)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(value: &str) -> Decimal {
+        value.parse().unwrap()
+    }
+
+    // 10 bonds at 100 paying 10% at maturity, issued at 0 and maturing at 100
+    fn terms(sinking_fund: Vec<(u64, Decimal)>) -> ZeroCouponTerms {
+        ZeroCouponTerms {
+            contract_identifier: "bond".to_string(),
+            initial_exchange_date: 0,
+            maturity_date: 100,
+            price: dec("100"),
+            nominal_interest_rate: dec("10"),
+            number_of_bonds: dec("10"),
+            sinking_fund,
+        }
+    }
+
+    fn payoffs(events: &[ContractEvent]) -> Vec<(u64, &'static str, Decimal)> {
+        events
+            .iter()
+            .map(|event| (event.time, event.event_type.code(), event.payoff))
+            .collect()
+    }
+
+    #[test]
+    fn redemption_value_is_price_plus_interest() {
+        for (price, rate, expected) in [
+            ("100", "10", "110"),
+            ("40", "2", "40.8"),
+            ("100", "0", "100"),
+            ("0", "5", "0"),
+            ("1", "100", "2"),
+        ] {
+            assert_eq!(redemption_value(dec(price), dec(rate)), dec(expected), "{price} at {rate}%");
+        }
+    }
+
+    #[test]
+    fn interest_accrues_linearly_between_issue_and_maturity() {
+        for (initial_exchange_date, maturity_date, time, expected) in [
+            (0, 100, 0, "0"),
+            (0, 100, 25, "2.5"),
+            (0, 100, 50, "5"),
+            (0, 100, 100, "10"),
+            // no more interest after maturity
+            (0, 100, 150, "10"),
+            // none before the issue
+            (10, 110, 5, "0"),
+            (10, 110, 60, "5"),
+            // a bond maturing at issue owes its interest at once
+            (50, 50, 0, "10"),
+        ] {
+            assert_eq!(
+                accrued_interest(dec("100"), dec("10"), initial_exchange_date, maturity_date, time),
+                dec(expected),
+                "issued at {initial_exchange_date}, maturing at {maturity_date}, at {time}"
+            );
+        }
+    }
+
+    #[test]
+    fn schedule_pays_principal_plus_interest_at_maturity() {
+        assert_eq!(
+            payoffs(&terms(Vec::new()).schedule(dec("2"))),
+            vec![(0, "IED", dec("-200")), (100, "MD", dec("220"))]
+        );
+    }
+
+    #[test]
+    fn sinking_fund_instalments_are_shared_by_quantity_and_come_off_the_maturity_payoff() {
+        let events = terms(vec![(30, dec("100")), (60, dec("200"))]).schedule(dec("2"));
+
+        assert_eq!(
+            payoffs(&events),
+            vec![
+                (0, "IED", dec("-200")),
+                (30, "PR", dec("20")),
+                (60, "PR", dec("40")),
+                (100, "MD", dec("160")),
+            ]
+        );
+    }
+
+    #[test]
+    fn maturity_payoff_never_goes_negative() {
+        let events = terms(vec![(50, dec("2000"))]).schedule(dec("1"));
+
+        assert_eq!(
+            payoffs(&events),
+            vec![(0, "IED", dec("-100")), (50, "PR", dec("200")), (100, "MD", dec("0"))]
+        );
+    }
+
+    #[test]
+    fn an_issue_without_bonds_shares_no_instalments() {
+        let mut terms = terms(vec![(50, dec("100"))]);
+        terms.number_of_bonds = Decimal::zero();
+
+        assert_eq!(
+            payoffs(&terms.schedule(dec("1"))),
+            vec![(0, "IED", dec("-100")), (50, "PR", dec("0")), (100, "MD", dec("110"))]
+        );
+    }
+
+    #[test]
+    fn events_after_leaves_out_events_at_or_before_the_time() {
+        let terms = terms(vec![(50, dec("100"))]);
+
+        for (time, expected) in [(0, vec!["PR", "MD"]), (50, vec!["MD"]), (100, vec![])] {
+            let codes: Vec<&str> = terms
+                .events_after(dec("1"), time)
+                .iter()
+                .map(|event| event.event_type.code())
+                .collect();
+            assert_eq!(codes, expected, "after {time}");
+        }
+    }
+}
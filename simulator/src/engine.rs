@@ -0,0 +1,432 @@
+use crate::scenario::{ContractDefault, DefaultScenario, RatePath, SimulationInput};
+use actus_schedule::{ContractEvent, Decimal, ZeroCouponTerms};
+
+pub struct CashFlowRow {
+    pub rate_path: String,
+    pub scenario: String,
+    pub contract_identifier: String,
+    pub time: u64,
+    pub event_type: &'static str,
+    pub contractual_amount: Decimal,
+    pub realised_amount: Decimal,
+    pub discount_factor: f64,
+    pub present_value: f64,
+}
+
+pub struct LossRow {
+    pub rate_path: String,
+    pub scenario: String,
+    pub contractual_value: f64,
+    pub realised_value: f64,
+    pub loss: f64,
+}
+
+pub struct LossDistribution {
+    pub rate_path: String,
+    pub runs: usize,
+    pub mean: f64,
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub max: f64,
+}
+
+pub struct Simulation {
+    pub cash_flows: Vec<CashFlowRow>,
+    pub losses: Vec<LossRow>,
+}
+
+struct Position {
+    terms: ZeroCouponTerms,
+    quantity: Decimal,
+    default_probability: f64,
+    recovery_rate: f64,
+}
+
+fn to_f64(value: Decimal) -> f64 {
+    value.to_string().parse::<f64>().unwrap_or(0.0)
+}
+
+// xorshift64*, enough to draw reproducible default scenarios without extra dependencies
+struct Random(u64);
+
+impl Random {
+    fn next_f64(&mut self) -> f64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        (self.0.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+// the contractual events after the valuation date, with what is actually received under a default
+fn realise(
+    events: &[ContractEvent],
+    default: Option<&ContractDefault>,
+    recovery_rate: f64,
+) -> Vec<(ContractEvent, Decimal)> {
+    let default = match default {
+        Some(default) => default,
+        None => {
+            return events
+                .iter()
+                .map(|event| (event.clone(), event.payoff))
+                .collect()
+        }
+    };
+
+    let recovery_rate = default.recovery_rate.unwrap_or(recovery_rate);
+    let owed_after_default = events
+        .iter()
+        .filter(|event| event.time >= default.time)
+        .fold(Decimal::zero(), |total, event| total + event.payoff);
+    let recovered = owed_after_default
+        * recovery_rate
+            .to_string()
+            .parse::<Decimal>()
+            .unwrap_or(Decimal::zero());
+
+    let mut recovery_paid = false;
+
+    events
+        .iter()
+        .map(|event| {
+            if event.time < default.time {
+                (event.clone(), event.payoff)
+            } else if !recovery_paid {
+                // everything still owed is settled by the recovery on the first event after default
+                recovery_paid = true;
+                (event.clone(), recovered)
+            } else {
+                (event.clone(), Decimal::zero())
+            }
+        })
+        .collect()
+}
+
+fn run_scenario(
+    input_valuation_date: u64,
+    positions: &[Position],
+    rate_path: &RatePath,
+    scenario: &DefaultScenario,
+    cash_flows: Option<&mut Vec<CashFlowRow>>,
+) -> LossRow {
+    let mut contractual_value = 0.0;
+    let mut realised_value = 0.0;
+    let mut rows = Vec::new();
+
+    for position in positions {
+        let events = position
+            .terms
+            .events_after(position.quantity, input_valuation_date);
+
+        let default = scenario
+            .defaults
+            .iter()
+            .find(|default| default.contract_identifier == position.terms.contract_identifier);
+
+        for (event, realised_amount) in realise(&events, default, position.recovery_rate) {
+            let discount_factor = rate_path.discount_factor(input_valuation_date, event.time);
+
+            contractual_value += to_f64(event.payoff) * discount_factor;
+            realised_value += to_f64(realised_amount) * discount_factor;
+
+            rows.push(CashFlowRow {
+                rate_path: rate_path.name.clone(),
+                scenario: scenario.name.clone(),
+                contract_identifier: position.terms.contract_identifier.clone(),
+                time: event.time,
+                event_type: event.event_type.code(),
+                contractual_amount: event.payoff,
+                realised_amount,
+                discount_factor,
+                present_value: to_f64(realised_amount) * discount_factor,
+            });
+        }
+    }
+
+    if let Some(cash_flows) = cash_flows {
+        cash_flows.extend(rows);
+    }
+
+    LossRow {
+        rate_path: rate_path.name.clone(),
+        scenario: scenario.name.clone(),
+        contractual_value,
+        realised_value,
+        loss: contractual_value - realised_value,
+    }
+}
+
+pub fn simulate(input: &SimulationInput) -> Result<Simulation, String> {
+    let mut positions = Vec::new();
+
+    for contract in &input.contracts {
+        positions.push(Position {
+            terms: contract.terms()?,
+            quantity: crate::scenario::parse_decimal(&contract.quantity, "quantity")?,
+            default_probability: contract.default_probability,
+            recovery_rate: contract.recovery_rate,
+        });
+    }
+
+    let mut scenarios = vec![DefaultScenario {
+        name: "no defaults".to_string(),
+        defaults: Vec::new(),
+    }];
+    scenarios.extend(input.default_scenarios.iter().cloned());
+
+    let mut simulation = Simulation {
+        cash_flows: Vec::new(),
+        losses: Vec::new(),
+    };
+
+    for rate_path in &input.rate_paths {
+        for scenario in &scenarios {
+            let loss = run_scenario(
+                input.valuation_date,
+                &positions,
+                rate_path,
+                scenario,
+                Some(&mut simulation.cash_flows),
+            );
+            simulation.losses.push(loss);
+        }
+
+        if let Some(monte_carlo) = &input.monte_carlo {
+            let mut random = Random(monte_carlo.seed.max(1));
+
+            for run in 0..monte_carlo.runs {
+                let mut defaults = Vec::new();
+
+                for position in &positions {
+                    if random.next_f64() < position.default_probability {
+                        let start = input.valuation_date;
+                        let end = position.terms.maturity_date.max(start);
+                        let time = start + ((end - start) as f64 * random.next_f64()) as u64;
+
+                        defaults.push(ContractDefault {
+                            contract_identifier: position.terms.contract_identifier.clone(),
+                            time,
+                            recovery_rate: None,
+                        });
+                    }
+                }
+
+                let scenario = DefaultScenario {
+                    name: format!("monte carlo {run}"),
+                    defaults,
+                };
+
+                // cash flows of the random runs are not written, only their losses
+                let loss = run_scenario(input.valuation_date, &positions, rate_path, &scenario, None);
+                simulation.losses.push(loss);
+            }
+        }
+    }
+
+    Ok(simulation)
+}
+
+// loss quantiles per rate path over every scenario run under it
+pub fn loss_distribution(losses: &[LossRow]) -> Vec<LossDistribution> {
+    let mut rate_paths: Vec<String> = Vec::new();
+    for loss in losses {
+        if !rate_paths.contains(&loss.rate_path) {
+            rate_paths.push(loss.rate_path.clone());
+        }
+    }
+
+    rate_paths
+        .into_iter()
+        .map(|rate_path| {
+            let mut values: Vec<f64> = losses
+                .iter()
+                .filter(|loss| loss.rate_path == rate_path)
+                .map(|loss| loss.loss)
+                .collect();
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+            let quantile = |q: f64| {
+                let index = ((values.len() as f64 - 1.0) * q).round() as usize;
+                values.get(index).cloned().unwrap_or(0.0)
+            };
+            let mean = values.iter().sum::<f64>() / values.len().max(1) as f64;
+
+            LossDistribution {
+                rate_path,
+                runs: values.len(),
+                mean,
+                p50: quantile(0.5),
+                p95: quantile(0.95),
+                p99: quantile(0.99),
+                max: values.last().cloned().unwrap_or(0.0),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::{ContractPosition, MonteCarlo, RatePoint};
+    use actus_schedule::SECONDS_IN_YEAR;
+
+    const VALUATION_DATE: u64 = 1_000;
+
+    // one bond bought at 100 paying 10% at maturity, a year after the valuation date
+    fn contract(default_probability: f64, recovery_rate: f64) -> ContractPosition {
+        ContractPosition {
+            contract_identifier: "bond".to_string(),
+            initial_exchange_date: 0,
+            maturity_date: VALUATION_DATE + SECONDS_IN_YEAR,
+            price: "100".to_string(),
+            nominal_interest_rate: "10".to_string(),
+            number_of_bonds: "1".to_string(),
+            sinking_fund: Vec::new(),
+            quantity: "1".to_string(),
+            default_probability,
+            recovery_rate,
+        }
+    }
+
+    fn rate_path(name: &str, rate: f64) -> RatePath {
+        RatePath {
+            name: name.to_string(),
+            points: vec![RatePoint { time: 0, rate }],
+            shift: 0.0,
+        }
+    }
+
+    fn input(contract: ContractPosition, default_scenarios: Vec<DefaultScenario>) -> SimulationInput {
+        SimulationInput {
+            valuation_date: VALUATION_DATE,
+            contracts: vec![contract],
+            rate_paths: vec![rate_path("flat", 0.0)],
+            default_scenarios,
+            monte_carlo: None,
+        }
+    }
+
+    fn loss_row(rate_path: &str, loss: f64) -> LossRow {
+        LossRow {
+            rate_path: rate_path.to_string(),
+            scenario: "run".to_string(),
+            contractual_value: loss,
+            realised_value: 0.0,
+            loss,
+        }
+    }
+
+    #[test]
+    fn test_simulate_only_values_events_after_the_valuation_date() {
+        let simulation = simulate(&input(contract(0.0, 0.0), Vec::new())).unwrap();
+
+        // the initial exchange is in the past, only maturity is left
+        assert_eq!(simulation.cash_flows.len(), 1);
+        assert_eq!(simulation.cash_flows[0].event_type, "MD");
+        assert_eq!(simulation.cash_flows[0].realised_amount, Decimal::from(110));
+
+        assert_eq!(simulation.losses.len(), 1);
+        assert_eq!(simulation.losses[0].contractual_value, 110.0);
+        assert_eq!(simulation.losses[0].loss, 0.0);
+    }
+
+    #[test]
+    fn test_simulate_discounts_along_the_rate_path() {
+        let mut input = input(contract(0.0, 0.0), Vec::new());
+        input.rate_paths = vec![rate_path("five percent", 0.05)];
+
+        let simulation = simulate(&input).unwrap();
+
+        let expected = 110.0 * (-0.05f64).exp();
+        assert!((simulation.losses[0].contractual_value - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_simulate_pays_the_recovery_on_default() {
+        let scenario = DefaultScenario {
+            name: "default".to_string(),
+            defaults: vec![ContractDefault {
+                contract_identifier: "bond".to_string(),
+                time: VALUATION_DATE + 1,
+                recovery_rate: Some(0.4),
+            }],
+        };
+
+        let simulation = simulate(&input(contract(0.0, 0.0), vec![scenario])).unwrap();
+
+        // the no defaults scenario always runs first
+        assert_eq!(simulation.losses.len(), 2);
+        assert_eq!(simulation.losses[1].realised_value, 44.0);
+        assert_eq!(simulation.losses[1].loss, 66.0);
+    }
+
+    #[test]
+    fn test_simulate_monte_carlo_runs_are_reproducible() {
+        let mut input = input(contract(1.0, 0.5), Vec::new());
+        input.monte_carlo = Some(MonteCarlo { runs: 5, seed: 7 });
+
+        let first = simulate(&input).unwrap();
+        let second = simulate(&input).unwrap();
+
+        assert_eq!(first.losses.len(), 6);
+        // the random runs do not write cash flows
+        assert_eq!(first.cash_flows.len(), 1);
+        for (a, b) in first.losses.iter().zip(second.losses.iter()) {
+            assert_eq!(a.loss, b.loss);
+        }
+        // a certain default with half recovered
+        for loss in &first.losses[1..] {
+            assert_eq!(loss.loss, 55.0);
+        }
+    }
+
+    #[test]
+    fn test_simulate_rejects_an_invalid_decimal() {
+        let mut contract = contract(0.0, 0.0);
+        contract.price = "a hundred".to_string();
+
+        assert!(simulate(&input(contract, Vec::new())).is_err());
+    }
+
+    #[test]
+    fn test_loss_distribution_quantiles() {
+        let losses: Vec<LossRow> = (0..=100).map(|loss| loss_row("flat", loss as f64)).collect();
+
+        let distribution = loss_distribution(&losses);
+
+        assert_eq!(distribution.len(), 1);
+        assert_eq!(distribution[0].runs, 101);
+        assert_eq!(distribution[0].mean, 50.0);
+        assert_eq!(distribution[0].p50, 50.0);
+        assert_eq!(distribution[0].p95, 95.0);
+        assert_eq!(distribution[0].p99, 99.0);
+        assert_eq!(distribution[0].max, 100.0);
+    }
+
+    #[test]
+    fn test_loss_distribution_is_per_rate_path() {
+        let losses = vec![
+            loss_row("low", 3.0),
+            loss_row("high", 10.0),
+            loss_row("low", 1.0),
+            loss_row("high", 20.0),
+        ];
+
+        let distribution = loss_distribution(&losses);
+
+        // in order of first appearance
+        assert_eq!(distribution.len(), 2);
+        assert_eq!(distribution[0].rate_path, "low");
+        assert_eq!(distribution[0].max, 3.0);
+        assert_eq!(distribution[0].mean, 2.0);
+        assert_eq!(distribution[1].rate_path, "high");
+        assert_eq!(distribution[1].mean, 15.0);
+    }
+
+    #[test]
+    fn test_loss_distribution_of_nothing_is_empty() {
+        assert!(loss_distribution(&[]).is_empty());
+    }
+}
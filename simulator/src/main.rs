@@ -0,0 +1,84 @@
+// Runs a portfolio of ACTUS zero coupon bonds through rate paths and default scenarios,
+// without a ledger, and writes the results as CSV.
+//
+// cargo run -p actus-simulator -- simulator/examples/scenario.json out
+mod engine;
+mod scenario;
+
+use std::fs;
+use std::path::Path;
+
+use engine::{loss_distribution, simulate, Simulation};
+use scenario::SimulationInput;
+
+fn write_csv(simulation: &Simulation, output_dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(output_dir).map_err(|error| error.to_string())?;
+
+    let mut cash_flows = String::from(
+        "rate_path,scenario,contract_identifier,time,event_type,contractual_amount,realised_amount,discount_factor,present_value\n",
+    );
+    for row in &simulation.cash_flows {
+        cash_flows.push_str(&format!(
+            "{},{},{},{},{},{},{},{:.6},{:.6}\n",
+            row.rate_path,
+            row.scenario,
+            row.contract_identifier,
+            row.time,
+            row.event_type,
+            row.contractual_amount,
+            row.realised_amount,
+            row.discount_factor,
+            row.present_value
+        ));
+    }
+
+    let mut losses = String::from("rate_path,scenario,contractual_value,realised_value,loss\n");
+    for row in &simulation.losses {
+        losses.push_str(&format!(
+            "{},{},{:.6},{:.6},{:.6}\n",
+            row.rate_path, row.scenario, row.contractual_value, row.realised_value, row.loss
+        ));
+    }
+
+    let mut distribution = String::from("rate_path,runs,mean,p50,p95,p99,max\n");
+    for row in loss_distribution(&simulation.losses) {
+        distribution.push_str(&format!(
+            "{},{},{:.6},{:.6},{:.6},{:.6},{:.6}\n",
+            row.rate_path, row.runs, row.mean, row.p50, row.p95, row.p99, row.max
+        ));
+    }
+
+    for (file_name, contents) in [
+        ("cash_flows.csv", cash_flows),
+        ("losses.csv", losses),
+        ("loss_distribution.csv", distribution),
+    ] {
+        fs::write(output_dir.join(file_name), contents).map_err(|error| error.to_string())?;
+    }
+
+    Ok(())
+}
+
+fn run(input_path: &str, output_dir: &str) -> Result<(), String> {
+    let input = fs::read_to_string(input_path).map_err(|error| error.to_string())?;
+    let input: SimulationInput =
+        serde_json::from_str(&input).map_err(|error| error.to_string())?;
+
+    let simulation = simulate(&input)?;
+
+    write_csv(&simulation, Path::new(output_dir))
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+
+    if args.len() != 3 {
+        eprintln!("usage: actus-simulator <scenario.json> <output directory>");
+        std::process::exit(2);
+    }
+
+    if let Err(error) = run(&args[1], &args[2]) {
+        eprintln!("simulation failed: {error}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,147 @@
+use actus_schedule::{Decimal, ZeroCouponTerms, SECONDS_IN_YEAR};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct SimulationInput {
+    // present values are taken at this time
+    pub valuation_date: u64,
+    pub contracts: Vec<ContractPosition>,
+    pub rate_paths: Vec<RatePath>,
+    #[serde(default)]
+    pub default_scenarios: Vec<DefaultScenario>,
+    // random defaults drawn from each contract's default probability
+    pub monte_carlo: Option<MonteCarlo>,
+}
+
+#[derive(Deserialize)]
+pub struct SinkingFundInstalment {
+    pub due_date: u64,
+    pub amount: String,
+}
+
+#[derive(Deserialize)]
+pub struct ContractPosition {
+    pub contract_identifier: String,
+    pub initial_exchange_date: u64,
+    pub maturity_date: u64,
+    pub price: String,
+    pub nominal_interest_rate: String,
+    pub number_of_bonds: String,
+    #[serde(default)]
+    pub sinking_fund: Vec<SinkingFundInstalment>,
+    // bonds of this issue held by the portfolio
+    pub quantity: String,
+    // chance the issuer defaults before maturity, used by the monte carlo runs
+    #[serde(default)]
+    pub default_probability: f64,
+    // share of what is still owed that is recovered on default
+    #[serde(default)]
+    pub recovery_rate: f64,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct RatePoint {
+    pub time: u64,
+    // annual rate, 0.05 for 5%
+    pub rate: f64,
+}
+
+// piecewise constant short rate, each point holds until the next one
+#[derive(Deserialize, Clone)]
+pub struct RatePath {
+    pub name: String,
+    pub points: Vec<RatePoint>,
+    // parallel shock added to every point
+    #[serde(default)]
+    pub shift: f64,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct ContractDefault {
+    pub contract_identifier: String,
+    pub time: u64,
+    // overrides the contract's recovery rate
+    pub recovery_rate: Option<f64>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct DefaultScenario {
+    pub name: String,
+    pub defaults: Vec<ContractDefault>,
+}
+
+#[derive(Deserialize)]
+pub struct MonteCarlo {
+    pub runs: u32,
+    pub seed: u64,
+}
+
+pub fn parse_decimal(value: &str, field: &str) -> Result<Decimal, String> {
+    value
+        .parse::<Decimal>()
+        .map_err(|_| format!("{field} is not a valid decimal: {value}"))
+}
+
+impl ContractPosition {
+    pub fn terms(&self) -> Result<ZeroCouponTerms, String> {
+        let mut sinking_fund = Vec::new();
+
+        for instalment in &self.sinking_fund {
+            sinking_fund.push((
+                instalment.due_date,
+                parse_decimal(&instalment.amount, "sinking fund amount")?,
+            ));
+        }
+
+        Ok(ZeroCouponTerms {
+            contract_identifier: self.contract_identifier.clone(),
+            initial_exchange_date: self.initial_exchange_date,
+            maturity_date: self.maturity_date,
+            price: parse_decimal(&self.price, "price")?,
+            nominal_interest_rate: parse_decimal(&self.nominal_interest_rate, "nominal_interest_rate")?,
+            number_of_bonds: parse_decimal(&self.number_of_bonds, "number_of_bonds")?,
+            sinking_fund,
+        })
+    }
+}
+
+impl RatePath {
+    fn rate_at(&self, time: u64) -> f64 {
+        let rate = self
+            .points
+            .iter()
+            .take_while(|point| point.time <= time)
+            .last()
+            .or(self.points.first())
+            .map(|point| point.rate)
+            .unwrap_or(0.0);
+
+        rate + self.shift
+    }
+
+    // exp(-integral of the short rate) between `from` and `to`
+    pub fn discount_factor(&self, from: u64, to: u64) -> f64 {
+        if to <= from {
+            return 1.0;
+        }
+
+        let mut boundaries: Vec<u64> = self
+            .points
+            .iter()
+            .map(|point| point.time)
+            .filter(|time| *time > from && *time < to)
+            .collect();
+        boundaries.push(to);
+
+        let mut integral = 0.0;
+        let mut start = from;
+
+        for end in boundaries {
+            let years = (end - start) as f64 / SECONDS_IN_YEAR as f64;
+            integral += self.rate_at(start) * years;
+            start = end;
+        }
+
+        (-integral).exp()
+    }
+}
@@ -0,0 +1,200 @@
+use scrypto::prelude::*;
+
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub enum DecayCurve {
+    /// Price falls in a straight line from the start price to the floor over the auction duration.
+    Linear,
+
+    /// Price is multiplied by (1 - rate) for every full `interval` seconds that pass.
+    Exponential { rate: Decimal, interval: u64 },
+
+    /// Price drops by a fixed `drop` for every full `interval` seconds that pass.
+    Step { drop: Decimal, interval: u64 },
+}
+
+#[derive(ScryptoSbor, Debug)]
+pub struct AuctionDetails {
+    pub collateral_resource_address: ResourceAddress,
+    pub collateral_left: Decimal,
+    pub proceeds: Decimal,
+    pub start_price: Decimal,
+    pub floor_price: Decimal,
+    pub current_price: Decimal,
+    pub decay_curve: DecayCurve,
+    pub start_time: u64,
+    pub duration: u64,
+    pub closed: bool,
+}
+
+#[blueprint]
+mod dutch_auction {
+
+    enable_method_auth! {
+        methods {
+            bid => PUBLIC;
+            get_current_price => PUBLIC;
+            get_auction_details => PUBLIC;
+            withdraw_proceeds => restrict_to: [OWNER];
+            close_auction => restrict_to: [OWNER];
+        }
+    }
+
+    pub struct DutchAuction {
+        /// Seized collateral being sold.
+        pub collateral: Vault,
+
+        /// XRD collected from bidders, waiting to be settled by the DAO.
+        pub proceeds: Vault,
+
+        /// Price per unit of collateral when the auction opens.
+        pub start_price: Decimal,
+
+        /// Price per unit of collateral will never go below this.
+        pub floor_price: Decimal,
+
+        pub decay_curve: DecayCurve,
+
+        pub start_time: u64,
+
+        /// Seconds over which the linear curve reaches the floor; no bids are accepted afterwards.
+        pub duration: u64,
+
+        pub closed: bool,
+    }
+
+    impl DutchAuction {
+
+        pub fn instantiate_dutch_auction(
+            dao_address: ComponentAddress,
+            collateral: Bucket,
+            start_price: Decimal,
+            floor_price: Decimal,
+            decay_curve: DecayCurve,
+            duration: u64,
+        ) -> Global<DutchAuction> {
+            assert!(
+                floor_price > Decimal::zero() && floor_price <= start_price,
+                "floor price must be positive and not above the start price"
+            );
+            assert!(duration > 0, "auction duration must be greater than zero");
+            assert!(!collateral.is_empty(), "there is no collateral to auction");
+
+            match &decay_curve {
+                DecayCurve::Linear => {}
+                DecayCurve::Exponential { rate, interval } => {
+                    assert!(
+                        *rate > Decimal::zero() && *rate < Decimal::one(),
+                        "exponential decay rate must be between 0 and 1"
+                    );
+                    assert!(*interval > 0, "decay interval must be greater than zero");
+                }
+                DecayCurve::Step { drop, interval } => {
+                    assert!(*drop > Decimal::zero(), "step drop must be greater than zero");
+                    assert!(*interval > 0, "decay interval must be greater than zero");
+                }
+            }
+
+            let now: Instant = Clock::current_time_rounded_to_seconds();
+            let start_time: u64 = now.seconds_since_unix_epoch as u64;
+
+            Self {
+                collateral: Vault::with_bucket(collateral),
+                proceeds: Vault::new(XRD),
+                start_price,
+                floor_price,
+                decay_curve,
+                start_time,
+                duration,
+                closed: false,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Fixed(rule!(require(global_caller(
+                dao_address
+            )))))
+            .globalize()
+        }
+
+        pub fn get_current_price(&self) -> Decimal {
+            let now: Instant = Clock::current_time_rounded_to_seconds();
+            let current_time_seconds: u64 = now.seconds_since_unix_epoch as u64;
+
+            let elapsed = current_time_seconds.saturating_sub(self.start_time);
+
+            let price = match &self.decay_curve {
+                DecayCurve::Linear => {
+                    let elapsed = elapsed.min(self.duration);
+                    self.start_price
+                        - (self.start_price - self.floor_price) * Decimal::from(elapsed)
+                            / Decimal::from(self.duration)
+                }
+                DecayCurve::Exponential { rate, interval } => {
+                    let periods = (elapsed / interval) as i64;
+                    (Decimal::one() - *rate)
+                        .checked_powi(periods)
+                        .map(|factor| self.start_price * factor)
+                        .unwrap_or(self.floor_price)
+                }
+                DecayCurve::Step { drop, interval } => {
+                    let periods = elapsed / interval;
+                    self.start_price - *drop * Decimal::from(periods)
+                }
+            };
+
+            price.max(self.floor_price)
+        }
+
+        /// Buys `amount` units of collateral at the current price.
+        /// Returns the collateral and any change.
+        pub fn bid(&mut self, mut payment: Bucket, amount: Decimal) -> (Bucket, Bucket) {
+            assert!(!self.closed, "auction is closed");
+            assert_eq!(payment.resource_address(), XRD, "auction only accepts XRD");
+            assert!(amount > Decimal::zero(), "bid amount must be greater than zero");
+            assert!(
+                amount <= self.collateral.amount(),
+                "not enough collateral left in the auction"
+            );
+
+            let now: Instant = Clock::current_time_rounded_to_seconds();
+            let current_time_seconds: u64 = now.seconds_since_unix_epoch as u64;
+
+            assert!(
+                current_time_seconds <= self.start_time + self.duration,
+                "auction has ended"
+            );
+
+            let cost = self.get_current_price() * amount;
+
+            assert!(payment.amount() >= cost, "you are paying an insufficient amount");
+
+            self.proceeds.put(payment.take(cost));
+
+            (self.collateral.take(amount), payment)
+        }
+
+        pub fn get_auction_details(&self) -> AuctionDetails {
+            AuctionDetails {
+                collateral_resource_address: self.collateral.resource_address(),
+                collateral_left: self.collateral.amount(),
+                proceeds: self.proceeds.amount(),
+                start_price: self.start_price,
+                floor_price: self.floor_price,
+                current_price: self.get_current_price(),
+                decay_curve: self.decay_curve.clone(),
+                start_time: self.start_time,
+                duration: self.duration,
+                closed: self.closed,
+            }
+        }
+
+        pub fn withdraw_proceeds(&mut self) -> Bucket {
+            self.proceeds.take_all()
+        }
+
+        /// Closes the auction and hands back the proceeds and any unsold collateral.
+        pub fn close_auction(&mut self) -> (Bucket, Bucket) {
+            self.closed = true;
+
+            (self.proceeds.take_all(), self.collateral.take_all())
+        }
+    }
+}
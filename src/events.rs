@@ -1,6 +1,11 @@
 use scrypto::prelude::*;
+use crate::auction::DecayCurve;
+use crate::treasury::{LadderInterval, PaymentStream};
+use crate::zerocouponbond::{
+    BondStatus, CallOption, ConversionStats, ConversionTerms, PutOption, SinkingFundInstalment,
+};
 
-#[allow(non_camel_case_types)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
 #[derive(ScryptoSbor, ScryptoEvent)]
 pub enum EventType {
 
@@ -72,6 +77,124 @@ pub enum EventType {
     FUNDS_WITHDRAWN,
 
     FUNDS_NOT_WITHDRAWN,
+
+    PRICE_ORACLE_SET,
+
+    MARGIN_CHECK_PASSED,
+
+    MARGIN_CALL_ISSUED,
+
+    MARGIN_CALL_STILL_OPEN,
+
+    MARGIN_CALL_CURED,
+
+    MARGIN_CALL_LIQUIDATION,
+
+    COLLATERAL_AUCTION_STARTED,
+
+    COLLATERAL_AUCTION_SETTLED,
+
+    COLLATERAL_AUCTION_CLOSED,
+
+    BOND_STATUS_UPDATED,
+
+    ISSUER_APPLIED,
+
+    PROPOSAL_TO_ONBOARD_ISSUER,
+
+    ISSUER_ONBOARDED,
+
+    ISSUER_ONBOARDING_REJECTED,
+
+    SINKING_FUND_DEPOSIT,
+
+    SINKING_FUND_INSTALMENT_MISSED,
+
+    BOND_CALLED,
+
+    CALLED_BONDS_REDEEMED,
+
+    BOND_PUT_EXERCISED,
+
+    BOND_PUT_NOT_PAID,
+
+    BONDS_CONVERTED,
+
+    CONVERSION_TOKENS_RECLAIMED,
+
+    BOND_ISSUED,
+
+    BOND_PURCHASED,
+
+    BOND_REPAID,
+
+    BOND_REDEEMED,
+
+    BOND_COLLATERAL_RETURNED,
+
+    BOND_COLLATERAL_SEIZED,
+
+    BOND_STATUS_CHANGED,
+
+    LIQUIDITY_POLICY_SET,
+
+    PAYMENT_STREAM_ADDED,
+
+    PAYMENT_STREAM_REMOVED,
+
+    INSURANCE_POLICY_PURCHASED,
+
+    INSURANCE_POLICY_RENEWED,
+
+    INSURANCE_CLAIM_FILED,
+
+    INSURANCE_CLAIM_APPROVED,
+
+    INSURANCE_CLAIM_REJECTED,
+
+    INSURANCE_CLAIM_PAID,
+
+    PROPOSAL_TO_TRANSFER_FROM_TREASURY,
+
+    PROPOSAL_TO_CHANGE_CONFIG,
+
+    TREASURY_TRANSFER,
+
+    CONFIG_CHANGED,
+
+    PROPOSAL_STATUS_CHANGED,
+
+    PROPOSAL_RULES_NOT_MET,
+
+    PASSING_RULE_SET,
+
+    VOTE_TOKENS_CLAIMED_BACK,
+
+    PROPOSAL_TO_ADMIT_MEMBER,
+
+    MEMBERSHIP_APPLIED,
+
+    MEMBERSHIP_APPROVED,
+
+    MEMBERSHIP_REJECTED,
+
+    MEMBER_JOINED,
+
+    MEMBER_LEFT,
+
+    MEMBER_EXPELLED,
+
+    MEMBER_ROLE_TAGS_SET,
+
+    JOIN_POLICY_SET,
+
+    DELEGATION_SET,
+
+    DELEGATION_REVOKED,
+
+    DELEGATED_VOTE,
+
+    DELEGATE_OVERRIDDEN,
 }
 
 #[derive(ScryptoSbor, ScryptoEvent)]
@@ -151,15 +274,16 @@ pub struct PraposalMetadata {
     pub start_time_ts: i64,
     pub owner_token_address: ResourceAddress,
     pub component_address: ComponentAddress, // votes:HashMap<Address,Decimal>
-    pub address_issued_bonds_to_sell : Option<ComponentAddress>,
-    pub target_xrd_amount : Option<Decimal>,
+    pub action : ProposalAction,
     pub proposal_creator_address : Option<ComponentAddress>,
-    pub amount_of_tokens_should_be_minted : Option<usize>,
     pub proposal_id : usize,
     pub governance_token_or_owner_token_address : ResourceAddress,
     pub token_type : VotingType,
-    pub desired_token_price : Option<Decimal>,
-    pub desired_token_buy_back_price : Option<Decimal>
+    // empty for yes/no proposals
+    pub options : Vec<ProposalOption>,
+    pub counting_method : Option<CountingMethod>,
+    // only for bond purchases
+    pub issuer_reputation_score : Option<Decimal>
 }
 
 #[derive(ScryptoSbor, ScryptoEvent)]
@@ -225,7 +349,93 @@ pub enum DaoEvent {
 
     FundsWithdrawn(FundsWithdrawn),
 
-    FundsNotWithdrawn(FundsNotWithdrawn)
+    FundsNotWithdrawn(FundsNotWithdrawn),
+
+    PriceOracleSet(PriceOracleSet),
+
+    MarginCall(MarginCallEvent),
+
+    CollateralAuctionStarted(CollateralAuctionStarted),
+
+    CollateralAuctionSettled(CollateralAuctionSettled),
+
+    BondStatusUpdated(BondStatusUpdated),
+
+    IssuerApplied(IssuerApplied),
+
+    IssuerOnboardingDecision(IssuerOnboardingDecision),
+
+    SinkingFundDeposit(SinkingFundDeposit),
+
+    SinkingFundInstalmentMissed(SinkingFundInstalmentMissed),
+
+    BondCalled(BondCalled),
+
+    BondRedeemedEarly(BondRedeemedEarly),
+
+    BondsConverted(BondsConverted),
+
+    ConversionTokensReclaimed(ConversionTokensReclaimed),
+
+    // emitted by the ZeroCouponBond component itself
+    BondIssued(BondIssued),
+
+    BondPurchased(BondPurchased),
+
+    BondRepaid(BondRepaid),
+
+    BondRedeemed(BondRedeemed),
+
+    BondCollateralReturned(BondCollateralMovement),
+
+    BondCollateralSeized(BondCollateralMovement),
+
+    BondStatusChanged(BondStatusChanged),
+
+    LiquidityPolicySet(LiquidityPolicySet),
+
+    PaymentStreamUpdated(PaymentStreamUpdated),
+
+    // emitted by the InsuranceDao component
+    InsurancePolicyUpdated(InsurancePolicyUpdated),
+
+    InsuranceClaimFiled(InsuranceClaimFiled),
+
+    InsuranceClaimDecision(InsuranceClaimDecision),
+
+    InsuranceClaimPaid(InsuranceClaimPaid),
+
+    TreasuryTransfer(TreasuryTransfer),
+
+    ConfigChanged(ConfigChanged),
+
+    ProposalStatusChanged(ProposalStatusChanged),
+
+    ProposalRulesNotMet(ProposalRulesNotMet),
+
+    PassingRuleSet(PassingRuleSet),
+
+    VoteTokensClaimedBack(VoteTokensClaimedBack),
+
+    MembershipApplied(MembershipApplied),
+
+    MembershipDecision(MembershipDecision),
+
+    MemberJoined(MemberJoined),
+
+    MemberLeft(MemberLeft),
+
+    MemberExpelled(MemberExpelled),
+
+    MemberRoleTagsSet(MemberRoleTagsSet),
+
+    JoinPolicySet(JoinPolicySet),
+
+    DelegationChanged(DelegationChanged),
+
+    DelegatedVoteCast(DelegatedVoteCast),
+
+    DelegateOverridden(DelegateOverridden)
 
 }
 
@@ -243,7 +453,9 @@ pub enum DaoEvent {
 #[derive(ScryptoSbor, ScryptoEvent)]
 pub struct PraposalExecute{
     pub praposal_address : ComponentAddress ,
-    pub proposal_id : usize
+    pub proposal_id : usize,
+    pub action : ProposalAction,
+    pub tally : VoteTally
     // pub purchased_bond_address : Option<ResourceAddress>,
     // pub purchased_amount : Decimal
 }
@@ -252,7 +464,11 @@ pub struct PraposalExecute{
 pub struct ProposalVote{
     pub praposal_address : ComponentAddress,
     pub voting_amount : Decimal,
+    // what the voting amount counts for under the proposal's voting type
+    pub voting_weight : Decimal,
     pub againts: bool,
+    // options chosen on a proposal with several options, empty on yes/no proposals
+    pub choices : Vec<usize>,
     pub voter_address : ComponentAddress,
     pub proposal_id : usize
 }
@@ -269,14 +485,6 @@ pub struct PandaoEvent {
     
     pub meta_data: DaoEvent
 }
-// create an event for community_creation
-#[derive(ScryptoSbor, ScryptoEvent)]
-pub struct BoughtToken {
-    pub component_address: ComponentAddress,
-    pub user_address: ResourceAddress,
-    pub amount: Decimal,
-}
-
 #[derive(ScryptoSbor, Debug)]
 pub struct TreasuryContribution {
     pub contributor: ComponentAddress,
@@ -284,7 +492,7 @@ pub struct TreasuryContribution {
     pub timestamp: u64,
 }
 
-#[allow(non_camel_case_types)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
 #[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
 pub enum ProposalCreationRight {
     EVERYONE,
@@ -292,11 +500,442 @@ pub enum ProposalCreationRight {
     ADMIN,
 }
 
+/// How an account becomes a member of the DAO.
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub enum JoinPolicy {
+    /// Anyone can join.
+    Open,
+
+    /// Joining needs a proof of at least this many governance tokens.
+    TokenThreshold(Decimal),
+
+    /// Applications wait for an AdmitMember proposal to pass.
+    Vote,
+}
+
 #[allow(non_camel_case_types)]
 #[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
 pub enum VotingType {
     ResourceHold,
     Equality,
+    // weight is the square root of the tokens, only members can vote
+    Quadratic,
+}
+
+/// What a proposal does once it is executed.
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub enum ProposalAction {
+    /// Buy bonds from the latest issue of this creator with treasury XRD.
+    PurchaseBond {
+        bond_creator_address: ComponentAddress,
+        target_xrd_amount: Decimal,
+    },
+
+    /// Mint governance tokens into the DAO's token vault.
+    MintTokens { amount: usize },
+
+    ChangePrice {
+        token_price: Decimal,
+        buy_back_price: Decimal,
+    },
+
+    /// Send treasury XRD to an account.
+    TreasuryTransfer {
+        recipient: ComponentAddress,
+        amount: Decimal,
+    },
+
+    ConfigChange(ConfigChange),
+
+    /// Approve a pending issuer application and mint its issuer badge.
+    OnboardIssuer { application_id: u64 },
+
+    /// Approve a pending membership application and mint its member badge.
+    AdmitMember { application_id: u64 },
+
+    /// Pay out an insurance claim, only executed by the InsuranceDao that filed it.
+    InsuranceClaim { claim_id: u64, amount: Decimal },
+}
+
+#[derive(ScryptoSbor, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProposalStatus {
+    /// Voting has not started yet.
+    Pending,
+    Active,
+    /// Voting ended and the proposal passed, waiting to be queued.
+    Succeeded,
+    Defeated,
+    /// Waiting for the execution delay to pass.
+    Queued,
+    Executed,
+    Cancelled,
+    /// Passed but was not executed within the execution window.
+    Expired,
+}
+
+impl ProposalStatus {
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            ProposalStatus::Defeated
+                | ProposalStatus::Executed
+                | ProposalStatus::Cancelled
+                | ProposalStatus::Expired
+        )
+    }
+
+    pub fn can_transition_to(&self, next: ProposalStatus) -> bool {
+        match (self, next) {
+            (ProposalStatus::Pending, ProposalStatus::Cancelled)
+            | (ProposalStatus::Active, ProposalStatus::Cancelled)
+            | (ProposalStatus::Succeeded, ProposalStatus::Queued)
+            | (ProposalStatus::Succeeded, ProposalStatus::Cancelled)
+            | (ProposalStatus::Queued, ProposalStatus::Executed)
+            | (ProposalStatus::Queued, ProposalStatus::Cancelled) => true,
+            // Defeated and Expired follow from time and tallies, closing the proposal only records them
+            (current, next) => *current == next && next.is_final(),
+        }
+    }
+}
+
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub enum ConfigChange {
+    ProposalCreationRight(ProposalCreationRight),
+
+    LiquidityPolicy {
+        minimum_liquidity: Decimal,
+        interval: LadderInterval,
+        number_of_rungs: u32,
+    },
+
+    PassingRule {
+        category: ProposalCategory,
+        rule: PassingRule,
+    },
+}
+
+/// The kind of a proposal's action, passing rules are set per category.
+#[derive(ScryptoSbor, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProposalCategory {
+    PurchaseBond,
+    MintTokens,
+    ChangePrice,
+    TreasuryTransfer,
+    ConfigChange,
+    OnboardIssuer,
+    AdmitMember,
+    InsuranceClaim,
+}
+
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub enum ApprovalThreshold {
+    /// More votes for than against.
+    SimpleMajority,
+
+    /// At least this percent of the votes cast must be in favour, e.g. 66.67.
+    Supermajority(Decimal),
+}
+
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub struct PassingRule {
+    pub approval: ApprovalThreshold,
+
+    /// Percent of the circulating governance tokens that must take part in the vote, 0 for no minimum.
+    pub minimum_turnout: Decimal,
+}
+
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub struct PassingCheck {
+    pub quorum_met: bool,
+    // percent of the circulating supply that voted
+    pub turnout: Decimal,
+    pub turnout_met: bool,
+    // percent of the votes cast that are in favour
+    pub approval: Decimal,
+    pub approval_met: bool,
+    pub passed: bool,
+}
+
+/// A proposal's tallies split into votes cast by voters themselves and by delegates for them.
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub struct VoteTally {
+    pub direct_for: Decimal,
+    pub direct_against: Decimal,
+    pub delegated_for: Decimal,
+    pub delegated_against: Decimal,
+    // raw tokens behind all votes for and against, direct and delegated
+    pub tokens_for: Decimal,
+    pub tokens_against: Decimal,
+    pub direct_voters: usize,
+    pub delegators: usize,
+}
+
+impl PassingRule {
+    pub fn simple_majority() -> Self {
+        PassingRule {
+            approval: ApprovalThreshold::SimpleMajority,
+            minimum_turnout: Decimal::zero(),
+        }
+    }
+
+    pub fn assert_valid(&self) {
+        if let ApprovalThreshold::Supermajority(percent) = self.approval {
+            assert!(
+                percent > dec!("50") && percent <= dec!("100"),
+                "a supermajority must be above 50 and at most 100 percent"
+            );
+        }
+
+        assert!(
+            self.minimum_turnout >= Decimal::zero() && self.minimum_turnout <= dec!("100"),
+            "minimum turnout must be between 0 and 100 percent"
+        );
+    }
+
+    pub fn evaluate(
+        &self,
+        number_of_voters: usize,
+        minimum_quorum: usize,
+        voted_for: Decimal,
+        voted_against: Decimal,
+        tokens_voted: Decimal,
+        circulating_supply: Decimal,
+    ) -> PassingCheck {
+        let quorum_met = number_of_voters >= minimum_quorum;
+
+        let turnout = if circulating_supply > Decimal::zero() {
+            tokens_voted * dec!("100") / circulating_supply
+        } else {
+            Decimal::zero()
+        };
+        let turnout_met = turnout >= self.minimum_turnout;
+
+        let votes_cast = voted_for + voted_against;
+        let approval = if votes_cast > Decimal::zero() {
+            voted_for * dec!("100") / votes_cast
+        } else {
+            Decimal::zero()
+        };
+        let approval_met = match self.approval {
+            ApprovalThreshold::SimpleMajority => voted_for > voted_against,
+            ApprovalThreshold::Supermajority(percent) => {
+                votes_cast > Decimal::zero() && approval >= percent
+            }
+        };
+
+        PassingCheck {
+            quorum_met,
+            turnout,
+            turnout_met,
+            approval,
+            approval_met,
+            passed: quorum_met && turnout_met && approval_met,
+        }
+    }
+}
+
+/// How the ballots of a proposal with several options are counted.
+#[derive(ScryptoSbor, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CountingMethod {
+    /// Each ballot names one option, the option with the most weight wins.
+    Plurality,
+
+    /// Each ballot approves any number of options, the most approved option wins.
+    Approval,
+
+    /// Ballots rank the options, the weakest option is dropped and its ballots move to their next choice until one option holds a majority.
+    InstantRunoff,
+}
+
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub struct ProposalOption {
+    pub label: String,
+    /// Executed when this option wins.
+    pub action: ProposalAction,
+}
+
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub struct Ballot {
+    // option indexes, in order of preference for instant runoff
+    pub choices: Vec<usize>,
+    pub tokens: Decimal,
+    pub weight: Decimal,
+}
+
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub struct OptionCount {
+    // weight per option in each round, plurality and approval have a single round
+    pub rounds: Vec<Vec<Decimal>>,
+    pub winner: Option<usize>,
+}
+
+impl OptionCount {
+    /// (winner, strongest other option) in the last round, what the passing rule is evaluated on.
+    pub fn decisive_votes(&self) -> (Decimal, Decimal) {
+        let last_round = match self.rounds.last() {
+            Some(last_round) => last_round,
+            None => return (Decimal::zero(), Decimal::zero()),
+        };
+
+        let strongest = |skip: Option<usize>| {
+            last_round
+                .iter()
+                .enumerate()
+                .filter(|(option, _)| Some(*option) != skip)
+                .map(|(_, weight)| *weight)
+                .max()
+                .unwrap_or(Decimal::zero())
+        };
+
+        match self.winner {
+            Some(winner) => (last_round[winner], strongest(Some(winner))),
+            // a tie, so the rule's approval check fails
+            None => (strongest(None), strongest(None)),
+        }
+    }
+}
+
+impl CountingMethod {
+    pub fn assert_valid_choices(&self, number_of_options: usize, choices: &[usize]) {
+        assert!(!choices.is_empty(), "please choose at least one option");
+
+        if *self == CountingMethod::Plurality {
+            assert!(choices.len() == 1, "plurality ballots choose exactly one option");
+        }
+
+        for (position, choice) in choices.iter().enumerate() {
+            assert!(*choice < number_of_options, "there is no option {}", choice);
+            assert!(
+                !choices[..position].contains(choice),
+                "option {} is chosen more than once",
+                choice
+            );
+        }
+    }
+
+    pub fn count(&self, number_of_options: usize, ballots: &[Ballot]) -> OptionCount {
+        match self {
+            CountingMethod::Plurality | CountingMethod::Approval => {
+                let mut tally = vec![Decimal::zero(); number_of_options];
+
+                for ballot in ballots {
+                    for choice in &ballot.choices {
+                        tally[*choice] += ballot.weight;
+                    }
+                }
+
+                let winner = Self::unique_leader(&tally, &(0..number_of_options).collect::<Vec<_>>());
+
+                OptionCount {
+                    rounds: vec![tally],
+                    winner,
+                }
+            }
+            CountingMethod::InstantRunoff => Self::instant_runoff(number_of_options, ballots),
+        }
+    }
+
+    fn instant_runoff(number_of_options: usize, ballots: &[Ballot]) -> OptionCount {
+        let mut remaining: Vec<usize> = (0..number_of_options).collect();
+        let mut rounds = Vec::new();
+
+        loop {
+            let mut tally = vec![Decimal::zero(); number_of_options];
+
+            for ballot in ballots {
+                if let Some(choice) = ballot.choices.iter().find(|choice| remaining.contains(*choice)) {
+                    tally[*choice] += ballot.weight;
+                }
+            }
+
+            let total = tally.iter().fold(Decimal::zero(), |total, weight| total + *weight);
+            let leader = Self::unique_leader(&tally, &remaining);
+
+            rounds.push(tally.clone());
+
+            if let Some(leader) = leader {
+                if tally[leader] * 2 > total || remaining.len() <= 2 {
+                    return OptionCount {
+                        rounds,
+                        winner: Some(leader),
+                    };
+                }
+            }
+
+            let lowest = remaining
+                .iter()
+                .map(|option| tally[*option])
+                .min()
+                .unwrap_or(Decimal::zero());
+
+            // everything left is tied, or only two options remain and they are tied
+            if remaining.iter().all(|option| tally[*option] == lowest) || remaining.len() <= 2 {
+                return OptionCount {
+                    rounds,
+                    winner: None,
+                };
+            }
+
+            // of the options tied for last, the one listed last is dropped
+            let dropped = *remaining
+                .iter()
+                .rev()
+                .find(|option| tally[**option] == lowest)
+                .unwrap();
+            remaining.retain(|option| *option != dropped);
+        }
+    }
+
+    // the option with the most weight, None on a tie for first or when nothing was cast
+    fn unique_leader(tally: &[Decimal], candidates: &[usize]) -> Option<usize> {
+        let highest = candidates.iter().map(|option| tally[*option]).max()?;
+
+        if highest == Decimal::zero() {
+            return None;
+        }
+
+        let mut leaders = candidates.iter().filter(|option| tally[**option] == highest);
+
+        match (leaders.next(), leaders.next()) {
+            (Some(leader), None) => Some(*leader),
+            _ => None,
+        }
+    }
+}
+
+impl ProposalAction {
+    pub fn category(&self) -> ProposalCategory {
+        match self {
+            ProposalAction::PurchaseBond { .. } => ProposalCategory::PurchaseBond,
+            ProposalAction::MintTokens { .. } => ProposalCategory::MintTokens,
+            ProposalAction::ChangePrice { .. } => ProposalCategory::ChangePrice,
+            ProposalAction::TreasuryTransfer { .. } => ProposalCategory::TreasuryTransfer,
+            ProposalAction::ConfigChange(_) => ProposalCategory::ConfigChange,
+            ProposalAction::OnboardIssuer { .. } => ProposalCategory::OnboardIssuer,
+            ProposalAction::AdmitMember { .. } => ProposalCategory::AdmitMember,
+            ProposalAction::InsuranceClaim { .. } => ProposalCategory::InsuranceClaim,
+        }
+    }
+
+    pub fn event_type(&self) -> EventType {
+        match self {
+            ProposalAction::PurchaseBond { .. } => EventType::PROPOSAL_TO_PURCHASE_BOND,
+            ProposalAction::MintTokens { .. } => EventType::PROPOSAL_TO_MINT_MORE_TOKENS,
+            ProposalAction::ChangePrice { .. } => EventType::PROPOSAL_TO_CHANGE_TOKEN_PRICE,
+            ProposalAction::TreasuryTransfer { .. } => EventType::PROPOSAL_TO_TRANSFER_FROM_TREASURY,
+            ProposalAction::ConfigChange(_) => EventType::PROPOSAL_TO_CHANGE_CONFIG,
+            ProposalAction::OnboardIssuer { .. } => EventType::PROPOSAL_TO_ONBOARD_ISSUER,
+            ProposalAction::AdmitMember { .. } => EventType::PROPOSAL_TO_ADMIT_MEMBER,
+            ProposalAction::InsuranceClaim { .. } => EventType::INSURANCE_CLAIM_FILED,
+        }
+    }
+}
+
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub enum AuctionProceedsDestination {
+    Treasury,
+    // proceeds go to the bond component the collateral was seized from, so its bondholders can redeem
+    Bondholders(ComponentAddress),
 }
 
 #[derive(ScryptoSbor, ScryptoEvent)]
@@ -316,6 +955,13 @@ pub struct ZeroCouponBondCreation {
     pub number_of_bonds: Decimal,
     pub creator_address: ComponentAddress,
     pub collateral_resource_address: ResourceAddress,
+    pub grace_period: u64,
+    pub delinquency_period: u64,
+    pub issuer_badge_id: NonFungibleLocalId,
+    pub sinking_fund_schedule: Vec<SinkingFundInstalment>,
+    pub call_option: Option<CallOption>,
+    pub put_option: Option<PutOption>,
+    pub conversion_terms: Option<ConversionTerms>,
 }
 
 #[derive(ScryptoSbor, ScryptoEvent)]
@@ -382,6 +1028,7 @@ pub struct LiquidatedCollateralEvent {
 #[derive(ScryptoSbor, ScryptoEvent)]
 pub struct CollateralLiquidationFailedEvent {
     pub bond_creator_address: ComponentAddress,
+    pub bond_status: BondStatus,
     // pub liquidated_amount: Decimal,
 }
 
@@ -503,3 +1150,575 @@ pub struct FundsNotWithdrawn {
     pub request_id : u64
 }
 
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct PriceOracleSet {
+    pub oracle_address: ComponentAddress,
+    pub maintenance_ratio: Decimal,
+    pub margin_call_window: u64
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct MarginCallEvent {
+    pub bond_creator_address: ComponentAddress,
+    pub bond_component_address: ComponentAddress,
+    pub collateral_resource_address: ResourceAddress,
+    pub collateral_amount: Decimal,
+    pub collateral_price: Decimal,
+    pub collateral_value: Decimal,
+    pub outstanding_amount: Decimal,
+    pub required_collateral_value: Decimal,
+    pub maintenance_ratio: Decimal,
+    pub margin_call_started_at: Option<u64>,
+    pub margin_call_deadline: Option<u64>
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct CollateralAuctionStarted {
+    pub auction_address: ComponentAddress,
+    pub collateral_resource_address: ResourceAddress,
+    pub amount: Decimal,
+    pub start_price: Decimal,
+    pub floor_price: Decimal,
+    pub decay_curve: DecayCurve,
+    pub duration: u64,
+    pub proceeds_destination: AuctionProceedsDestination
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct CollateralAuctionSettled {
+    pub auction_address: ComponentAddress,
+    pub collateral_resource_address: ResourceAddress,
+    pub proceeds: Decimal,
+    pub proceeds_destination: AuctionProceedsDestination,
+    pub unsold_collateral_returned: Decimal
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct BondStatusUpdated {
+    pub bond_creator_address: ComponentAddress,
+    pub bond_component_address: ComponentAddress,
+    pub previous_status: BondStatus,
+    pub current_status: BondStatus
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct IssuerApplied {
+    pub application_id: u64,
+    pub account_address: ComponentAddress,
+    pub issuer_name: String,
+    pub description: String
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct IssuerOnboardingDecision {
+    pub proposal_id: usize,
+    pub application_id: u64,
+    pub account_address: ComponentAddress,
+    pub approved: bool,
+    pub voted_for: Decimal,
+    pub voted_against: Decimal,
+    pub number_of_voters: usize,
+    pub minimum_quorum: usize,
+    pub issuer_badge_id: Option<NonFungibleLocalId>
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct SinkingFundDeposit {
+    pub bond_creator_address: ComponentAddress,
+    pub bond_component_address: ComponentAddress,
+    pub amount_deposited: Decimal,
+    pub deposited_by_issuer: Decimal,
+    pub required_to_date: Decimal,
+    pub coverage_ratio: Option<Decimal>,
+    pub bond_status: BondStatus
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct SinkingFundInstalmentMissed {
+    pub bond_creator_address: ComponentAddress,
+    pub bond_component_address: ComponentAddress,
+    pub instalment: SinkingFundInstalment,
+    pub deposited_by_issuer: Decimal,
+    pub required_to_date: Decimal,
+    pub bond_status: BondStatus
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct BondCalled {
+    pub bond_creator_address: ComponentAddress,
+    pub bond_component_address: ComponentAddress,
+    pub call_price: Decimal,
+    pub outstanding_bonds: Decimal,
+    pub amount_paid: Decimal,
+    pub collateral_released: Decimal
+}
+
+// bonds the DAO handed back before maturity, through an issuer call or a holder put
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct BondRedeemedEarly {
+    pub bond_creator_address: ComponentAddress,
+    pub bond_component_address: ComponentAddress,
+    pub bonds_redeemed: Decimal,
+    pub amount_received: Decimal,
+    pub bond_status: BondStatus
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct BondsConverted {
+    pub bond_creator_address: ComponentAddress,
+    pub bond_component_address: ComponentAddress,
+    pub bonds_converted: Decimal,
+    pub tokens_delivered: Decimal,
+    pub conversion_stats: ConversionStats
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct ConversionTokensReclaimed {
+    pub bond_creator_address: ComponentAddress,
+    pub bond_component_address: ComponentAddress,
+    pub amount_returned: Decimal
+}
+
+// how bonds left the hands of their holder
+#[derive(ScryptoSbor, Clone, Debug)]
+pub enum BondRedemptionKind {
+    Maturity,
+    Sale,
+    Call,
+    Put,
+    Conversion,
+    ForcedTransfer,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct BondIssued {
+    pub bond_component_address: ComponentAddress,
+    pub bond_resource_address: ResourceAddress,
+    pub issuer_address: ComponentAddress,
+    pub contract_identifier: String,
+    pub number_of_bonds: Decimal,
+    pub price: u64,
+    pub maturity_date: u64,
+    pub collateral_resource_address: ResourceAddress,
+    pub collateral_amount: Decimal
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct BondPurchased {
+    pub bond_component_address: ComponentAddress,
+    pub bonds_purchased: Decimal,
+    pub amount_paid: Decimal,
+    pub bonds_left: Decimal
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct BondRepaid {
+    pub bond_component_address: ComponentAddress,
+    pub deposited_by_issuer: Decimal,
+    pub repaid_at: u64,
+    pub repaid_on_time: bool
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct BondRedeemed {
+    pub bond_component_address: ComponentAddress,
+    pub redemption_kind: BondRedemptionKind,
+    pub bonds_redeemed: Decimal,
+    pub amount_paid: Decimal,
+    // XRD, or the governance token for conversions
+    pub paid_in: ResourceAddress
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct BondCollateralMovement {
+    pub bond_component_address: ComponentAddress,
+    pub collateral_resource_address: ResourceAddress,
+    pub amount: Decimal,
+    pub bond_status: BondStatus
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct BondStatusChanged {
+    pub bond_component_address: ComponentAddress,
+    pub previous_status: BondStatus,
+    pub current_status: BondStatus
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct LiquidityPolicySet {
+    pub minimum_liquidity: Decimal,
+    pub interval: LadderInterval,
+    pub number_of_rungs: u32
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct PaymentStreamUpdated {
+    pub stream_id: u64,
+    pub payment_stream: PaymentStream
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct InsurancePolicyUpdated {
+    pub policy_id: u64,
+    pub coverage_limit: Decimal,
+    pub premium_paid: Decimal,
+    pub expiry: u64
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct InsuranceClaimFiled {
+    pub claim_id: u64,
+    pub policy_id: u64,
+    pub amount: Decimal,
+    pub description: String,
+    pub evidence_links: Vec<String>,
+    pub proposal_address: ComponentAddress,
+    pub voting_ends_at: u64
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct InsuranceClaimDecision {
+    pub claim_id: u64,
+    pub policy_id: u64,
+    pub amount: Decimal,
+    pub approved: bool,
+    pub voted_for: Decimal,
+    pub voted_against: Decimal,
+    pub number_of_voters: usize,
+    pub minimum_quorum: usize
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct InsuranceClaimPaid {
+    pub claim_id: u64,
+    pub policy_id: u64,
+    pub amount_paid: Decimal,
+    // left to collect once the pool is above its reserve again
+    pub amount_owed: Decimal,
+    pub pool_balance: Decimal,
+    pub required_reserve: Decimal
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct TreasuryTransfer {
+    pub proposal_id: usize,
+    pub recipient: ComponentAddress,
+    pub amount: Decimal
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct ConfigChanged {
+    pub proposal_id: usize,
+    pub change: ConfigChange
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct ProposalStatusChanged {
+    pub proposal_id: usize,
+    pub praposal_address: ComponentAddress,
+    pub previous_status: ProposalStatus,
+    pub current_status: ProposalStatus
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct ProposalRulesNotMet {
+    pub proposal_id: usize,
+    pub praposal_address: ComponentAddress,
+    pub category: ProposalCategory,
+    pub passing_rule: PassingRule,
+    pub check: PassingCheck,
+    pub voted_for: Decimal,
+    pub voted_against: Decimal,
+    pub number_of_voters: usize,
+    pub minimum_quorum: usize
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct PassingRuleSet {
+    pub category: ProposalCategory,
+    pub rule: PassingRule
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct VoteTokensClaimedBack {
+    pub proposal_id: usize,
+    pub praposal_address: ComponentAddress,
+    pub amount: Decimal,
+    pub receipt_id: NonFungibleLocalId
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct MembershipApplied {
+    pub application_id: u64,
+    pub account_address: ComponentAddress,
+    pub display_name: String
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct MembershipDecision {
+    pub proposal_id: usize,
+    pub application_id: u64,
+    pub account_address: ComponentAddress,
+    pub approved: bool,
+    pub voted_for: Decimal,
+    pub voted_against: Decimal,
+    pub number_of_voters: usize,
+    pub minimum_quorum: usize,
+    pub member_id: Option<u64>
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct MemberJoined {
+    pub member_id: u64,
+    pub account_address: ComponentAddress,
+    pub display_name: String,
+    pub join_policy: JoinPolicy
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct MemberLeft {
+    pub member_id: u64,
+    pub account_address: ComponentAddress
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct MemberExpelled {
+    pub member_id: u64,
+    pub account_address: ComponentAddress,
+    pub reason: String
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct MemberRoleTagsSet {
+    pub member_id: u64,
+    pub role_tags: Vec<String>
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct JoinPolicySet {
+    pub policy: JoinPolicy
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct DelegationChanged {
+    pub delegator: ComponentAddress,
+    // None once the delegation is revoked
+    pub delegate: Option<ComponentAddress>,
+    // None for the delegation covering every category
+    pub category: Option<ProposalCategory>,
+    pub locked_tokens: Decimal
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct DelegatedVoteCast {
+    pub proposal_id: usize,
+    pub praposal_address: ComponentAddress,
+    pub delegate: ComponentAddress,
+    pub delegators: Vec<ComponentAddress>,
+    pub delegated_tokens: Decimal,
+    pub delegated_weight: Decimal,
+    pub againts: bool
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct DelegateOverridden {
+    pub proposal_id: usize,
+    pub praposal_address: ComponentAddress,
+    pub delegator: ComponentAddress,
+    pub delegate: ComponentAddress,
+    pub withdrawn_weight: Decimal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn supermajority(percent: Decimal) -> PassingRule {
+        PassingRule {
+            approval: ApprovalThreshold::Supermajority(percent),
+            minimum_turnout: Decimal::zero(),
+        }
+    }
+
+    // one voter, enough for a quorum of one, with all of the supply voting
+    fn evaluate(rule: &PassingRule, voted_for: Decimal, voted_against: Decimal) -> PassingCheck {
+        let tokens_voted = voted_for + voted_against;
+        rule.evaluate(1, 1, voted_for, voted_against, tokens_voted, tokens_voted)
+    }
+
+    #[test]
+    fn a_supermajority_is_met_exactly_at_its_threshold() {
+        let check = evaluate(&supermajority(dec!("75")), dec!("75"), dec!("25"));
+
+        assert_eq!(check.approval, dec!("75"));
+        assert!(check.approval_met);
+        assert!(check.passed);
+    }
+
+    #[test]
+    fn a_supermajority_is_missed_just_below_its_threshold() {
+        let check = evaluate(&supermajority(dec!("75")), dec!("74.99"), dec!("25.01"));
+
+        assert_eq!(check.approval, dec!("74.99"));
+        assert!(!check.approval_met);
+        assert!(!check.passed);
+    }
+
+    #[test]
+    fn a_unanimous_supermajority_needs_every_vote() {
+        assert!(evaluate(&supermajority(dec!("100")), dec!("10"), Decimal::zero()).passed);
+        assert!(!evaluate(&supermajority(dec!("100")), dec!("10"), dec!("0.001")).passed);
+    }
+
+    #[test]
+    fn a_supermajority_is_not_met_without_votes() {
+        let check = evaluate(&supermajority(dec!("51")), Decimal::zero(), Decimal::zero());
+
+        assert_eq!(check.approval, Decimal::zero());
+        assert!(!check.approval_met);
+    }
+
+    #[test]
+    fn a_tie_is_not_a_simple_majority() {
+        assert!(!evaluate(&PassingRule::simple_majority(), dec!("5"), dec!("5")).approval_met);
+        assert!(evaluate(&PassingRule::simple_majority(), dec!("5.1"), dec!("5")).approval_met);
+    }
+
+    #[test]
+    fn turnout_is_met_at_the_minimum() {
+        let rule = PassingRule {
+            approval: ApprovalThreshold::SimpleMajority,
+            minimum_turnout: dec!("20"),
+        };
+
+        assert!(rule.evaluate(1, 1, dec!("20"), Decimal::zero(), dec!("20"), dec!("100")).turnout_met);
+
+        let check = rule.evaluate(1, 1, dec!("19.99"), Decimal::zero(), dec!("19.99"), dec!("100"));
+        assert!(!check.turnout_met);
+        assert!(!check.passed);
+    }
+
+    #[test]
+    fn too_few_voters_miss_the_quorum() {
+        let check = PassingRule::simple_majority().evaluate(2, 3, dec!("10"), Decimal::zero(), dec!("10"), dec!("10"));
+
+        assert!(!check.quorum_met);
+        assert!(check.approval_met);
+        assert!(!check.passed);
+    }
+
+    #[test]
+    #[should_panic(expected = "a supermajority must be above 50 and at most 100 percent")]
+    fn half_is_not_a_supermajority() {
+        supermajority(dec!("50")).assert_valid();
+    }
+
+    #[test]
+    fn a_full_supermajority_is_valid() {
+        supermajority(dec!("100")).assert_valid();
+    }
+
+    fn ballot(choices: &[usize], weight: u64) -> Ballot {
+        Ballot {
+            choices: choices.to_vec(),
+            tokens: Decimal::from(weight),
+            weight: Decimal::from(weight),
+        }
+    }
+
+    fn weights(weights: &[u64]) -> Vec<Decimal> {
+        weights.iter().map(|weight| Decimal::from(*weight)).collect()
+    }
+
+    #[test]
+    fn plurality_picks_the_heaviest_option() {
+        let count = CountingMethod::Plurality.count(3, &[ballot(&[0], 2), ballot(&[1], 5), ballot(&[0], 2)]);
+
+        assert_eq!(count.rounds, vec![weights(&[4, 5, 0])]);
+        assert_eq!(count.winner, Some(1));
+        assert_eq!(count.decisive_votes(), (dec!("5"), dec!("4")));
+    }
+
+    #[test]
+    fn a_tie_for_first_has_no_winner() {
+        let count = CountingMethod::Plurality.count(2, &[ballot(&[0], 3), ballot(&[1], 3)]);
+
+        assert_eq!(count.winner, None);
+        assert_eq!(count.decisive_votes(), (dec!("3"), dec!("3")));
+    }
+
+    #[test]
+    fn nothing_cast_has_no_winner() {
+        assert_eq!(CountingMethod::Approval.count(2, &[]).winner, None);
+    }
+
+    #[test]
+    fn approval_counts_every_approved_option() {
+        let count = CountingMethod::Approval.count(3, &[ballot(&[0, 2], 3), ballot(&[2], 1), ballot(&[1, 0], 2)]);
+
+        assert_eq!(count.rounds, vec![weights(&[5, 2, 4])]);
+        assert_eq!(count.winner, Some(0));
+    }
+
+    #[test]
+    fn instant_runoff_stops_at_a_first_round_majority() {
+        let count = CountingMethod::InstantRunoff.count(3, &[ballot(&[0, 1], 6), ballot(&[1, 0], 3), ballot(&[2], 2)]);
+
+        assert_eq!(count.rounds.len(), 1);
+        assert_eq!(count.winner, Some(0));
+    }
+
+    #[test]
+    fn instant_runoff_moves_the_weakest_ballots_to_their_next_choice() {
+        let ballots = [ballot(&[0, 1], 4), ballot(&[1, 0], 3), ballot(&[2, 1], 2)];
+
+        let count = CountingMethod::InstantRunoff.count(3, &ballots);
+
+        // 4 of 9 is no majority, option 2 is dropped and its ballots go to option 1
+        assert_eq!(count.rounds, vec![weights(&[4, 3, 2]), weights(&[4, 5, 0])]);
+        assert_eq!(count.winner, Some(1));
+    }
+
+    #[test]
+    fn instant_runoff_drops_the_last_listed_of_the_options_tied_for_last() {
+        let ballots = [ballot(&[0], 4), ballot(&[1, 2], 3), ballot(&[2, 1], 3)];
+
+        let count = CountingMethod::InstantRunoff.count(3, &ballots);
+
+        // options 1 and 2 tie for last, option 2 goes and option 1 takes its ballots
+        assert_eq!(count.rounds, vec![weights(&[4, 3, 3]), weights(&[4, 6, 0])]);
+        assert_eq!(count.winner, Some(1));
+    }
+
+    #[test]
+    fn instant_runoff_with_every_option_tied_has_no_winner() {
+        let count = CountingMethod::InstantRunoff.count(3, &[ballot(&[0], 1), ballot(&[1], 1), ballot(&[2], 1)]);
+
+        assert_eq!(count.rounds.len(), 1);
+        assert_eq!(count.winner, None);
+    }
+
+    #[test]
+    fn instant_runoff_with_the_last_two_options_tied_has_no_winner() {
+        let ballots = [ballot(&[0], 3), ballot(&[1], 3), ballot(&[2, 0], 1), ballot(&[3, 1], 1)];
+
+        let count = CountingMethod::InstantRunoff.count(4, &ballots);
+
+        // options 3 and then 2 are dropped, leaving 0 and 1 on 4 each
+        assert_eq!(count.rounds.last(), Some(&weights(&[4, 4, 0, 0])));
+        assert_eq!(count.winner, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "option 1 is chosen more than once")]
+    fn a_ranking_can_not_repeat_an_option() {
+        CountingMethod::InstantRunoff.assert_valid_choices(3, &[1, 0, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "plurality ballots choose exactly one option")]
+    fn a_plurality_ballot_chooses_one_option() {
+        CountingMethod::Plurality.assert_valid_choices(3, &[0, 1]);
+    }
+}
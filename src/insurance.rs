@@ -0,0 +1,801 @@
+use scrypto::prelude::*;
+// Kept outside the blueprint, which would also copy them into its test bindings module unused.
+use crate::events::*;
+use crate::proposal::pandao_praposal::TokenWeightProposal;
+use actus_schedule::SECONDS_IN_YEAR;
+
+#[derive(ScryptoSbor, NonFungibleData, Clone, Debug)]
+pub struct InsurancePolicy {
+    pub coverage_limit: Decimal,
+    pub start_time: u64,
+    #[mutable]
+    pub expiry: u64,
+}
+
+// handed to a claim voter, the voting tokens stay in the claim's vote until it closes
+#[derive(ScryptoSbor, NonFungibleData, Clone, Debug)]
+pub struct ClaimVoteReceipt {
+    pub claim_id: u64,
+    pub proposal_address: ComponentAddress,
+    pub amount: Decimal,
+    pub against: bool,
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct PolicyRecord {
+    pub coverage_limit: Decimal,
+    pub premiums_paid: Decimal,
+    pub start_time: u64,
+    pub expiry: u64,
+    // approved claims against the policy, paid out or still owed
+    pub claimed: Decimal,
+    // claims against the policy that are still being voted on
+    pub pending: Decimal,
+}
+
+impl PolicyRecord {
+    pub fn is_active(&self, now: u64) -> bool {
+        now <= self.expiry
+    }
+
+    pub fn remaining_coverage(&self) -> Decimal {
+        (self.coverage_limit - self.claimed - self.pending).max(Decimal::zero())
+    }
+}
+
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub enum ClaimVoters {
+    // policy holders, weighted by the member tokens they got for their premiums
+    Members,
+    // holders of an assessor badge, one vote each
+    Assessors,
+}
+
+#[derive(ScryptoSbor, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClaimStatus {
+    Voting,
+    Approved,
+    Rejected,
+    Paid,
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct Claim {
+    pub policy_id: u64,
+    // account that filed the claim, it can not vote on it
+    pub claimant_address: ComponentAddress,
+    pub amount: Decimal,
+    pub description: String,
+    pub evidence_links: Vec<String>,
+    pub filed_at: u64,
+    pub proposal_address: ComponentAddress,
+    pub status: ClaimStatus,
+    pub amount_paid: Decimal,
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct PoolStatus {
+    pub pool_balance: Decimal,
+    pub active_policies: usize,
+    // coverage still available on policies that have not expired
+    pub active_coverage: Decimal,
+    // approved claims not paid out yet
+    pub claims_owed: Decimal,
+    pub reserve_ratio: Decimal,
+    pub required_reserve: Decimal,
+}
+
+#[blueprint]
+#[events(PandaoEvent, DaoEvent, DaoType, EventType)]
+mod insurance_dao {
+    enable_method_auth! {
+        methods {
+            buy_policy => PUBLIC;
+            renew_policy => PUBLIC;
+            file_claim => PUBLIC;
+            vote_on_claim => PUBLIC;
+            claim_back_vote_tokens => PUBLIC;
+            settle_claim => PUBLIC;
+            collect_claim_payout => PUBLIC;
+            get_premium => PUBLIC;
+            get_policy => PUBLIC;
+            get_claim => PUBLIC;
+            get_claims => PUBLIC;
+            get_pool_status => PUBLIC;
+            mint_assessor_badges => restrict_to: [OWNER];
+            set_premium_rate => restrict_to: [OWNER];
+            set_reserve_ratio => restrict_to: [OWNER];
+            set_claim_voting => restrict_to: [OWNER];
+        }
+    }
+
+    pub struct InsuranceDao {
+        pub organization_name: String,
+
+        /// Premiums of every member, claims are paid out of it.
+        pub pool: Vault,
+
+        pub owner_token_address: ResourceAddress,
+
+        pub policy_resource_manager: NonFungibleResourceManager,
+
+        /// Minted to policy holders, one per XRD of premium paid.
+        pub member_token_resource_manager: FungibleResourceManager,
+
+        pub assessor_badge_resource_manager: FungibleResourceManager,
+
+        pub vote_receipt_resource_manager: NonFungibleResourceManager,
+
+        pub vote_receipt_counter: u64,
+
+        /// Yearly premium as a fraction of the coverage limit.
+        pub premium_rate: Decimal,
+
+        /// Share of the coverage left on the other active policies that must stay in the pool after a payout.
+        pub reserve_ratio: Decimal,
+
+        pub claim_voters: ClaimVoters,
+
+        pub claim_quorum: u8,
+
+        /// Seconds a claim stays open for voting.
+        pub claim_voting_period: u64,
+
+        pub policies: HashMap<u64, PolicyRecord>,
+
+        pub policy_counter: u64,
+
+        pub claims: HashMap<u64, Claim>,
+
+        pub claim_counter: u64,
+    }
+
+    impl InsuranceDao {
+
+        pub fn instantiate_insurance_dao(
+            organization_name: String,
+            premium_rate: Decimal,
+            reserve_ratio: Decimal,
+            claim_voters: ClaimVoters,
+            claim_quorum: u8,
+            claim_voting_period: u64,
+        ) -> (Global<InsuranceDao>, Bucket) {
+            assert!(premium_rate > Decimal::zero(), "premium rate must be greater than zero");
+            assert!(
+                reserve_ratio >= Decimal::zero() && reserve_ratio <= Decimal::one(),
+                "reserve ratio must be between 0 and 1"
+            );
+            assert!(claim_voting_period > 0, "claim voting period must be greater than zero");
+
+            let (address_reservation, component_address) =
+                Runtime::allocate_component_address(InsuranceDao::blueprint_id());
+
+            let owner_badge: Bucket = ResourceBuilder::new_fungible(OwnerRole::None)
+                .divisibility(DIVISIBILITY_NONE)
+                .metadata(metadata!(
+                    init {
+                        "name" => format!("{}'s owner badge", &organization_name), locked;
+                    }
+                ))
+                .mint_initial_supply(1)
+                .into();
+
+            let policy_resource_manager: NonFungibleResourceManager =
+                ResourceBuilder::new_integer_non_fungible::<InsurancePolicy>(OwnerRole::None)
+                    .metadata(metadata!(
+                        init {
+                            "name" => format!("{} coverage policy", &organization_name), locked;
+                        }
+                    ))
+                    .mint_roles(mint_roles! {
+                        minter => rule!(require(global_caller(component_address)));
+                        minter_updater => rule!(deny_all);
+                    })
+                    .non_fungible_data_update_roles(non_fungible_data_update_roles! {
+                        non_fungible_data_updater => rule!(require(global_caller(component_address)));
+                        non_fungible_data_updater_updater => rule!(deny_all);
+                    })
+                    .create_with_no_initial_supply();
+
+            let member_token_resource_manager: FungibleResourceManager =
+                ResourceBuilder::new_fungible(OwnerRole::None)
+                    .metadata(metadata!(
+                        init {
+                            "name" => format!("{} member token", &organization_name), locked;
+                        }
+                    ))
+                    .mint_roles(mint_roles! {
+                        minter => rule!(require(global_caller(component_address)));
+                        minter_updater => rule!(deny_all);
+                    })
+                    .create_with_no_initial_supply();
+
+            let assessor_badge_resource_manager: FungibleResourceManager =
+                ResourceBuilder::new_fungible(OwnerRole::None)
+                    .divisibility(DIVISIBILITY_NONE)
+                    .metadata(metadata!(
+                        init {
+                            "name" => format!("{} claim assessor badge", &organization_name), locked;
+                        }
+                    ))
+                    .mint_roles(mint_roles! {
+                        minter => rule!(require(global_caller(component_address)));
+                        minter_updater => rule!(deny_all);
+                    })
+                    .burn_roles(burn_roles! {
+                        burner => rule!(require(owner_badge.resource_address()));
+                        burner_updater => rule!(deny_all);
+                    })
+                    .create_with_no_initial_supply();
+
+            let vote_receipt_resource_manager: NonFungibleResourceManager =
+                ResourceBuilder::new_integer_non_fungible::<ClaimVoteReceipt>(OwnerRole::None)
+                    .metadata(metadata!(
+                        init {
+                            "name" => format!("{} claim vote receipt", &organization_name), locked;
+                        }
+                    ))
+                    .mint_roles(mint_roles! {
+                        minter => rule!(require(global_caller(component_address)));
+                        minter_updater => rule!(deny_all);
+                    })
+                    .burn_roles(burn_roles! {
+                        burner => rule!(require(global_caller(component_address)));
+                        burner_updater => rule!(deny_all);
+                    })
+                    .create_with_no_initial_supply();
+
+            let component = Self {
+                organization_name,
+                pool: Vault::new(XRD),
+                owner_token_address: owner_badge.resource_address(),
+                policy_resource_manager,
+                member_token_resource_manager,
+                assessor_badge_resource_manager,
+                vote_receipt_resource_manager,
+                vote_receipt_counter: 0,
+                premium_rate,
+                reserve_ratio,
+                claim_voters,
+                claim_quorum,
+                claim_voting_period,
+                policies: HashMap::new(),
+                policy_counter: 0,
+                claims: HashMap::new(),
+                claim_counter: 0,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Fixed(rule!(require(
+                owner_badge.resource_address()
+            ))))
+            .with_address(address_reservation)
+            .globalize();
+
+            (component, owner_badge)
+        }
+
+        fn emit_insurance_event(event_type: EventType, meta_data: DaoEvent) {
+            Runtime::emit_event(PandaoEvent {
+                event_type,
+                dao_type: DaoType::Insurance,
+                component_address: Runtime::global_address(),
+                meta_data,
+            });
+        }
+
+        fn authenticate_account(account: &Global<Account>) -> ComponentAddress {
+            // the transaction must be signed by the owner of the account
+            let owner_role_of_account = account.get_owner_role();
+            Runtime::assert_access_rule(owner_role_of_account.rule);
+            account.address()
+        }
+
+        fn now() -> u64 {
+            let now: Instant = Clock::current_time_rounded_to_seconds();
+            now.seconds_since_unix_epoch as u64
+        }
+
+        fn check_policy(&self, policy: Proof) -> u64 {
+            let policy = policy
+                .check_with_message(
+                    self.policy_resource_manager.address(),
+                    "please present a coverage policy of this insurance DAO",
+                )
+                .as_non_fungible()
+                .non_fungible::<InsurancePolicy>();
+
+            match policy.local_id() {
+                NonFungibleLocalId::Integer(id) => id.value(),
+                _ => panic!("unexpected policy id"),
+            }
+        }
+
+        // premium for `coverage_limit` of cover over `duration` seconds
+        pub fn get_premium(&self, coverage_limit: Decimal, duration: u64) -> Decimal {
+            coverage_limit * self.premium_rate * Decimal::from(duration)
+                / Decimal::from(SECONDS_IN_YEAR)
+        }
+
+        // what the pool must keep for every active policy except `excluded_policy_id`
+        fn required_reserve(&self, excluded_policy_id: Option<u64>) -> Decimal {
+            let now = Self::now();
+
+            let coverage = self
+                .policies
+                .iter()
+                .filter(|(policy_id, policy)| {
+                    Some(**policy_id) != excluded_policy_id && policy.is_active(now)
+                })
+                .fold(Decimal::zero(), |total, (_, policy)| {
+                    total + policy.remaining_coverage()
+                });
+
+            coverage * self.reserve_ratio
+        }
+
+        /// Pays the premium and mints a coverage policy NFT.
+        /// Returns the policy, the member tokens earned with the premium and any change.
+        pub fn buy_policy(
+            &mut self,
+            mut payment: Bucket,
+            coverage_limit: Decimal,
+            duration: u64,
+        ) -> (Bucket, Bucket, Bucket) {
+            assert_eq!(payment.resource_address(), XRD, "premiums are paid in XRD");
+            assert!(coverage_limit > Decimal::zero(), "coverage limit must be greater than zero");
+            assert!(duration > 0, "policy duration must be greater than zero");
+
+            let premium = self.get_premium(coverage_limit, duration);
+
+            assert!(payment.amount() >= premium, "you are paying an insufficient premium");
+
+            self.pool.put(payment.take(premium));
+
+            let start_time = Self::now();
+            let expiry = start_time + duration;
+
+            self.policy_counter += 1;
+            let policy_id = self.policy_counter;
+
+            let policy = self.policy_resource_manager.mint_non_fungible(
+                &NonFungibleLocalId::integer(policy_id),
+                InsurancePolicy {
+                    coverage_limit,
+                    start_time,
+                    expiry,
+                },
+            );
+
+            self.policies.insert(
+                policy_id,
+                PolicyRecord {
+                    coverage_limit,
+                    premiums_paid: premium,
+                    start_time,
+                    expiry,
+                    claimed: Decimal::zero(),
+                    pending: Decimal::zero(),
+                },
+            );
+
+            let member_tokens = self.member_token_resource_manager.mint(premium);
+
+            Self::emit_insurance_event(
+                EventType::INSURANCE_POLICY_PURCHASED,
+                DaoEvent::InsurancePolicyUpdated(InsurancePolicyUpdated {
+                    policy_id,
+                    coverage_limit,
+                    premium_paid: premium,
+                    expiry,
+                }),
+            );
+
+            (policy.into(), member_tokens.into(), payment)
+        }
+
+        /// Extends the policy by `duration` seconds from its expiry, or from now if it already expired.
+        pub fn renew_policy(&mut self, policy: Proof, mut payment: Bucket, duration: u64) -> (Bucket, Bucket) {
+            let policy_id = self.check_policy(policy);
+
+            assert_eq!(payment.resource_address(), XRD, "premiums are paid in XRD");
+            assert!(duration > 0, "policy duration must be greater than zero");
+
+            let record = self.policies.get(&policy_id).expect("no policy found with the given id").clone();
+
+            let premium = self.get_premium(record.coverage_limit, duration);
+
+            assert!(payment.amount() >= premium, "you are paying an insufficient premium");
+
+            self.pool.put(payment.take(premium));
+
+            let expiry = record.expiry.max(Self::now()) + duration;
+
+            let record = self.policies.get_mut(&policy_id).unwrap();
+            record.expiry = expiry;
+            record.premiums_paid += premium;
+
+            self.policy_resource_manager.update_non_fungible_data(
+                &NonFungibleLocalId::integer(policy_id),
+                "expiry",
+                expiry,
+            );
+
+            let member_tokens = self.member_token_resource_manager.mint(premium);
+
+            Self::emit_insurance_event(
+                EventType::INSURANCE_POLICY_RENEWED,
+                DaoEvent::InsurancePolicyUpdated(InsurancePolicyUpdated {
+                    policy_id,
+                    coverage_limit: record.coverage_limit,
+                    premium_paid: premium,
+                    expiry,
+                }),
+            );
+
+            (member_tokens.into(), payment)
+        }
+
+        /// Files a claim against an active policy and opens a vote on it.
+        pub fn file_claim(
+            &mut self,
+            policy: Proof,
+            amount: Decimal,
+            description: String,
+            evidence_links: Vec<String>,
+            account: Global<Account>,
+        ) -> u64 {
+            let policy_id = self.check_policy(policy);
+            let claimant_address = Self::authenticate_account(&account);
+            let now = Self::now();
+
+            assert!(amount > Decimal::zero(), "claim amount must be greater than zero");
+            assert!(!evidence_links.is_empty(), "a claim needs at least one evidence link");
+
+            let record = self.policies.get_mut(&policy_id).expect("no policy found with the given id");
+
+            assert!(record.is_active(now), "the policy has expired");
+            assert!(
+                amount <= record.remaining_coverage(),
+                "claim amount is above the coverage left on the policy"
+            );
+
+            record.pending += amount;
+
+            self.claim_counter += 1;
+            let claim_id = self.claim_counter;
+
+            let (voter_badge_address, voting_type) = match self.claim_voters {
+                ClaimVoters::Members => (
+                    self.member_token_resource_manager.address(),
+                    VotingType::ResourceHold,
+                ),
+                ClaimVoters::Assessors => (
+                    self.assessor_badge_resource_manager.address(),
+                    VotingType::Equality,
+                ),
+            };
+
+            let start_time = UtcDateTime::from_instant(&Instant::new(now as i64)).unwrap();
+            let end_time =
+                UtcDateTime::from_instant(&Instant::new((now + self.claim_voting_period) as i64)).unwrap();
+
+            let (proposal, _) = TokenWeightProposal::new(
+                format!("{} claim #{}", self.organization_name, claim_id),
+                description.clone(),
+                self.claim_quorum,
+                start_time,
+                end_time,
+                self.owner_token_address,
+                voter_badge_address,
+                None,
+                voting_type,
+                ProposalAction::InsuranceClaim { claim_id, amount },
+                Vec::new(),
+                None,
+                0,
+                // a claim can be settled any time after its vote
+                u64::MAX,
+                Runtime::global_address(),
+                PassingRule::simple_majority(),
+                ResourceManager::from(voter_badge_address)
+                    .total_supply()
+                    .unwrap_or(Decimal::zero()),
+            );
+
+            self.claims.insert(
+                claim_id,
+                Claim {
+                    policy_id,
+                    claimant_address,
+                    amount,
+                    description: description.clone(),
+                    evidence_links: evidence_links.clone(),
+                    filed_at: now,
+                    proposal_address: proposal.address(),
+                    status: ClaimStatus::Voting,
+                    amount_paid: Decimal::zero(),
+                },
+            );
+
+            Self::emit_insurance_event(
+                EventType::INSURANCE_CLAIM_FILED,
+                DaoEvent::InsuranceClaimFiled(InsuranceClaimFiled {
+                    claim_id,
+                    policy_id,
+                    amount,
+                    description,
+                    evidence_links,
+                    proposal_address: proposal.address(),
+                    voting_ends_at: now + self.claim_voting_period,
+                }),
+            );
+
+            claim_id
+        }
+
+        pub fn vote_on_claim(
+            &mut self,
+            claim_id: u64,
+            token: Bucket,
+            against: bool,
+            account: Global<Account>,
+        ) -> Bucket {
+            let your_address = Self::authenticate_account(&account);
+
+            let claim = self.claims.get(&claim_id).expect("no claim found with the given id");
+
+            assert!(claim.status == ClaimStatus::Voting, "voting on this claim has closed");
+            assert!(
+                claim.claimant_address != your_address,
+                "the claimant can not vote on its own claim"
+            );
+
+            let proposal: Global<TokenWeightProposal> = claim.proposal_address.into();
+
+            assert!(proposal.is_open(), "voting on this claim has closed");
+
+            assert!(
+                !proposal.get_vote_caster_addresses().contains(&your_address),
+                "You have already voted on this claim."
+            );
+
+            let event_metadata = ProposalVote {
+                praposal_address: claim.proposal_address,
+                voting_amount: token.amount(),
+                voting_weight: proposal.vote_weight(token.amount()),
+                againts: against,
+                choices: Vec::new(),
+                voter_address: your_address,
+                proposal_id: claim_id as usize,
+            };
+
+            let amount = token.amount();
+
+            proposal.vote(token, against);
+
+            proposal.set_vote_caster_address(your_address);
+
+            Self::emit_insurance_event(EventType::VOTE, DaoEvent::PraposalVote(event_metadata));
+
+            self.vote_receipt_counter += 1;
+
+            self.vote_receipt_resource_manager
+                .mint_non_fungible(
+                    &NonFungibleLocalId::integer(self.vote_receipt_counter),
+                    ClaimVoteReceipt {
+                        claim_id,
+                        proposal_address: claim.proposal_address,
+                        amount,
+                        against,
+                    },
+                )
+                .into()
+        }
+
+        /// Hands back the voting tokens behind a claim vote receipt once voting on the claim has ended.
+        pub fn claim_back_vote_tokens(&mut self, vote_receipt: Bucket) -> Bucket {
+            assert_eq!(
+                vote_receipt.resource_address(),
+                self.vote_receipt_resource_manager.address(),
+                "please supply a claim vote receipt of this insurance DAO"
+            );
+
+            let vote_receipt = vote_receipt.as_non_fungible();
+            let receipt = vote_receipt.non_fungible::<ClaimVoteReceipt>();
+            let receipt_id = receipt.local_id().clone();
+            let receipt_data = receipt.data();
+
+            let proposal: Global<TokenWeightProposal> = receipt_data.proposal_address.into();
+            let tokens = proposal.release_escrow(receipt_data.amount);
+
+            vote_receipt.burn();
+
+            Self::emit_insurance_event(
+                EventType::VOTE_TOKENS_CLAIMED_BACK,
+                DaoEvent::VoteTokensClaimedBack(VoteTokensClaimedBack {
+                    proposal_id: receipt_data.claim_id as usize,
+                    praposal_address: receipt_data.proposal_address,
+                    amount: receipt_data.amount,
+                    receipt_id,
+                }),
+            );
+
+            tokens
+        }
+
+        /// Closes the vote on a claim once its voting period is over.
+        pub fn settle_claim(&mut self, claim_id: u64) -> Result<String, String> {
+            let claim = self
+                .claims
+                .get(&claim_id)
+                .ok_or(format!("no claim found with id : {claim_id}"))?
+                .clone();
+
+            if claim.status != ClaimStatus::Voting {
+                return Err(format!("claim {claim_id} has already been settled"));
+            }
+
+            let proposal: Global<TokenWeightProposal> = claim.proposal_address.into();
+
+            let approved = match proposal.get_status() {
+                ProposalStatus::Succeeded => {
+                    proposal.queue();
+                    proposal.mark_executed();
+                    true
+                }
+                ProposalStatus::Defeated => {
+                    proposal.close();
+                    false
+                }
+                _ => panic!("a claim can only be settled after its voting period"),
+            };
+
+            let number_of_voters = proposal.get_number_of_voters();
+            let minimum_quorum = proposal.get_minimum_quorum();
+            let (voted_for, voted_against) = proposal.get_votes();
+
+            let policy = self.policies.get_mut(&claim.policy_id).unwrap();
+            policy.pending -= claim.amount;
+            if approved {
+                policy.claimed += claim.amount;
+            }
+
+            let status = if approved {
+                ClaimStatus::Approved
+            } else {
+                ClaimStatus::Rejected
+            };
+            self.claims.get_mut(&claim_id).unwrap().status = status;
+
+            Self::emit_insurance_event(
+                if approved {
+                    EventType::INSURANCE_CLAIM_APPROVED
+                } else {
+                    EventType::INSURANCE_CLAIM_REJECTED
+                },
+                DaoEvent::InsuranceClaimDecision(InsuranceClaimDecision {
+                    claim_id,
+                    policy_id: claim.policy_id,
+                    amount: claim.amount,
+                    approved,
+                    voted_for,
+                    voted_against,
+                    number_of_voters,
+                    minimum_quorum,
+                }),
+            );
+
+            if approved {
+                Ok(format!("claim {claim_id} approved"))
+            } else {
+                Err(format!("claim {claim_id} was rejected by the vote"))
+            }
+        }
+
+        /// Pays out an approved claim to the policy holder.
+        /// Only what the pool can spare above the reserve of the other active policies is paid,
+        /// the rest stays owed and can be collected once the pool has grown.
+        pub fn collect_claim_payout(&mut self, policy: Proof, claim_id: u64) -> Bucket {
+            let policy_id = self.check_policy(policy);
+
+            let claim = self.claims.get(&claim_id).expect("no claim found with the given id").clone();
+
+            assert_eq!(claim.policy_id, policy_id, "the claim was not filed against this policy");
+            assert!(claim.status == ClaimStatus::Approved, "the claim is not approved or already paid");
+
+            let required_reserve = self.required_reserve(Some(policy_id));
+            let available = (self.pool.amount() - required_reserve).max(Decimal::zero());
+            let owed = claim.amount - claim.amount_paid;
+            let payout_amount = owed.min(available);
+
+            assert!(
+                payout_amount > Decimal::zero(),
+                "the pool cannot pay this claim without going below its reserve"
+            );
+
+            let payout = self.pool.take(payout_amount);
+
+            let claim = self.claims.get_mut(&claim_id).unwrap();
+            claim.amount_paid += payout_amount;
+            if claim.amount_paid == claim.amount {
+                claim.status = ClaimStatus::Paid;
+            }
+
+            Self::emit_insurance_event(
+                EventType::INSURANCE_CLAIM_PAID,
+                DaoEvent::InsuranceClaimPaid(InsuranceClaimPaid {
+                    claim_id,
+                    policy_id,
+                    amount_paid: payout_amount,
+                    amount_owed: owed - payout_amount,
+                    pool_balance: self.pool.amount(),
+                    required_reserve,
+                }),
+            );
+
+            payout
+        }
+
+        pub fn get_policy(&self, policy_id: u64) -> PolicyRecord {
+            self.policies.get(&policy_id).expect("no policy found with the given id").clone()
+        }
+
+        pub fn get_claim(&self, claim_id: u64) -> Claim {
+            self.claims.get(&claim_id).expect("no claim found with the given id").clone()
+        }
+
+        pub fn get_claims(&self) -> HashMap<u64, Claim> {
+            self.claims.clone()
+        }
+
+        pub fn get_pool_status(&self) -> PoolStatus {
+            let now = Self::now();
+
+            let active_policies: Vec<&PolicyRecord> = self
+                .policies
+                .values()
+                .filter(|policy| policy.is_active(now))
+                .collect();
+
+            PoolStatus {
+                pool_balance: self.pool.amount(),
+                active_policies: active_policies.len(),
+                active_coverage: active_policies
+                    .iter()
+                    .fold(Decimal::zero(), |total, policy| total + policy.remaining_coverage()),
+                claims_owed: self
+                    .claims
+                    .values()
+                    .filter(|claim| claim.status == ClaimStatus::Approved)
+                    .fold(Decimal::zero(), |total, claim| total + claim.amount - claim.amount_paid),
+                reserve_ratio: self.reserve_ratio,
+                required_reserve: self.required_reserve(None),
+            }
+        }
+
+        pub fn mint_assessor_badges(&mut self, amount: Decimal) -> Bucket {
+            self.assessor_badge_resource_manager.mint(amount).into()
+        }
+
+        pub fn set_premium_rate(&mut self, premium_rate: Decimal) {
+            assert!(premium_rate > Decimal::zero(), "premium rate must be greater than zero");
+            self.premium_rate = premium_rate;
+        }
+
+        pub fn set_reserve_ratio(&mut self, reserve_ratio: Decimal) {
+            assert!(
+                reserve_ratio >= Decimal::zero() && reserve_ratio <= Decimal::one(),
+                "reserve ratio must be between 0 and 1"
+            );
+            self.reserve_ratio = reserve_ratio;
+        }
+
+        // only applies to claims filed afterwards
+        pub fn set_claim_voting(&mut self, claim_voters: ClaimVoters, claim_quorum: u8, claim_voting_period: u64) {
+            assert!(claim_voting_period > 0, "claim voting period must be greater than zero");
+            self.claim_voters = claim_voters;
+            self.claim_quorum = claim_quorum;
+            self.claim_voting_period = claim_voting_period;
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use scrypto::prelude::*;
+
+#[derive(ScryptoSbor, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BondOutcome {
+    Outstanding,
+    RepaidOnTime,
+    RepaidLate,
+    Defaulted,
+    CollateralLiquidated,
+}
+
+impl BondOutcome {
+    // how much a settled bond counts towards the issuer's reputation, out of 1
+    fn weight(&self) -> Option<Decimal> {
+        match self {
+            BondOutcome::Outstanding => None,
+            BondOutcome::RepaidOnTime => Some(Decimal::one()),
+            BondOutcome::RepaidLate => Some(dec!("0.6")),
+            BondOutcome::Defaulted => Some(dec!("0.2")),
+            BondOutcome::CollateralLiquidated => Some(Decimal::zero()),
+        }
+    }
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct IssuedBondRecord {
+    pub bond_component_address: ComponentAddress,
+    pub contract_identifier: String,
+    // XRD the issuer asked for: price * number of bonds
+    pub amount_issued: Decimal,
+    pub maturity_date: u64,
+    pub outcome: BondOutcome,
+    pub amount_repaid: Decimal,
+    pub collateral_liquidated: Decimal,
+}
+
+#[derive(ScryptoSbor, Clone, Debug, Default)]
+pub struct IssuerRecord {
+    pub bonds: Vec<IssuedBondRecord>,
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct IssuerReputation {
+    pub issuer_address: ComponentAddress,
+    pub bonds_issued: usize,
+    pub outstanding: usize,
+    pub repaid_on_time: usize,
+    pub repaid_late: usize,
+    pub defaulted: usize,
+    pub collateral_liquidated: usize,
+    pub total_amount_issued: Decimal,
+    pub total_amount_repaid: Decimal,
+    // 0 - 100, 50 for an issuer without any settled bond
+    pub score: Decimal,
+}
+
+impl IssuerRecord {
+    pub fn add_bond(&mut self, record: IssuedBondRecord) {
+        self.bonds.push(record);
+    }
+
+    pub fn get_bond_mut(
+        &mut self,
+        bond_component_address: ComponentAddress,
+    ) -> Option<&mut IssuedBondRecord> {
+        self.bonds
+            .iter_mut()
+            .find(|bond| bond.bond_component_address == bond_component_address)
+    }
+
+    // principal weighted average of the outcome weights of all settled bonds
+    pub fn reputation_score(&self) -> Decimal {
+        let mut weighted_sum = Decimal::zero();
+        let mut total_weight = Decimal::zero();
+
+        for bond in &self.bonds {
+            if let Some(outcome_weight) = bond.outcome.weight() {
+                let principal = bond.amount_issued.max(Decimal::one());
+                weighted_sum += outcome_weight * principal;
+                total_weight += principal;
+            }
+        }
+
+        if total_weight == Decimal::zero() {
+            dec!("50")
+        } else {
+            weighted_sum * dec!("100") / total_weight
+        }
+    }
+
+    pub fn reputation(&self, issuer_address: ComponentAddress) -> IssuerReputation {
+        let count = |outcome: BondOutcome| {
+            self.bonds
+                .iter()
+                .filter(|bond| bond.outcome == outcome)
+                .count()
+        };
+
+        IssuerReputation {
+            issuer_address,
+            bonds_issued: self.bonds.len(),
+            outstanding: count(BondOutcome::Outstanding),
+            repaid_on_time: count(BondOutcome::RepaidOnTime),
+            repaid_late: count(BondOutcome::RepaidLate),
+            defaulted: count(BondOutcome::Defaulted),
+            collateral_liquidated: count(BondOutcome::CollateralLiquidated),
+            total_amount_issued: self
+                .bonds
+                .iter()
+                .fold(Decimal::zero(), |total, bond| total + bond.amount_issued),
+            total_amount_repaid: self
+                .bonds
+                .iter()
+                .fold(Decimal::zero(), |total, bond| total + bond.amount_repaid),
+            score: self.reputation_score(),
+        }
+    }
+}
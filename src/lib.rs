@@ -1,9 +1,28 @@
 // blue print for
+// blueprint functions take their arguments straight from transaction manifests, and a few
+// methods keep the mixed-case names manifests already call them by
+#![allow(clippy::too_many_arguments, non_snake_case)]
 mod events;
 use crate::events::*;
 mod proposal;
 use scrypto::prelude::*;
 mod zerocouponbond;
+mod oracle;
+mod auction;
+mod insurance;
+mod issuer_registry;
+mod portfolio;
+mod treasury;
+use actus_schedule as schedule;
+// Imported here rather than inside the blueprint: the blueprint macro copies its `use` items into
+// the generated test bindings module too, where only the method bodies' imports go unused.
+use auction::dutch_auction::DutchAuction;
+use issuer_registry::{BondOutcome, IssuedBondRecord};
+use oracle::price_oracle::PriceOracle;
+use portfolio::BondHolding;
+use proposal::pandao_praposal::TokenWeightProposal;
+use treasury::{CashFlow, CashFlowKind};
+use zerocouponbond::zerocouponbond::ZeroCouponBond;
 
 #[derive(ScryptoSbor, NonFungibleData, Clone)]
 struct ExecutiveBadge {
@@ -11,6 +30,104 @@ struct ExecutiveBadge {
     executive_name: String,
 }
 
+#[derive(ScryptoSbor, NonFungibleData, Clone)]
+struct IssuerBadge {
+    issuer_name: String,
+    account_address: ComponentAddress,
+    approved_at: u64,
+}
+
+// handed out for voting tokens locked in a proposal, redeemed for the tokens once voting has ended
+#[derive(ScryptoSbor, NonFungibleData, Clone)]
+struct VoteReceipt {
+    proposal_id: usize,
+    praposal_address: ComponentAddress,
+    amount: Decimal,
+    against: bool,
+    // empty on yes/no proposals
+    choices: Vec<usize>,
+}
+
+// proof of membership, the member registry decides whether it is still valid
+#[derive(ScryptoSbor, NonFungibleData, Clone)]
+struct MemberBadge {
+    display_name: String,
+    account_address: ComponentAddress,
+    joined_at: u64,
+    #[mutable]
+    role_tags: Vec<String>,
+}
+
+// badge id recorded on bonds the DAO issues itself, issuer applications start at 1
+const DAO_ISSUER_BADGE_ID: u64 = 0;
+
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub enum IssuerApplicationStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+#[derive(ScryptoSbor, Clone)]
+pub struct IssuerApplication {
+    account_address: ComponentAddress,
+    issuer_name: String,
+    description: String,
+    applied_at: u64,
+    status: IssuerApplicationStatus,
+}
+
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub enum MemberStatus {
+    Active,
+    Left,
+    Expelled,
+}
+
+#[derive(ScryptoSbor, Clone)]
+pub struct MemberRecord {
+    account_address: ComponentAddress,
+    display_name: String,
+    joined_at: u64,
+    status: MemberStatus,
+}
+
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub enum MembershipApplicationStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+#[derive(ScryptoSbor, Clone)]
+pub struct MembershipApplication {
+    account_address: ComponentAddress,
+    display_name: String,
+    applied_at: u64,
+    status: MembershipApplicationStatus,
+}
+
+#[derive(ScryptoSbor, Clone)]
+pub struct Delegator {
+    locked_tokens: Decimal,
+    // used for every category without a delegate of its own
+    delegate: Option<ComponentAddress>,
+    category_delegates: HashMap<ProposalCategory, ComponentAddress>,
+}
+
+impl Delegator {
+    fn delegate_for(&self, category: ProposalCategory) -> Option<ComponentAddress> {
+        self.category_delegates.get(&category).copied().or(self.delegate)
+    }
+}
+
+#[derive(ScryptoSbor, Clone)]
+pub struct DelegatedVote {
+    delegate: ComponentAddress,
+    tokens: Decimal,
+    against: bool,
+}
+
 #[derive(ScryptoSbor, Clone)]
 pub struct ApprovalDetails {
     approval_giver_addresses: Vec<ComponentAddress>,
@@ -19,6 +136,18 @@ pub struct ApprovalDetails {
     denials: u32,
 }
 
+#[derive(ScryptoSbor, Clone)]
+pub struct WithdrawalRequest {
+    requester_address: ComponentAddress,
+    requested_amount: Decimal,
+}
+
+#[derive(ScryptoSbor, Clone)]
+pub struct CollateralAuction {
+    collateral_resource_address: ResourceAddress,
+    proceeds_destination: AuctionProceedsDestination,
+}
+
 #[derive(ScryptoSbor, Clone)] // Enum to represent approval responses
 pub enum ApprovalResponse {
     Approve,
@@ -32,11 +161,15 @@ mod radixdao {
     
 
     use super::*;
-    use crate::zerocouponbond::BondDetails;
-    use proposal::pandao_praposal::TokenWeightProposal;
-    use scrypto::address;
+    use crate::zerocouponbond::{
+        BondDetails, BondStatus, CallPutTerms, ConversionStats, ConversionTerms, RepaymentTerms,
+        SinkingFundStatus,
+    };
     use std::collections::HashMap;
-    use zerocouponbond::zerocouponbond::ZeroCouponBond;
+    use crate::auction::{AuctionDetails, DecayCurve};
+    use crate::portfolio::PortfolioReport;
+    use crate::treasury::{LadderInterval, LiquidityLadder, PaymentStream};
+    use crate::issuer_registry::{IssuerRecord, IssuerReputation};
 
 
     enable_method_auth! {
@@ -54,8 +187,20 @@ mod radixdao {
             obtain_community_token => PUBLIC;
             request_withdrawal => PUBLIC;
             create_praposal => PUBLIC;
+            create_praposal_with_options => PUBLIC;
             vote => PUBLIC;
+            vote_quadratic => PUBLIC;
+            vote_on_options => PUBLIC;
+            vote_quadratic_on_options => PUBLIC;
+            claim_back_vote_tokens => PUBLIC;
             execute_proposal => PUBLIC;
+            close_proposal => PUBLIC;
+            cancel_proposal => restrict_to: [executive, OWNER];
+            get_proposal_status => PUBLIC;
+            get_proposal_history => PUBLIC;
+            set_proposal_execution_policy => restrict_to: [OWNER];
+            set_passing_rule => restrict_to: [OWNER];
+            get_passing_rules => PUBLIC;
             create_zero_coupon_bond => PUBLIC;
             purchase_bond => PUBLIC;
             sell_bond => PUBLIC;
@@ -65,10 +210,6 @@ mod radixdao {
             withdraw_power => PUBLIC;
             // get_usd_price => PUBLIC;
             // get_proposal_id => PUBLIC;
-            create_proposal_to_mint_more_dao_tokens => PUBLIC;
-            mint_more_tokens => PUBLIC;
-            set_price => PUBLIC;
-            execute_proposal_to_mint_more_tokens => PUBLIC;
             get_back_the_collateral => PUBLIC;
             liquidate_collateral => PUBLIC;
             claim_the_invested_XRDs_plus_interest => PUBLIC;
@@ -76,17 +217,59 @@ mod radixdao {
             put_in_money_plus_interest_for_the_community_to_redeem => PUBLIC;
             check_the_balance_of_bond_issuer => PUBLIC;
             transfer_xrds_to_community_vault => PUBLIC;
-            create_proposal_to_change_token_price => PUBLIC;
-            execute_proposal_to_change_token_price => PUBLIC;
             get_all_contributors => PUBLIC;
             get_all_proposals => PUBLIC;
             get_bond_creator_addresses => PUBLIC;
             get_bond_creators => PUBLIC;
             get_bond_creator_and_details => PUBLIC;
+            get_issuer_reputation => PUBLIC;
+            get_issuer_record => PUBLIC;
             get_investment_details => PUBLIC;
             get_created_proposals => PUBLIC;
             get_proposal_using_proposal_id => PUBLIC;
             get_executives => PUBLIC;
+            set_price_oracle => restrict_to: [OWNER];
+            monitor_margin => PUBLIC;
+            get_margin_call_start => PUBLIC;
+            refresh_bond_status => PUBLIC;
+            deposit_to_sinking_fund => PUBLIC;
+            check_sinking_fund => PUBLIC;
+            exercise_bond_call => PUBLIC;
+            redeem_called_bonds => PUBLIC;
+            exercise_bond_put => restrict_to: [executive, OWNER];
+            create_dao_convertible_bond => restrict_to: [OWNER];
+            convert_held_bonds => restrict_to: [executive, OWNER];
+            reclaim_conversion_tokens => PUBLIC;
+            get_conversion_report => PUBLIC;
+            get_bond_portfolio => PUBLIC;
+            get_liquidity_ladder => PUBLIC;
+            set_liquidity_policy => restrict_to: [OWNER];
+            add_payment_stream => restrict_to: [executive, OWNER];
+            remove_payment_stream => restrict_to: [executive, OWNER];
+            get_payment_streams => PUBLIC;
+            apply_as_issuer => PUBLIC;
+            get_issuer_applications => PUBLIC;
+            start_collateral_auction => restrict_to: [executive, OWNER];
+            settle_collateral_auction => PUBLIC;
+            close_collateral_auction => restrict_to: [executive, OWNER];
+            get_liquidated_collateral => PUBLIC;
+            get_collateral_auctions => PUBLIC;
+            become_a_dao_member => PUBLIC;
+            leave_dao => PUBLIC;
+            expel_member => restrict_to: [executive, OWNER];
+            set_member_role_tags => restrict_to: [OWNER];
+            set_join_policy => restrict_to: [OWNER];
+            set_one_vote_per_member => restrict_to: [OWNER];
+            vote_as_member => PUBLIC;
+            get_members => PUBLIC;
+            get_membership_applications => PUBLIC;
+            get_join_policy => PUBLIC;
+            lock_tokens_for_delegation => PUBLIC;
+            unlock_delegated_tokens => PUBLIC;
+            delegate_votes => PUBLIC;
+            revoke_delegation => PUBLIC;
+            get_delegators => PUBLIC;
+            get_delegated_votes => PUBLIC;
         }
 
     }
@@ -96,7 +279,7 @@ mod radixdao {
         // current_praposal: Option<Global<TokenWeightProposal>>,
         current_praposals: HashMap<ComponentAddress, HashMap<usize, Global<TokenWeightProposal>>>,
 
-        dao_token_resource_manager: ResourceManager,
+        dao_token_resource_manager: FungibleResourceManager,
 
         dao_token: Vault,
 
@@ -121,11 +304,30 @@ mod radixdao {
 
         proposal_creation_right: ProposalCreationRight,
 
-        liquidated_collateral: Vault,
+        // one vault per seized collateral resource
+        liquidated_collateral: HashMap<ResourceAddress, Vault>,
+
+        // auction component address -> where its proceeds should go
+        collateral_auctions: HashMap<ComponentAddress, CollateralAuction>,
+
+        // bond creator address -> every bond they issued and how it ended
+        issuer_registry: HashMap<ComponentAddress, IssuerRecord>,
+
+        // only holders of this badge can create bonds, minted once the DAO votes an issuer in
+        issuer_badge_resource_manager: NonFungibleResourceManager,
+
+        // votes lock the governance tokens in the proposal, the voter gets one of these to claim them back
+        vote_receipt_resource_manager: NonFungibleResourceManager,
+
+        vote_receipt_counter: u64,
+
+        issuer_applications: HashMap<u64, IssuerApplication>,
+
+        issuer_application_counter: u64,
 
         investment_record: HashMap<ComponentAddress, Decimal>,
 
-        withdraw_requests: HashMap<u64, Decimal>,
+        withdraw_requests: HashMap<u64, WithdrawalRequest>,
 
         approval_details: HashMap<u64, ApprovalDetails>,
 
@@ -133,7 +335,75 @@ mod radixdao {
 
         // executive_token_address: ResourceAddress
 
-        executive_badge_resource_manager : NonFungibleResourceManager
+        executive_badge_resource_manager : NonFungibleResourceManager,
+
+        price_oracle: Option<Global<PriceOracle>>,
+
+        // collateral value must stay above maintenance_ratio * (principal + interest)
+        maintenance_ratio: Decimal,
+
+        // seconds a bond issuer has to cure a margin call before liquidation
+        margin_call_window: u64,
+
+        // bond component address -> time at which the margin call was issued
+        margin_calls: HashMap<ComponentAddress, u64>,
+
+        // bond resource -> XRD paid for the bonds the DAO still holds
+        bond_cost_basis: HashMap<ResourceAddress, Decimal>,
+
+        // bond resource -> when the DAO bought the bonds it holds, averaged by quantity
+        bond_acquired_at: HashMap<ResourceAddress, u64>,
+
+        // recurring payments the treasury has committed to, for cash flow projections
+        payment_streams: HashMap<u64, PaymentStream>,
+
+        payment_stream_counter: u64,
+
+        // bond purchases may not push the projected treasury balance below this
+        minimum_liquidity: Decimal,
+
+        liquidity_ladder_interval: LadderInterval,
+
+        liquidity_ladder_rungs: u32,
+
+        // executed, defeated, cancelled and expired proposals, moved out of current_praposals
+        proposal_history: HashMap<usize, Global<TokenWeightProposal>>,
+
+        // seconds a succeeded proposal stays queued before it can be executed
+        proposal_execution_delay: u64,
+
+        // seconds after voting ends during which a succeeded proposal can be executed
+        proposal_execution_window: u64,
+
+        // how each category of proposal passes, see passing_rule for the default
+        passing_rules: HashMap<ProposalCategory, PassingRule>,
+
+        member_badge_resource_manager: NonFungibleResourceManager,
+
+        // member id (the badge's integer id) -> membership, kept after a member leaves or is expelled
+        members: HashMap<u64, MemberRecord>,
+
+        member_counter: u64,
+
+        membership_applications: HashMap<u64, MembershipApplication>,
+
+        membership_application_counter: u64,
+
+        join_policy: JoinPolicy,
+
+        // equality proposals count one vote per member badge instead of one per bucket
+        one_vote_per_member: bool,
+
+        // governance tokens locked by delegators, their delegates vote with this weight
+        delegation_vault: Vault,
+
+        delegators: HashMap<ComponentAddress, Delegator>,
+
+        // proposal id -> delegator -> the vote cast for them
+        delegated_votes: HashMap<usize, HashMap<ComponentAddress, DelegatedVote>>,
+
+        // last proposal id handed out, ids are never reused
+        proposal_counter: usize
     }
 
     impl TokenWeigtedDao {
@@ -226,6 +496,83 @@ mod radixdao {
 
             
 
+            let issuer_badge_description = format!("{}'s bond issuer badge", &organization_name);
+
+            // create a new Issuer Badge resource manager, badges are minted on approval by vote
+            let issuer_badges_manager: NonFungibleResourceManager =
+                ResourceBuilder::new_integer_non_fungible::<IssuerBadge>(OwnerRole::None)
+                    .metadata(metadata!(
+                        init {
+                            "name" => issuer_badge_description, locked;
+                        }
+                    ))
+                    .mint_roles(mint_roles! {
+                        minter => rule!(require(global_caller(component_address)));
+                        minter_updater => rule!(deny_all);
+                    })
+                    .recall_roles(recall_roles! {
+                        recaller => rule!(require(owner_badge.resource_address()));
+                        recaller_updater => rule!(deny_all);
+                    })
+                    .burn_roles(burn_roles! {
+                        burner => rule!(require(owner_badge.resource_address()));
+                        burner_updater => rule!(deny_all);
+                    })
+                    .create_with_no_initial_supply();
+
+            let vote_receipt_description = format!("{}'s vote receipt", &organization_name);
+
+            // create a new Vote Receipt resource manager, one receipt per escrowed vote
+            let vote_receipts_manager: NonFungibleResourceManager =
+                ResourceBuilder::new_integer_non_fungible::<VoteReceipt>(OwnerRole::None)
+                    .metadata(metadata!(
+                        init {
+                            "name" => vote_receipt_description, locked;
+                        }
+                    ))
+                    .mint_roles(mint_roles! {
+                        minter => rule!(require(global_caller(component_address)));
+                        minter_updater => rule!(deny_all);
+                    })
+                    .burn_roles(burn_roles! {
+                        burner => rule!(require(global_caller(component_address)));
+                        burner_updater => rule!(deny_all);
+                    })
+                    .create_with_no_initial_supply();
+
+            let member_badge_description = format!("{}'s member badge", &organization_name);
+
+            // create a new Member Badge resource manager, badges are minted when an account joins
+            let member_badges_manager: NonFungibleResourceManager =
+                ResourceBuilder::new_integer_non_fungible::<MemberBadge>(OwnerRole::None)
+                    .metadata(metadata!(
+                        init {
+                            "name" => member_badge_description, locked;
+                        }
+                    ))
+                    .mint_roles(mint_roles! {
+                        minter => rule!(require(global_caller(component_address)));
+                        minter_updater => rule!(deny_all);
+                    })
+                    .non_fungible_data_update_roles(non_fungible_data_update_roles! {
+                        non_fungible_data_updater => rule!(require(global_caller(component_address)));
+                        non_fungible_data_updater_updater => rule!(deny_all);
+                    })
+                    .recall_roles(recall_roles! {
+                        recaller => rule!(require(owner_badge.resource_address()));
+                        recaller_updater => rule!(deny_all);
+                    })
+                    // badges stay with the account they were minted to, so one holder can not collect several
+                    .withdraw_roles(withdraw_roles! {
+                        withdrawer => rule!(deny_all);
+                        withdrawer_updater => rule!(deny_all);
+                    })
+                    .burn_roles(burn_roles! {
+                        burner => rule!(require(global_caller(component_address)));
+                        burner_updater => rule!(deny_all);
+                    })
+                    .create_with_no_initial_supply();
+
             // create nft to be sold for voting purpose
             let dao_token_description = format!("{} voting share", token_name);
 
@@ -236,6 +583,11 @@ mod radixdao {
                     "organization name" => organization_name.as_str() , locked ;
                     "icon_url" => Url::of(&power_token_url), locked;
                 }))
+                // only the DAO mints, and only when a MintTokens proposal is executed
+                .mint_roles(mint_roles! {
+                    minter => rule!(require(global_caller(component_address)));
+                    minter_updater => rule!(deny_all);
+                })
                 .mint_initial_supply(token_supply)
                 .into();
 
@@ -243,26 +595,24 @@ mod radixdao {
 
             let owner_token_addresss = owner_badge.resource_address();
 
-            let component: Global<TokenWeigtedDao>;
-
-            match proposal_creation_right {
+            let component: Global<TokenWeigtedDao> = match proposal_creation_right {
                 ProposalCreationRight::EVERYONE => {
-                    component = Self {
-                        token_price: token_price.clone(),
+                    Self {
+                        token_price,
 
                         organization_name: organization_name.clone(),
 
-                        dao_token_address: dao_token_address.clone(),
+                        dao_token_address,
 
-                        owner_token_addresss: owner_token_addresss.clone(),
+                        owner_token_addresss,
 
                         current_praposals: HashMap::new(),
 
-                        dao_token_resource_manager: voting_power_tokens.resource_manager(),
+                        dao_token_resource_manager: dao_token_address.into(),
 
                         dao_token: Vault::with_bucket(voting_power_tokens),
 
-                        buy_back_price: token_buy_back_price.clone(),
+                        buy_back_price: token_buy_back_price,
 
                         shares: Vault::new(XRD),
 
@@ -275,7 +625,21 @@ mod radixdao {
 
                         proposal_creation_right: ProposalCreationRight::EVERYONE,
 
-                        liquidated_collateral: Vault::new(XRD),
+                        liquidated_collateral: HashMap::new(),
+
+                        collateral_auctions: HashMap::new(),
+
+                        issuer_registry: HashMap::new(),
+
+                        issuer_badge_resource_manager: issuer_badges_manager,
+
+                        vote_receipt_resource_manager: vote_receipts_manager,
+
+                        vote_receipt_counter: 0,
+
+                        issuer_applications: HashMap::new(),
+
+                        issuer_application_counter: 0,
 
                         investment_record: HashMap::new(),
 
@@ -285,37 +649,89 @@ mod radixdao {
 
                         executives: HashSet::new(),
 
-                        executive_badge_resource_manager : executive_badges_manager
+                        executive_badge_resource_manager : executive_badges_manager,
+
+                        price_oracle: None,
+
+                        maintenance_ratio: dec!("1.5"),
+
+                        margin_call_window: 3 * 24 * 60 * 60,
+
+                        margin_calls: HashMap::new(),
+
+                        bond_cost_basis: HashMap::new(),
+
+                        bond_acquired_at: HashMap::new(),
+
+                        payment_streams: HashMap::new(),
+
+                        payment_stream_counter: 0,
+
+                        minimum_liquidity: Decimal::zero(),
+
+                        liquidity_ladder_interval: LadderInterval::Monthly,
+
+                        liquidity_ladder_rungs: 12,
+
+                        proposal_history: HashMap::new(),
+
+                        proposal_execution_delay: 0,
+
+                        proposal_execution_window: 604800,
+
+                        passing_rules: HashMap::new(),
+
+                        member_badge_resource_manager: member_badges_manager,
+
+                        members: HashMap::new(),
+
+                        member_counter: 0,
+
+                        membership_applications: HashMap::new(),
+
+                        membership_application_counter: 0,
+
+                        join_policy: JoinPolicy::Open,
+
+                        one_vote_per_member: false,
+
+                        delegation_vault: Vault::new(dao_token_address),
+
+                        delegators: HashMap::new(),
+
+                        delegated_votes: HashMap::new(),
+
+                        proposal_counter: 0
 
                         // executive_token_address
                     }
                     .instantiate()
                     .prepare_to_globalize(OwnerRole::Fixed(rule!(require(
-                        owner_token_addresss.clone()
+                        owner_token_addresss
                     ))))
                     .roles(roles!(
                         // manager => rule!(require(manager_badge.resource_address()));
                         executive => rule!(require(executive_badges_manager.address()));))
                     .with_address(address_reservation.clone())
-                    .globalize();
+                    .globalize()
                 }
                 ProposalCreationRight::TOKEN_HOLDER_THRESHOLD(threshold) => {
-                    component = Self {
-                        token_price: token_price.clone(),
+                    Self {
+                        token_price,
 
                         organization_name: organization_name.clone(),
 
-                        dao_token_address: dao_token_address.clone(),
+                        dao_token_address,
 
-                        owner_token_addresss: owner_token_addresss.clone(),
+                        owner_token_addresss,
 
                         current_praposals: HashMap::new(),
 
-                        dao_token_resource_manager: voting_power_tokens.resource_manager(),
+                        dao_token_resource_manager: dao_token_address.into(),
 
                         dao_token: Vault::with_bucket(voting_power_tokens),
 
-                        buy_back_price: token_buy_back_price.clone(),
+                        buy_back_price: token_buy_back_price,
 
                         shares: Vault::new(XRD),
 
@@ -330,7 +746,21 @@ mod radixdao {
                             threshold,
                         ),
 
-                        liquidated_collateral: Vault::new(XRD),
+                        liquidated_collateral: HashMap::new(),
+
+                        collateral_auctions: HashMap::new(),
+
+                        issuer_registry: HashMap::new(),
+
+                        issuer_badge_resource_manager: issuer_badges_manager,
+
+                        vote_receipt_resource_manager: vote_receipts_manager,
+
+                        vote_receipt_counter: 0,
+
+                        issuer_applications: HashMap::new(),
+
+                        issuer_application_counter: 0,
 
                         investment_record: HashMap::new(),
 
@@ -340,37 +770,89 @@ mod radixdao {
 
                         executives: HashSet::new(),
 
-                        executive_badge_resource_manager : executive_badges_manager
+                        executive_badge_resource_manager : executive_badges_manager,
+
+                        price_oracle: None,
+
+                        maintenance_ratio: dec!("1.5"),
+
+                        margin_call_window: 3 * 24 * 60 * 60,
+
+                        margin_calls: HashMap::new(),
+
+                        bond_cost_basis: HashMap::new(),
+
+                        bond_acquired_at: HashMap::new(),
+
+                        payment_streams: HashMap::new(),
+
+                        payment_stream_counter: 0,
+
+                        minimum_liquidity: Decimal::zero(),
+
+                        liquidity_ladder_interval: LadderInterval::Monthly,
+
+                        liquidity_ladder_rungs: 12,
+
+                        proposal_history: HashMap::new(),
+
+                        proposal_execution_delay: 0,
+
+                        proposal_execution_window: 604800,
+
+                        passing_rules: HashMap::new(),
+
+                        member_badge_resource_manager: member_badges_manager,
+
+                        members: HashMap::new(),
+
+                        member_counter: 0,
+
+                        membership_applications: HashMap::new(),
+
+                        membership_application_counter: 0,
+
+                        join_policy: JoinPolicy::Open,
+
+                        one_vote_per_member: false,
+
+                        delegation_vault: Vault::new(dao_token_address),
+
+                        delegators: HashMap::new(),
+
+                        delegated_votes: HashMap::new(),
+
+                        proposal_counter: 0
 
                         // executive_token_address,
                     }
                     .instantiate()
                     .prepare_to_globalize(OwnerRole::Fixed(rule!(require(
-                        owner_token_addresss.clone()
+                        owner_token_addresss
                     ))))
                     .roles(roles!(
                         // manager => rule!(require(manager_badge.resource_address()));
                         executive => rule!(require(executive_badges_manager.address()));))
                     .with_address(address_reservation.clone())
-                    .globalize();
+                    .globalize()
                 }
                 ProposalCreationRight::ADMIN => {
-                    component = Self {
-                        token_price: token_price.clone(),
+                    Self {
+                        token_price,
 
                         organization_name: organization_name.clone(),
 
-                        dao_token_address: dao_token_address.clone(),
+                        dao_token_address,
 
-                        owner_token_addresss: owner_token_addresss.clone(),
+                        owner_token_addresss,
 
                         current_praposals: HashMap::new(),
 
-                        dao_token_resource_manager: voting_power_tokens.resource_manager(),
+                        dao_token_resource_manager: dao_token_address.into(),
 
                         dao_token: Vault::with_bucket(voting_power_tokens),
 
-                        buy_back_price: token_buy_back_price.clone(),
+                        buy_back_price: token_buy_back_price,
 
                         shares: Vault::new(XRD),
 
@@ -383,7 +865,21 @@ mod radixdao {
 
                         proposal_creation_right: ProposalCreationRight::ADMIN,
 
-                        liquidated_collateral: Vault::new(XRD),
+                        liquidated_collateral: HashMap::new(),
+
+                        collateral_auctions: HashMap::new(),
+
+                        issuer_registry: HashMap::new(),
+
+                        issuer_badge_resource_manager: issuer_badges_manager,
+
+                        vote_receipt_resource_manager: vote_receipts_manager,
+
+                        vote_receipt_counter: 0,
+
+                        issuer_applications: HashMap::new(),
+
+                        issuer_application_counter: 0,
 
                         investment_record: HashMap::new(),
 
@@ -393,21 +889,73 @@ mod radixdao {
 
                         executives: HashSet::new(),
 
-                        executive_badge_resource_manager : executive_badges_manager
+                        executive_badge_resource_manager : executive_badges_manager,
+
+                        price_oracle: None,
+
+                        maintenance_ratio: dec!("1.5"),
+
+                        margin_call_window: 3 * 24 * 60 * 60,
+
+                        margin_calls: HashMap::new(),
+
+                        bond_cost_basis: HashMap::new(),
+
+                        bond_acquired_at: HashMap::new(),
+
+                        payment_streams: HashMap::new(),
+
+                        payment_stream_counter: 0,
+
+                        minimum_liquidity: Decimal::zero(),
+
+                        liquidity_ladder_interval: LadderInterval::Monthly,
+
+                        liquidity_ladder_rungs: 12,
+
+                        proposal_history: HashMap::new(),
+
+                        proposal_execution_delay: 0,
+
+                        proposal_execution_window: 604800,
+
+                        passing_rules: HashMap::new(),
+
+                        member_badge_resource_manager: member_badges_manager,
+
+                        members: HashMap::new(),
+
+                        member_counter: 0,
+
+                        membership_applications: HashMap::new(),
+
+                        membership_application_counter: 0,
+
+                        join_policy: JoinPolicy::Open,
+
+                        one_vote_per_member: false,
+
+                        delegation_vault: Vault::new(dao_token_address),
+
+                        delegators: HashMap::new(),
+
+                        delegated_votes: HashMap::new(),
+
+                        proposal_counter: 0
 
                         // executive_token_address,
                     }
                     .instantiate()
                     .prepare_to_globalize(OwnerRole::Fixed(rule!(require(
-                        owner_token_addresss.clone()
+                        owner_token_addresss
                     ))))
                     .roles(roles!(
                         // manager => rule!(require(manager_badge.resource_address()));
                         executive => rule!(require(executive_badges_manager.address()));))
                     .with_address(address_reservation.clone())
-                    .globalize();
+                    .globalize()
                 }
-            }
+            };
 
             let component_address = component.address();
 
@@ -545,8 +1093,7 @@ mod radixdao {
 
         fn get_discriminator(&self) -> u64 {
             let current_epoch = Runtime::current_epoch();
-            let unique_number = current_epoch.number();
-            unique_number
+            current_epoch.number()
         }
 
         // pub fn generate_uuid(&self){
@@ -678,7 +1225,7 @@ mod radixdao {
 
         pub fn get_investment_details(&self, address: ComponentAddress) -> Result<Decimal, String> {
             if let Some(amt) = self.investment_record.get(&address) {
-                Ok(amt.clone())
+                Ok(*amt)
             } else {
                 Err("No investment is made by this account address".to_string())
             }
@@ -692,14 +1239,15 @@ mod radixdao {
 
         pub fn request_withdrawal(
             &mut self,
-            requester_address: ComponentAddress,
+            account: Global<Account>,
             requested_amount: Decimal,
         ) -> Result<(), String> {
+            let requester_address = Self::authenticate_account(&account);
 
             //Check if the sender has invested any amount
             let invested_amount =
                 if let Some(amount) = self.investment_record.get(&requester_address) {
-                    amount.clone()
+                    *amount
                 } else {
                     return Err("No investment found for the sender".to_string());
                 };
@@ -734,8 +1282,13 @@ mod radixdao {
 
             let uuid = Self::generate_uuid();
             // Store the withdrawal request
-            self.withdraw_requests
-                .insert(uuid, requested_amount);
+            self.withdraw_requests.insert(
+                uuid,
+                WithdrawalRequest {
+                    requester_address,
+                    requested_amount,
+                },
+            );
 
             let event_metadata = WithdrawalRequested {
                 requester_address,
@@ -786,25 +1339,31 @@ mod radixdao {
 
         pub fn approve_or_deny_withdrawal_request(
             &mut self,
-            approver_address: ComponentAddress,
-            mut user_address: Global<Account>,
+            approver: Global<Account>,
             request_id : u64,
             response: ApprovalResponse,
         ) -> Result<(), String> {
             // check whether a caller is executive or not
+            let approver_address = Self::authenticate_account(&approver);
 
             // Check if the caller is an executive
             if !self.executives.contains(&approver_address) {
-                return Err(format!("Caller is not an executive"));
+                return Err("Caller is not an executive".to_string());
             }
 
             // Check if the user has a withdrawal request
-            if !self.withdraw_requests.contains_key(&request_id) {
-                return Err(format!(
-                    "No withdrawal request found for this request id {:?}",
-                    user_address
-                ));
-            }
+            let withdrawal_request = match self.withdraw_requests.get(&request_id) {
+                Some(withdrawal_request) => withdrawal_request.clone(),
+                None => {
+                    return Err(format!(
+                        "No withdrawal request found for this request id {:?}",
+                        request_id
+                    ))
+                }
+            };
+
+            // the XRD always go to the account that made the request
+            let mut user_address: Global<Account> = withdrawal_request.requester_address.into();
 
             // Get the approval details for the user
             let approval_details = self.approval_details.entry(request_id).or_insert(ApprovalDetails {
@@ -878,7 +1437,7 @@ mod radixdao {
                 if approval_details.approvals > approval_details.denials {
                     // Withdraw the money
 
-                    let demanded_xrds = *self.withdraw_requests.get(&request_id).unwrap();
+                    let demanded_xrds = withdrawal_request.requested_amount;
                     let bucket = self.shares.take(demanded_xrds);
 
                     user_address.try_deposit_or_abort(bucket, None);
@@ -905,7 +1464,7 @@ mod radixdao {
 
                 } else {
                     // Emit event indicating the request is denied
-                    let demanded_xrds = *self.withdraw_requests.get(&request_id).unwrap();
+                    let demanded_xrds = withdrawal_request.requested_amount;
 
                     //as request is denied, remove the request
                     self.withdraw_requests.remove(&request_id);
@@ -1051,7 +1610,7 @@ mod radixdao {
             let event_metadata = TokenWeightBuyToken {
                 amount: power_amount,
 
-                resource_address: self.dao_token_address.clone(),
+                resource_address: self.dao_token_address,
 
                 amount_paid: power_amount * self.buy_back_price,
 
@@ -1071,11 +1630,10 @@ mod radixdao {
         }
 
 
-        //get_unique_random_number
-        fn get_proposal_id() -> u64 {
-            let current_epoch = Runtime::current_epoch();
-            let unique_number: u64 = current_epoch.number();
-            unique_number
+        // several proposals can be created in the same epoch, so ids come from a counter
+        fn next_proposal_id(&mut self) -> usize {
+            self.proposal_counter += 1;
+            self.proposal_counter
         }
 
         pub fn create_praposal(
@@ -1085,193 +1643,206 @@ mod radixdao {
             minimun_quorum: u8,
             start_time: scrypto::time::UtcDateTime,
             end_time: scrypto::time::UtcDateTime,
-            address_issued_bonds_to_sell: Option<ComponentAddress>,
-            target_xrd_amount: Option<Decimal>,
-            proposal_creator_address: Option<ComponentAddress>,
+            account: Global<Account>,
             governance_token_or_owner_token_address: Bucket,
             voting_type: VotingType,
+            action: ProposalAction,
         ) -> (
             Global<crate::proposal::pandao_praposal::TokenWeightProposal>,
             String,
             Bucket,
         ) {
-            //implement proposal creation rights
-            match self.proposal_creation_right {
-                ProposalCreationRight::EVERYONE => {
-                    assert_eq!(
-                        governance_token_or_owner_token_address.resource_address(),
-                        self.dao_token_address,
-                        "wrong voting token supplied! please make sure that you supply DAO Governance Token"
-                    );
-
-                    assert!(
-                        governance_token_or_owner_token_address.amount() >= Decimal::one(),
-                        "Proposal creator must have at least one governance token to create a proposal"
-                    );
-
-                    //allow proposal creation
-                }
-                ProposalCreationRight::TOKEN_HOLDER_THRESHOLD(threshold) => {
-                    assert_eq!(
-                        governance_token_or_owner_token_address.resource_address(),
-                        self.dao_token_address,
-                        "wrong voting token supplied! please make sure that you supply DAO Governance Token"
-                    );
-
-                    assert!(
-                        governance_token_or_owner_token_address.amount() >= threshold,
-                        "Proposal creator does not have enough tokens to meet the threshold"
-                    );
-                }
-                ProposalCreationRight::ADMIN => {
-                    assert_eq!(
-                        governance_token_or_owner_token_address.resource_address(),
-                        self.owner_token_addresss,
-                        "Only the admin can create a proposal and If you are an Admin please make sure you pass OWNER TOKEN ADDRESS"
-                    );
+            self.deploy_proposal(
+                title,
+                description,
+                minimun_quorum,
+                start_time,
+                end_time,
+                account,
+                governance_token_or_owner_token_address,
+                voting_type,
+                action,
+                Vec::new(),
+                None,
+            )
+        }
 
-                    assert!(
-                        governance_token_or_owner_token_address.amount() >= Decimal::one(),
-                        "ADMIN must pass his/her OWNER TOKEN to create proposal"
-                    );
-                }
-            }
+        /// A proposal between labelled options, the action of the winning option is executed.
+        pub fn create_praposal_with_options(
+            &mut self,
+            title: String,
+            description: String,
+            minimun_quorum: u8,
+            start_time: scrypto::time::UtcDateTime,
+            end_time: scrypto::time::UtcDateTime,
+            account: Global<Account>,
+            governance_token_or_owner_token_address: Bucket,
+            voting_type: VotingType,
+            options: Vec<ProposalOption>,
+            counting_method: CountingMethod,
+        ) -> (
+            Global<crate::proposal::pandao_praposal::TokenWeightProposal>,
+            String,
+            Bucket,
+        ) {
+            assert!(options.len() >= 2, "a proposal with options needs at least two of them");
 
-            use crate::proposal::pandao_praposal::TokenWeightProposal;
+            // the category decides the passing rule, so every option has to share it
+            let category = options[0].action.category();
 
-            if let Some(address_selling_bonds) = address_issued_bonds_to_sell {
+            for (position, option) in options.iter().enumerate() {
+                assert!(!option.label.is_empty(), "every option needs a label");
+                assert!(
+                    !options[..position].iter().any(|other| other.label == option.label),
+                    "option labels must be unique"
+                );
                 assert!(
-                    self.zero_coupon_bond.contains_key(&address_selling_bonds),
-                    "The Address you have specified has not created any bond"
+                    option.action.category() == category,
+                    "all options of a proposal must be of the same category"
+                );
+                assert!(
+                    !matches!(
+                        option.action,
+                        ProposalAction::OnboardIssuer { .. }
+                            | ProposalAction::AdmitMember { .. }
+                            | ProposalAction::InsuranceClaim { .. }
+                    ),
+                    "applications and claims are decided one at a time"
                 );
             }
 
-            let amount_of_tokens_should_be_minted: Option<usize> = None;
-            let desired_token_price: Option<Decimal> = None;
-            let desired_token_buy_back_price: Option<Decimal> = None;
-
-            let global_proposal_component: Global<TokenWeightProposal>;
-
-            match voting_type {
-                VotingType::ResourceHold => {
-                    (global_proposal_component, _) = TokenWeightProposal::new(
-                        title.clone(),
-                        description.clone(),
-                        minimun_quorum,
-                        start_time,
-                        end_time,
-                        self.owner_token_addresss.clone(),
-                        self.dao_token_address.clone(),
-                        address_issued_bonds_to_sell.clone(),
-                        target_xrd_amount.clone(),
-                        proposal_creator_address,
-                        amount_of_tokens_should_be_minted,
-                        VotingType::ResourceHold,
-                        desired_token_price,
-                        desired_token_buy_back_price,
-                    );
-                }
-                VotingType::Equality => {
-                    (global_proposal_component, _) = TokenWeightProposal::new(
-                        title.clone(),
-                        description.clone(),
-                        minimun_quorum,
-                        start_time,
-                        end_time,
-                        self.owner_token_addresss.clone(),
-                        self.dao_token_address.clone(),
-                        address_issued_bonds_to_sell.clone(),
-                        target_xrd_amount.clone(),
-                        proposal_creator_address,
-                        amount_of_tokens_should_be_minted,
-                        VotingType::Equality,
-                        desired_token_price,
-                        desired_token_buy_back_price,
-                    );
-                }
+            let action = options[0].action.clone();
+
+            self.deploy_proposal(
+                title,
+                description,
+                minimun_quorum,
+                start_time,
+                end_time,
+                account,
+                governance_token_or_owner_token_address,
+                voting_type,
+                action,
+                options,
+                Some(counting_method),
+            )
+        }
+
+        fn deploy_proposal(
+            &mut self,
+            title: String,
+            description: String,
+            minimun_quorum: u8,
+            start_time: scrypto::time::UtcDateTime,
+            end_time: scrypto::time::UtcDateTime,
+            account: Global<Account>,
+            governance_token_or_owner_token_address: Bucket,
+            voting_type: VotingType,
+            action: ProposalAction,
+            options: Vec<ProposalOption>,
+            counting_method: Option<CountingMethod>,
+        ) -> (
+            Global<crate::proposal::pandao_praposal::TokenWeightProposal>,
+            String,
+            Bucket,
+        ) {
+            let proposal_creator_address = Self::authenticate_account(&account);
+
+            //implement proposal creation rights
+            self.assert_proposal_creation_right(&governance_token_or_owner_token_address);
+
+            let now: Instant = Clock::current_time_rounded_to_seconds();
+
+            assert!(
+                start_time.to_instant().seconds_since_unix_epoch < end_time.to_instant().seconds_since_unix_epoch,
+                "voting must start before it ends"
+            );
+            assert!(
+                end_time.to_instant().seconds_since_unix_epoch > now.seconds_since_unix_epoch,
+                "voting must end in the future"
+            );
+
+            self.assert_valid_action(&action);
+
+            for option in &options {
+                self.assert_valid_action(&option.action);
             }
 
+            assert!(
+                voting_type != VotingType::Quadratic || self.join_policy != JoinPolicy::Open,
+                "quadratic voting needs a join policy that keeps one holder from joining with many accounts"
+            );
+
+            // let voters see how this issuer behaved on earlier bonds
+            let issuer_reputation_score: Option<Decimal> = match &action {
+                ProposalAction::PurchaseBond {
+                    bond_creator_address,
+                    ..
+                } => Some(self.issuer_reputation_score(*bond_creator_address)),
+                _ => None,
+            };
+
+            let (global_proposal_component, _) = TokenWeightProposal::new(
+                title.clone(),
+                description.clone(),
+                minimun_quorum,
+                start_time,
+                end_time,
+                self.owner_token_addresss,
+                self.dao_token_address,
+                Some(proposal_creator_address),
+                voting_type.clone(),
+                action.clone(),
+                options.clone(),
+                counting_method,
+                self.proposal_execution_delay,
+                self.proposal_execution_window,
+                Runtime::global_address(),
+                self.passing_rule(action.category()),
+                self.circulating_supply(),
+            );
+
             let start_time_ts: i64 = start_time.to_instant().seconds_since_unix_epoch;
             let end_time_ts: i64 = end_time.to_instant().seconds_since_unix_epoch;
 
             //unique-id-generation
-            let proposal_id: usize = Self::get_proposal_id()
-                .try_into()
-                .expect("couldn't get called successfully");
+            let proposal_id: usize = self.next_proposal_id();
             //populate HashMap with newly created proposal
 
             let inner_map = self
                 .current_praposals
-                .entry(proposal_creator_address.unwrap())
-                .or_insert_with(HashMap::new);
+                .entry(proposal_creator_address)
+                .or_default();
 
             inner_map.insert(proposal_id, global_proposal_component);
 
-            match voting_type {
-                VotingType::ResourceHold => {
-                    let praposal_metadata = PraposalMetadata {
-                        title,
-                        description,
-                        minimum_quorum: minimun_quorum.into(),
-                        end_time_ts,
-                        start_time_ts,
-                        owner_token_address: self.owner_token_addresss.clone(),
-                        component_address: global_proposal_component.address(),
-                        address_issued_bonds_to_sell,
-                        target_xrd_amount,
-                        proposal_creator_address,
-                        amount_of_tokens_should_be_minted,
-                        proposal_id,
-                        governance_token_or_owner_token_address:
-                            governance_token_or_owner_token_address.resource_address(),
-                        token_type: VotingType::ResourceHold,
-                        desired_token_price,
-                        desired_token_buy_back_price,
-                    };
-
-                    let component_address = Runtime::global_address();
-
-                    Runtime::emit_event(PandaoEvent {
-                        event_type: EventType::PROPOSAL_TO_PURCHASE_BOND,
-                        dao_type: DaoType::Investment,
-                        meta_data: DaoEvent::PraposalDeployment(praposal_metadata),
-                        component_address,
-                    });
-                }
-                VotingType::Equality => {
-                    let praposal_metadata = PraposalMetadata {
-                        title,
-                        description,
-                        minimum_quorum: minimun_quorum.into(),
-                        end_time_ts,
-                        start_time_ts,
-                        owner_token_address: self.owner_token_addresss.clone(),
-                        component_address: global_proposal_component.address(),
-                        address_issued_bonds_to_sell,
-                        target_xrd_amount,
-                        proposal_creator_address,
-                        amount_of_tokens_should_be_minted,
-                        proposal_id,
-                        governance_token_or_owner_token_address:
-                            governance_token_or_owner_token_address.resource_address(),
-                        token_type: VotingType::Equality,
-                        desired_token_price,
-                        desired_token_buy_back_price,
-                    };
-
-                    let component_address = Runtime::global_address();
+            let praposal_metadata = PraposalMetadata {
+                title,
+                description,
+                minimum_quorum: minimun_quorum.into(),
+                end_time_ts,
+                start_time_ts,
+                owner_token_address: self.owner_token_addresss,
+                component_address: global_proposal_component.address(),
+                action: action.clone(),
+                proposal_creator_address: Some(proposal_creator_address),
+                proposal_id,
+                governance_token_or_owner_token_address:
+                    governance_token_or_owner_token_address.resource_address(),
+                token_type: voting_type,
+                options,
+                counting_method,
+                issuer_reputation_score,
+            };
 
-                    Runtime::emit_event(PandaoEvent {
-                        event_type: EventType::PROPOSAL_TO_PURCHASE_BOND,
-                        dao_type: DaoType::Investment,
-                        meta_data: DaoEvent::PraposalDeployment(praposal_metadata),
-                        component_address,
-                    });
-                }
-            }
+            Runtime::emit_event(PandaoEvent {
+                event_type: action.event_type(),
+                dao_type: DaoType::Investment,
+                meta_data: DaoEvent::PraposalDeployment(praposal_metadata),
+                component_address: Runtime::global_address(),
+            });
 
-            let mut message = String::new();
-            message = format!("Proposal created with id : {}", proposal_id);
+            let message = format!("Proposal created with id : {}", proposal_id);
 
             (
                 global_proposal_component,
@@ -1280,17 +1851,94 @@ mod radixdao {
             )
         }
 
+        fn assert_valid_action(&self, action: &ProposalAction) {
+            match action {
+                ProposalAction::PurchaseBond {
+                    bond_creator_address,
+                    target_xrd_amount,
+                } => {
+                    assert!(
+                        self.zero_coupon_bond.contains_key(bond_creator_address),
+                        "The Address you have specified has not created any bond"
+                    );
+                    assert!(
+                        *target_xrd_amount > Decimal::zero(),
+                        "target xrd amount must be greater than zero"
+                    );
+                }
+                ProposalAction::MintTokens { amount } => {
+                    assert!(*amount > 0, "amount of tokens to mint must be greater than zero");
+                }
+                ProposalAction::ChangePrice {
+                    token_price,
+                    buy_back_price,
+                } => {
+                    assert!(
+                        *token_price > Decimal::zero() && *buy_back_price > Decimal::zero(),
+                        "token price and buy back price must be greater than zero"
+                    );
+                }
+                ProposalAction::TreasuryTransfer { amount, .. } => {
+                    assert!(*amount > Decimal::zero(), "transfer amount must be greater than zero");
+                }
+                ProposalAction::ConfigChange(ConfigChange::ProposalCreationRight(right)) => {
+                    if let ProposalCreationRight::TOKEN_HOLDER_THRESHOLD(threshold) = right {
+                        assert!(
+                            *threshold > Decimal::zero(),
+                            "token holder threshold must be greater than zero"
+                        );
+                    }
+                }
+                ProposalAction::ConfigChange(ConfigChange::LiquidityPolicy {
+                    minimum_liquidity,
+                    number_of_rungs,
+                    ..
+                }) => {
+                    assert!(
+                        *minimum_liquidity >= Decimal::zero(),
+                        "minimum liquidity can not be negative"
+                    );
+                    assert!(*number_of_rungs > 0, "the ladder needs at least one rung");
+                }
+                ProposalAction::ConfigChange(ConfigChange::PassingRule { category, rule }) => {
+                    self.assert_valid_passing_rule(*category, rule);
+                }
+                ProposalAction::OnboardIssuer { application_id } => {
+                    let application = self
+                        .issuer_applications
+                        .get(application_id)
+                        .expect("no issuer application found with the given id");
+
+                    assert!(
+                        application.status == IssuerApplicationStatus::Pending,
+                        "issuer application is not pending"
+                    );
+                }
+                ProposalAction::AdmitMember { application_id } => {
+                    let application = self
+                        .membership_applications
+                        .get(application_id)
+                        .expect("no membership application found with the given id");
+
+                    assert!(
+                        application.status == MembershipApplicationStatus::Pending,
+                        "membership application is not pending"
+                    );
+                }
+                ProposalAction::InsuranceClaim { .. } => {
+                    panic!("insurance claims are filed and voted on in an insurance DAO")
+                }
+            }
+        }
+
         pub fn get_created_proposals(
             &self,
             your_address: ComponentAddress,
         ) -> Result<HashMap<usize, Global<TokenWeightProposal>>, String> {
             let inner_map = self.current_praposals.get(&your_address);
             match inner_map {
-                Some(map) => {
-                    let map = map;
-                    Ok(map.clone())
-                }
-                None => Err(format!("this addres has no created proposals")),
+                Some(map) => Ok(map.clone()),
+                None => Err("this addres has no created proposals".to_string()),
             }
         }
 
@@ -1298,184 +1946,748 @@ mod radixdao {
             &self,
             proposal_id: usize,
         ) -> Result<Global<TokenWeightProposal>, String> {
-            for (_, inner_map) in &self.current_praposals {
-                let proposal = inner_map.get(&proposal_id);
-                match proposal {
-                    Some(proposal) => return Ok(proposal.clone()),
-                    None => return Err(format!("proposal with id : {proposal_id} not found")),
-                }
-            }
-            return Err(format!("proposal with id : {proposal_id} not found"));
+            self.current_praposals
+                .values()
+                .find_map(|inner_map| inner_map.get(&proposal_id).cloned())
+                .or(self.proposal_history.get(&proposal_id).cloned())
+                .ok_or(format!("proposal with id : {proposal_id} not found"))
         }
 
         pub fn get_all_proposals(&self) -> Vec<Global<TokenWeightProposal>> {
             let mut all_proposals: Vec<Global<TokenWeightProposal>> = Vec::new();
-            for (_, inner_map) in &self.current_praposals {
-                for (_, proposal) in inner_map {
-                    all_proposals.push(proposal.clone());
+            for inner_map in self.current_praposals.values() {
+                for proposal in inner_map.values() {
+                    all_proposals.push(*proposal);
                 }
             }
             all_proposals
         }
 
-        pub fn execute_proposal(&mut self, proposal_id: usize) {
-            // First, find the proposal
-            let mut proposal_option = None;
-            let mut bond_creator_address_option = None;
-            let mut target_xrd_amount_option = None;
-
-            for (_, inner_map) in &self.current_praposals {
-                if let Some(proposal) = inner_map.get(&proposal_id) {
-                    proposal_option = Some(proposal.clone());
-                    bond_creator_address_option = Some(proposal.get_address_issued_bonds());
-                    target_xrd_amount_option = Some(proposal.get_target_xrd_amount());
-                    break;
+        /// Queues a succeeded proposal and runs its action once the execution delay has passed.
+        /// With no delay both happen in the same call. Defeated and expired proposals are closed instead.
+        pub fn execute_proposal(&mut self, proposal_id: usize) -> Result<String, String> {
+            let proposal = self
+                .current_praposals
+                .values()
+                .find_map(|inner_map| inner_map.get(&proposal_id).cloned())
+                .ok_or(format!(
+                    "there is no current active proposal with id : {proposal_id}"
+                ))?;
+
+            match proposal.get_status() {
+                ProposalStatus::Succeeded => {
+                    let previous_status = proposal.queue();
+                    self.emit_proposal_status_changed(proposal_id, &proposal, previous_status);
+
+                    if self.proposal_execution_delay > 0 {
+                        return Ok(format!(
+                            "proposal with id : {proposal_id} is queued and can be executed in {} seconds",
+                            self.proposal_execution_delay
+                        ));
+                    }
+                }
+                ProposalStatus::Queued => {
+                    if !proposal.is_executable() {
+                        return Err(format!(
+                            "the execution delay of proposal with id : {proposal_id} has not passed yet"
+                        ));
+                    }
+                }
+                ProposalStatus::Defeated => {
+                    let check = proposal.get_passing_check();
+                    self.reject_defeated_proposal(proposal_id, &proposal);
+                    self.close_finished_proposal(proposal_id, proposal);
+
+                    return Err(format!(
+                        "proposal with id : {proposal_id} did not pass: quorum met {}, turnout {}% met {}, approval {}% met {}",
+                        check.quorum_met,
+                        check.turnout,
+                        check.turnout_met,
+                        check.approval,
+                        check.approval_met
+                    ));
+                }
+                ProposalStatus::Expired => {
+                    self.close_finished_proposal(proposal_id, proposal);
+                    return Err(format!(
+                        "proposal with id : {proposal_id} was not executed in time and has expired"
+                    ));
+                }
+                status => {
+                    return Err(format!(
+                        "proposal with id : {proposal_id} can not be executed while it is {:?}",
+                        status
+                    ));
                 }
             }
 
-            // If the proposal is found, execute it
-            if let (Some(proposal), Some(bond_creator_address), Some(target_xrd_amount)) = (
-                proposal_option,
-                bond_creator_address_option,
-                target_xrd_amount_option,
-            ) {
-                // let current_epoch = Runtime::current_epoch();
-                // let current_time_seconds = current_epoch.number() as i64;
-                let now: Instant = Clock::current_time_rounded_to_seconds();
-                let current_time_seconds: i64 = now.seconds_since_unix_epoch;
-
-                let last_time = proposal.get_last_time();
-                let end_time_seconds = last_time.to_instant().seconds_since_unix_epoch;
+            let action = proposal.get_action();
 
-                // Debug statements to verify the times
-                println!("Current time (epoch seconds): {}", current_time_seconds);
-                println!("Proposal end time (epoch seconds): {}", end_time_seconds);
+            let result = match action.clone() {
+                ProposalAction::PurchaseBond {
+                    bond_creator_address,
+                    target_xrd_amount,
+                } => self.execute_bond_purchase(
+                    proposal_id,
+                    &proposal,
+                    bond_creator_address,
+                    target_xrd_amount,
+                ),
+                ProposalAction::MintTokens { amount } => {
+                    self.mint_more_tokens(amount);
+                    Ok(format!("{amount} tokens minted"))
+                }
+                ProposalAction::ChangePrice {
+                    token_price,
+                    buy_back_price,
+                } => self.execute_price_change(proposal_id, &proposal, token_price, buy_back_price),
+                ProposalAction::TreasuryTransfer { recipient, amount } => {
+                    self.execute_treasury_transfer(proposal_id, recipient, amount)
+                }
+                ProposalAction::ConfigChange(change) => self.execute_config_change(proposal_id, change),
+                ProposalAction::OnboardIssuer { application_id } => {
+                    self.execute_issuer_onboarding(proposal_id, &proposal, application_id)
+                }
+                ProposalAction::AdmitMember { application_id } => {
+                    self.execute_member_admission(proposal_id, &proposal, application_id)
+                }
+                ProposalAction::InsuranceClaim { .. } => Err(format!(
+                    "proposal with id : {proposal_id} is an insurance claim and can not be executed by this DAO"
+                )),
+            };
 
-                assert!(
-                    current_time_seconds > end_time_seconds,
-                    "Proposal can only be executed after the specified end time"
-                );
+            // a failed action leaves the proposal queued, it can be retried until it expires
+            if result.is_ok() {
+                let previous_status = proposal.mark_executed();
+                self.emit_proposal_status_changed(proposal_id, &proposal, previous_status);
+                self.archive_proposal(proposal_id);
 
-                let bond_components = self
-                    .zero_coupon_bond
-                    .get_mut(&bond_creator_address)
-                    .unwrap();
+                Runtime::emit_event(PandaoEvent {
+                    event_type: EventType::EXECUTE_PROPOSAL,
+                    dao_type: DaoType::Investment,
+                    component_address: Runtime::global_address(),
+                    meta_data: DaoEvent::ProposalExecute(PraposalExecute {
+                        praposal_address: proposal.address(),
+                        proposal_id,
+                        action,
+                        tally: proposal.get_vote_tally(),
+                    }),
+                });
+            }
 
-                //*we can restrict a creator in terms of bond creation
-                let latest_bond_component =
-                    bond_components.last_mut().expect("No bond component found");
+            result
+        }
 
-                let bond_uid = latest_bond_component.get_bond_u_id();
+        /// Closes a defeated or expired proposal and moves it to the history.
+        pub fn close_proposal(&mut self, proposal_id: usize) -> ProposalStatus {
+            let proposal = self
+                .current_praposals
+                .values()
+                .find_map(|inner_map| inner_map.get(&proposal_id).cloned())
+                .expect("there is no current active proposal with the given ID");
 
-                // Check if the minimum quorum is met
-                let number_of_voters = proposal.get_number_of_voters(); //inline attribute on fn definition
-                let minimum_quorum = proposal.get_minimum_quorum();
-                // let minimum_quorum = min_quo.0.to_u64().expect("Invalid minimum quorum value") as usize;
+            if proposal.get_status() == ProposalStatus::Defeated {
+                self.reject_defeated_proposal(proposal_id, &proposal);
+            }
 
-                if number_of_voters < minimum_quorum {
-                    // Emit an event indicating that the proposal cannot be executed due to insufficient participation
-                    let event_metadata = ProposalQuorumNotMet {
-                        proposal_id,
-                        minimum_quorum: proposal.get_minimum_quorum(),
-                        number_of_voters,
-                        bond_creator_address,
-                        contract_identity: bond_uid.clone(),
-                        proposal_type: EventType::PROPOSAL_TO_PURCHASE_BOND,
-                    };
+            self.close_finished_proposal(proposal_id, proposal)
+        }
 
-                    let component_address = Runtime::global_address();
+        fn reject_defeated_proposal(
+            &mut self,
+            proposal_id: usize,
+            proposal: &Global<TokenWeightProposal>,
+        ) {
+            let action = proposal.get_action();
+            let (voted_for, voted_against) = proposal.get_votes();
+
+            let event_metadata = ProposalRulesNotMet {
+                proposal_id,
+                praposal_address: proposal.address(),
+                category: action.category(),
+                passing_rule: proposal.get_passing_rule(),
+                check: proposal.get_passing_check(),
+                voted_for,
+                voted_against,
+                number_of_voters: proposal.get_number_of_voters(),
+                minimum_quorum: proposal.get_minimum_quorum(),
+            };
 
-                    Runtime::emit_event(PandaoEvent {
-                        event_type: EventType::QUORUM_NOT_MET_AND_FAILED,
-                        dao_type: DaoType::Investment,
-                        component_address,
-                        meta_data: DaoEvent::ProposalQuorumNotMet(event_metadata),
-                    });
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::PROPOSAL_RULES_NOT_MET,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::ProposalRulesNotMet(event_metadata),
+            });
 
-                    // panic!("The proposal cannot be executed due to insufficient participation");
+            // a voted down application is rejected so the account can apply again
+            match action {
+                ProposalAction::OnboardIssuer { application_id } => {
+                    let _ = self.execute_issuer_onboarding(proposal_id, proposal, application_id);
                 }
+                ProposalAction::AdmitMember { application_id } => {
+                    let _ = self.execute_member_admission(proposal_id, proposal, application_id);
+                }
+                _ => {}
+            }
+        }
+
+        pub fn set_passing_rule(&mut self, category: ProposalCategory, rule: PassingRule) {
+            self.assert_valid_passing_rule(category, &rule);
+            self.store_passing_rule(category, rule);
+        }
 
-                // Check if the treasury has enough XRD
-                let treasury_balance = self.shares.amount();
+        pub fn get_passing_rules(&self) -> HashMap<ProposalCategory, PassingRule> {
+            self.passing_rules.clone()
+        }
 
-                assert!(
-                    treasury_balance >= target_xrd_amount,
-                    "Insufficient funds in the treasury to execute the proposal."
-                );
+        // categories without a rule of their own pass on a simple majority
+        fn passing_rule(&self, category: ProposalCategory) -> PassingRule {
+            self.passing_rules
+                .get(&category)
+                .cloned()
+                .unwrap_or(PassingRule::simple_majority())
+        }
 
-                // Create a bucket with the exact XRD amount needed for the purchase
-                let payment = self.shares.take(target_xrd_amount);
+        fn assert_valid_passing_rule(&self, category: ProposalCategory, rule: &PassingRule) {
+            assert!(
+                category != ProposalCategory::InsuranceClaim,
+                "insurance claims are voted on in an insurance DAO"
+            );
+            rule.assert_valid();
+        }
 
-                // Call the purchase_bond function
-                let remaining = self.purchase_bond(bond_creator_address.clone(), payment);
+        fn store_passing_rule(&mut self, category: ProposalCategory, rule: PassingRule) {
+            self.passing_rules.insert(category, rule.clone());
 
-                // Handle remaining funds and received bond NFT
-                self.shares.put(remaining);
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::PASSING_RULE_SET,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::PassingRuleSet(PassingRuleSet { category, rule }),
+            });
+        }
 
-                let event_metadata = ProposalQuorumMet {
-                    proposal_id,
-                    minimum_quorum: proposal.get_minimum_quorum(),
-                    number_of_voters,
-                    //bond creator address
-                    bond_creator_address,
-                    contract_identity: bond_uid,
-                    proposal_type: EventType::PROPOSAL_TO_PURCHASE_BOND,
-                };
+        // governance tokens held outside the DAO's own vault
+        fn circulating_supply(&self) -> Decimal {
+            self.dao_token_resource_manager
+                .total_supply()
+                .unwrap_or(Decimal::zero())
+                - self.dao_token.amount()
+        }
 
-                let component_address = Runtime::global_address();
+        pub fn cancel_proposal(&mut self, proposal_id: usize) {
+            let proposal = self
+                .current_praposals
+                .values()
+                .find_map(|inner_map| inner_map.get(&proposal_id).cloned())
+                .expect("there is no current active proposal with the given ID");
 
-                //what to emit?
-                //1. success or unsuccess
-                //2. bond creator address + contract || bond id
+            let previous_status = proposal.cancel();
+            self.emit_proposal_status_changed(proposal_id, &proposal, previous_status);
+            self.archive_proposal(proposal_id);
+        }
 
-                Runtime::emit_event(PandaoEvent {
-                    event_type: EventType::QUORUM_MET_AND_SUCCESS,
-                    dao_type: DaoType::Investment,
-                    component_address,
-                    meta_data: DaoEvent::ProposalQuorumMet(event_metadata),
-                });
+        pub fn get_proposal_status(&self, proposal_id: usize) -> ProposalStatus {
+            self.get_proposal_using_proposal_id(proposal_id)
+                .expect("proposal with the given ID not found")
+                .get_status()
+        }
 
-                // Optionally, you can remove the executed proposal from the current proposals
-                for (_, inner_map) in &mut self.current_praposals {
-                    inner_map.remove(&proposal_id);
-                }
-            } else {
-                panic!("there is no current active proposal with the given ID");
-            }
+        pub fn get_proposal_history(&self) -> HashMap<usize, Global<TokenWeightProposal>> {
+            self.proposal_history.clone()
         }
 
-        pub fn vote(
+        pub fn set_proposal_execution_policy(&mut self, execution_delay: u64, execution_window: u64) {
+            assert!(
+                execution_window > execution_delay,
+                "the execution window must be longer than the execution delay"
+            );
+
+            // only applies to proposals created afterwards
+            self.proposal_execution_delay = execution_delay;
+            self.proposal_execution_window = execution_window;
+        }
+
+        fn close_finished_proposal(
             &mut self,
-            token: Bucket,
-            againsts: bool,
-            // account: Global<Account>,
-            your_address: ComponentAddress,
             proposal_id: usize,
-        ) -> Bucket {
-            // let owner_role_of_voter = account.get_owner_role();
-            // Runtime::assert_access_rule(owner_role_of_voter.rule);
-
-            // Find the proposal by proposal_id
-            let mut proposal_option = None;
+            proposal: Global<TokenWeightProposal>,
+        ) -> ProposalStatus {
+            let previous_status = proposal.close();
+            self.emit_proposal_status_changed(proposal_id, &proposal, previous_status);
+            self.archive_proposal(proposal_id);
+            proposal.get_status()
+        }
 
-            for (_, inner_map) in &self.current_praposals {
-                if let Some(proposal) = inner_map.get(&proposal_id) {
-                    proposal_option = Some(proposal.clone());
-                    break;
+        // finished proposals stay queryable in the history
+        fn archive_proposal(&mut self, proposal_id: usize) {
+            for inner_map in self.current_praposals.values_mut() {
+                if let Some(proposal) = inner_map.remove(&proposal_id) {
+                    self.proposal_history.insert(proposal_id, proposal);
                 }
             }
 
-            if let Some(proposal) = proposal_option {
-                assert_eq!(
-                    token.resource_address(),
-                    self.dao_token_address,
-                    "wrong voting token supplied"
-                );
+            // creators without open proposals are dropped, so `withdraw_power` sees when nothing is open
+            self.current_praposals.retain(|_, inner_map| !inner_map.is_empty());
+        }
+
+        fn emit_proposal_status_changed(
+            &self,
+            proposal_id: usize,
+            proposal: &Global<TokenWeightProposal>,
+            previous_status: ProposalStatus,
+        ) {
+            let event_metadata = ProposalStatusChanged {
+                proposal_id,
+                praposal_address: proposal.address(),
+                previous_status,
+                current_status: proposal.get_status(),
+            };
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::PROPOSAL_STATUS_CHANGED,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::ProposalStatusChanged(event_metadata),
+            });
+        }
+
+        fn execute_bond_purchase(
+            &mut self,
+            proposal_id: usize,
+            proposal: &Global<TokenWeightProposal>,
+            bond_creator_address: ComponentAddress,
+            target_xrd_amount: Decimal,
+        ) -> Result<String, String> {
+            let bond_components = self
+                .zero_coupon_bond
+                .get_mut(&bond_creator_address)
+                .unwrap();
+
+            //*we can restrict a creator in terms of bond creation
+            let latest_bond_component =
+                bond_components.last_mut().expect("No bond component found");
+
+            let bond_uid = latest_bond_component.get_bond_u_id();
+
+            // the passing rules were checked before the proposal could be queued
+            let number_of_voters = proposal.get_number_of_voters(); //inline attribute on fn definition
+            let minimum_quorum = proposal.get_minimum_quorum();
+
+            // Check if the treasury has enough XRD
+            let treasury_balance = self.shares.amount();
+
+            assert!(
+                treasury_balance >= target_xrd_amount,
+                "Insufficient funds in the treasury to execute the proposal."
+            );
+
+            // the purchase must not leave the projected treasury below the minimum liquidity
+            let projected_ladder = self.project_liquidity_ladder(
+                treasury_balance - target_xrd_amount,
+                self.liquidity_ladder_interval,
+                self.liquidity_ladder_rungs,
+            );
+
+            assert!(
+                projected_ladder.lowest_balance >= self.minimum_liquidity,
+                "The purchase would leave the treasury below the minimum liquidity."
+            );
+
+            // Create a bucket with the exact XRD amount needed for the purchase
+            let payment = self.shares.take(target_xrd_amount);
+
+            // Call the purchase_bond function
+            let remaining = self.purchase_bond(bond_creator_address, payment);
+
+            // Handle remaining funds and received bond NFT
+            self.shares.put(remaining);
+
+            let event_metadata = ProposalQuorumMet {
+                proposal_id,
+                minimum_quorum,
+                number_of_voters,
+                //bond creator address
+                bond_creator_address,
+                contract_identity: bond_uid,
+                proposal_type: EventType::PROPOSAL_TO_PURCHASE_BOND,
+            };
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::QUORUM_MET_AND_SUCCESS,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::ProposalQuorumMet(event_metadata),
+            });
+
+            Ok("bonds purchased".to_string())
+        }
+
+        fn execute_price_change(
+            &mut self,
+            proposal_id: usize,
+            proposal: &Global<TokenWeightProposal>,
+            desired_price: Decimal,
+            buy_back: Decimal,
+        ) -> Result<String, String> {
+            let number_of_voters = proposal.get_number_of_voters(); //inline attribute on fn definition
+            let minimum_quorum = proposal.get_minimum_quorum();
+
+            self.set_price(desired_price, buy_back);
+
+            let event_metadata = PriceChangeProposalQuorumMet {
+                proposal_id,
+                minimum_quorum,
+                number_of_voters,
+                desired_token_price: desired_price,
+                desired_token_buy_back_price: buy_back,
+                proposal_type: EventType::PROPOSAL_TO_CHANGE_TOKEN_PRICE,
+            };
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::PRICE_CHANGE_QUORUM_MET_AND_SUCCESS,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::PriceChangeProposalQuorumMet(event_metadata),
+            });
+
+            Ok("token price changed".to_string())
+        }
+
+        fn execute_treasury_transfer(
+            &mut self,
+            proposal_id: usize,
+            recipient: ComponentAddress,
+            amount: Decimal,
+        ) -> Result<String, String> {
+            let treasury_balance = self.shares.amount();
+
+            assert!(
+                treasury_balance >= amount,
+                "Insufficient funds in the treasury to execute the proposal."
+            );
+
+            // same liquidity floor as bond purchases
+            let projected_ladder = self.project_liquidity_ladder(
+                treasury_balance - amount,
+                self.liquidity_ladder_interval,
+                self.liquidity_ladder_rungs,
+            );
+
+            assert!(
+                projected_ladder.lowest_balance >= self.minimum_liquidity,
+                "The transfer would leave the treasury below the minimum liquidity."
+            );
+
+            let mut recipient_account: Global<Account> = recipient.into();
+            recipient_account.try_deposit_or_abort(self.shares.take(amount), None);
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::TREASURY_TRANSFER,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::TreasuryTransfer(TreasuryTransfer {
+                    proposal_id,
+                    recipient,
+                    amount,
+                }),
+            });
+
+            Ok(format!("{amount} XRD transferred from the treasury"))
+        }
+
+        fn execute_config_change(
+            &mut self,
+            proposal_id: usize,
+            change: ConfigChange,
+        ) -> Result<String, String> {
+            match change.clone() {
+                ConfigChange::ProposalCreationRight(proposal_creation_right) => {
+                    self.proposal_creation_right = proposal_creation_right;
+                }
+                ConfigChange::LiquidityPolicy {
+                    minimum_liquidity,
+                    interval,
+                    number_of_rungs,
+                } => {
+                    self.set_liquidity_policy(minimum_liquidity, interval, number_of_rungs);
+                }
+                ConfigChange::PassingRule { category, rule } => {
+                    self.store_passing_rule(category, rule);
+                }
+            }
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::CONFIG_CHANGED,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::ConfigChanged(ConfigChanged { proposal_id, change }),
+            });
+
+            Ok("configuration changed".to_string())
+        }
+
+        // last step of issuer onboarding: the application gets an issuer badge or is rejected, following the vote
+        fn execute_issuer_onboarding(
+            &mut self,
+            proposal_id: usize,
+            proposal: &Global<TokenWeightProposal>,
+            application_id: u64,
+        ) -> Result<String, String> {
+            let now: Instant = Clock::current_time_rounded_to_seconds();
+            let current_time_seconds: i64 = now.seconds_since_unix_epoch;
+
+            let application = self
+                .issuer_applications
+                .get_mut(&application_id)
+                .ok_or(format!("no issuer application found with id : {application_id}"))?;
+
+            if application.status != IssuerApplicationStatus::Pending {
+                return Err(format!("issuer application {application_id} is not pending"));
+            }
+
+            let number_of_voters = proposal.get_number_of_voters();
+            let minimum_quorum = proposal.get_minimum_quorum();
+            let (voted_for, voted_against) = proposal.get_votes();
+
+            let approved = proposal.get_passing_check().passed;
+
+            let account_address = application.account_address;
+
+            let issuer_badge_id = if approved {
+                application.status = IssuerApplicationStatus::Approved;
+
+                let issuer_badge_bucket = self.issuer_badge_resource_manager.mint_non_fungible(
+                    &NonFungibleLocalId::integer(application_id),
+                    IssuerBadge {
+                        issuer_name: application.issuer_name.clone(),
+                        account_address,
+                        approved_at: current_time_seconds as u64,
+                    },
+                );
+
+                let issuer_badge_id = issuer_badge_bucket.non_fungible_local_id();
+
+                let mut issuer_account: Global<Account> = account_address.into();
+                issuer_account.try_deposit_or_abort(issuer_badge_bucket.into(), None);
+
+                Some(issuer_badge_id)
+            } else {
+                application.status = IssuerApplicationStatus::Rejected;
+                None
+            };
+
+            let event_metadata = IssuerOnboardingDecision {
+                proposal_id,
+                application_id,
+                account_address,
+                approved,
+                voted_for,
+                voted_against,
+                number_of_voters,
+                minimum_quorum,
+                issuer_badge_id,
+            };
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: if approved {
+                    EventType::ISSUER_ONBOARDED
+                } else {
+                    EventType::ISSUER_ONBOARDING_REJECTED
+                },
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::IssuerOnboardingDecision(event_metadata),
+            });
+
+            if approved {
+                Ok("issuer onboarded and issuer badge minted".to_string())
+            } else {
+                Err(format!(
+                    "issuer application {application_id} was rejected by the vote"
+                ))
+            }
+        }
+
+        // last step of a voted membership application: the account gets a member badge or is rejected, following the vote
+        fn execute_member_admission(
+            &mut self,
+            proposal_id: usize,
+            proposal: &Global<TokenWeightProposal>,
+            application_id: u64,
+        ) -> Result<String, String> {
+            let application = self
+                .membership_applications
+                .get(&application_id)
+                .ok_or(format!("no membership application found with id : {application_id}"))?
+                .clone();
+
+            if application.status != MembershipApplicationStatus::Pending {
+                return Err(format!("membership application {application_id} is not pending"));
+            }
+
+            let number_of_voters = proposal.get_number_of_voters();
+            let minimum_quorum = proposal.get_minimum_quorum();
+            let (voted_for, voted_against) = proposal.get_votes();
+
+            let approved = proposal.get_passing_check().passed
+                && !self.is_active_member(application.account_address);
+
+            let member_id = if approved {
+                let member_badge_bucket =
+                    self.admit_member(application.account_address, application.display_name.clone());
+
+                let member_id = Self::member_id(&member_badge_bucket.non_fungible_local_id());
+
+                let mut member_account: Global<Account> = application.account_address.into();
+                member_account.try_deposit_or_abort(member_badge_bucket.into(), None);
+
+                Some(member_id)
+            } else {
+                None
+            };
+
+            if let Some(application) = self.membership_applications.get_mut(&application_id) {
+                application.status = if approved {
+                    MembershipApplicationStatus::Approved
+                } else {
+                    MembershipApplicationStatus::Rejected
+                };
+            }
+
+            let event_metadata = MembershipDecision {
+                proposal_id,
+                application_id,
+                account_address: application.account_address,
+                approved,
+                voted_for,
+                voted_against,
+                number_of_voters,
+                minimum_quorum,
+                member_id,
+            };
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: if approved {
+                    EventType::MEMBERSHIP_APPROVED
+                } else {
+                    EventType::MEMBERSHIP_REJECTED
+                },
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::MembershipDecision(event_metadata),
+            });
+
+            if approved {
+                Ok("member admitted and member badge minted".to_string())
+            } else {
+                Err(format!(
+                    "membership application {application_id} was rejected by the vote"
+                ))
+            }
+        }
+
+        pub fn vote(
+            &mut self,
+            token: Bucket,
+            againsts: bool,
+            account: Global<Account>,
+            proposal_id: usize,
+        ) -> Bucket {
+            // voter records are keyed by the account whose owner signed the transaction
+            let voter_address = Self::authenticate_account(&account);
+
+            self.cast_vote(token, againsts, None, voter_address, proposal_id, false)
+        }
+
+        /// Votes on a quadratic proposal, one member badge per voter so tokens split across accounts do not add weight.
+        pub fn vote_quadratic(
+            &mut self,
+            token: Bucket,
+            againsts: bool,
+            member_badge: Proof,
+            proposal_id: usize,
+        ) -> Bucket {
+            let (_, voter_address) = self.authenticate_member(member_badge);
+            self.assert_still_eligible(voter_address, token.amount());
+
+            self.cast_vote(token, againsts, None, voter_address, proposal_id, true)
+        }
+
+        /// Casts a ballot on a proposal with options, `choices` are option indexes in order of preference.
+        pub fn vote_on_options(
+            &mut self,
+            token: Bucket,
+            choices: Vec<usize>,
+            account: Global<Account>,
+            proposal_id: usize,
+        ) -> Bucket {
+            let voter_address = Self::authenticate_account(&account);
+
+            self.cast_vote(token, false, Some(choices), voter_address, proposal_id, false)
+        }
+
+        pub fn vote_quadratic_on_options(
+            &mut self,
+            token: Bucket,
+            choices: Vec<usize>,
+            member_badge: Proof,
+            proposal_id: usize,
+        ) -> Bucket {
+            let (_, voter_address) = self.authenticate_member(member_badge);
+            self.assert_still_eligible(voter_address, token.amount());
+
+            self.cast_vote(token, false, Some(choices), voter_address, proposal_id, true)
+        }
 
-                // Get the voter address from the account
-                let voter_address = your_address;
+        fn cast_vote(
+            &mut self,
+            token: Bucket,
+            againsts: bool,
+            // None on yes/no proposals
+            choices: Option<Vec<usize>>,
+            voter_address: ComponentAddress,
+            proposal_id: usize,
+            member_vote: bool,
+        ) -> Bucket {
+            // Find the proposal by proposal_id
+            let mut proposal_option = None;
+
+            for inner_map in self.current_praposals.values() {
+                if let Some(proposal) = inner_map.get(&proposal_id) {
+                    proposal_option = Some(*proposal);
+                    break;
+                }
+            }
+
+            if let Some(proposal) = proposal_option {
+                assert_eq!(
+                    token.resource_address(),
+                    self.dao_token_address,
+                    "wrong voting token supplied"
+                );
+
+                assert!(
+                    proposal.get_status() == ProposalStatus::Active,
+                    "proposal with id : {} is not open for voting, it is {:?}",
+                    proposal_id,
+                    proposal.get_status()
+                );
+
+                assert!(
+                    !(self.one_vote_per_member
+                        && choices.is_none()
+                        && proposal.get_voting_type() == VotingType::Equality),
+                    "this DAO counts one vote per member badge on equality proposals, please use vote_as_member"
+                );
+
+                assert!(
+                    member_vote || proposal.get_voting_type() != VotingType::Quadratic,
+                    "quadratic proposals only take votes from members, please vote with your member badge"
+                );
 
-                let mut vote_caster_addresses = proposal.get_vote_caster_addresses();
+                let vote_caster_addresses = proposal.get_vote_caster_addresses();
 
                 // Check if the voter has already voted
                 assert!(
@@ -1488,7 +2700,9 @@ mod radixdao {
                 let event_metadata = ProposalVote {
                     praposal_address: proposal.address(),
                     voting_amount: amount,
+                    voting_weight: proposal.vote_weight(amount),
                     againts: againsts,
+                    choices: choices.clone().unwrap_or_default(),
                     voter_address,
                     proposal_id,
                 };
@@ -1500,102 +2714,1354 @@ mod radixdao {
                     meta_data: DaoEvent::PraposalVote(event_metadata),
                 });
 
-                let result = proposal.vote(token, againsts);
+                self.override_delegate(proposal_id, &proposal, voter_address);
+
+                // the tokens stay in the proposal until voting ends, so they can not vote twice
+                match choices.clone() {
+                    Some(choices) => proposal.vote_on_options(token, choices),
+                    None => proposal.vote(token, againsts),
+                }
 
                 // Mark this voter as having voted
                 proposal.set_vote_caster_address(voter_address);
 
-                result
+                self.cast_delegated_votes(proposal_id, &proposal, voter_address, againsts);
+
+                self.vote_receipt_counter += 1;
+
+                self.vote_receipt_resource_manager
+                    .mint_non_fungible(
+                        &NonFungibleLocalId::integer(self.vote_receipt_counter),
+                        VoteReceipt {
+                            proposal_id,
+                            praposal_address: proposal.address(),
+                            amount,
+                            against: againsts,
+                            choices: choices.unwrap_or_default(),
+                        },
+                    )
+                    .into()
             } else {
-                assert!(false, "no active proposal with the given ID");
-                panic!();
+                panic!("no active proposal with the given ID");
             }
         }
 
-        pub fn create_zero_coupon_bond(
-            &mut self,
-            contract_type: String,
-            contract_role: String,
-            contract_identifier: String,
-            nominal_interest_rate: Decimal,
-            currency: String,
-            initial_exchange_date: u64,
-            maturity_date: u64,
-            notional_principal: Decimal,
-            discount: u64,
-            bond_position: String,
-            price: u64,
-            number_of_bonds: Decimal,
-            your_address: ComponentAddress,
-            nft_as_collateral: Bucket, //OK -> Account address is of ComponentAddress Type
-        ) -> Global<ZeroCouponBond> {
-            let collateral_resource_address = nft_as_collateral.resource_address();
-
-            let bond_component = ZeroCouponBond::instantiate_zerocouponbond(
-                contract_type.clone(),
-                contract_role.clone(),
-                contract_identifier.clone(),
-                nominal_interest_rate,
-                currency.clone(),
-                initial_exchange_date,
-                maturity_date,
-                notional_principal,
-                discount,
-                bond_position.clone(),
-                price,
-                number_of_bonds,
-                nft_as_collateral,
+        /// Hands back the voting tokens behind a vote receipt once voting on its proposal has ended.
+        pub fn claim_back_vote_tokens(&mut self, vote_receipt: Bucket) -> Bucket {
+            assert_eq!(
+                vote_receipt.resource_address(),
+                self.vote_receipt_resource_manager.address(),
+                "please supply a vote receipt of this DAO"
+            );
+
+            let vote_receipt = vote_receipt.as_non_fungible();
+            let receipt = vote_receipt.non_fungible::<VoteReceipt>();
+            let receipt_id = receipt.local_id().clone();
+            let receipt_data = receipt.data();
+
+            let proposal: Global<TokenWeightProposal> = receipt_data.praposal_address.into();
+            let tokens = proposal.release_escrow(receipt_data.amount);
+
+            vote_receipt.burn();
+
+            let event_metadata = VoteTokensClaimedBack {
+                proposal_id: receipt_data.proposal_id,
+                praposal_address: receipt_data.praposal_address,
+                amount: receipt_data.amount,
+                receipt_id,
+            };
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::VOTE_TOKENS_CLAIMED_BACK,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::VoteTokensClaimedBack(event_metadata),
+            });
+
+            tokens
+        }
+
+        fn authenticate_account(account: &Global<Account>) -> ComponentAddress {
+            // the transaction must satisfy the owner role of the account, i.e. be signed by its owner
+            let owner_role_of_account = account.get_owner_role();
+            Runtime::assert_access_rule(owner_role_of_account.rule);
+            account.address()
+        }
+
+        fn assert_proposal_creation_right(&self, governance_token_or_owner_token_address: &Bucket) {
+            match self.proposal_creation_right {
+                ProposalCreationRight::EVERYONE => {
+                    assert_eq!(
+                        governance_token_or_owner_token_address.resource_address(),
+                        self.dao_token_address,
+                        "wrong voting token supplied! please make sure that you supply DAO Governance Token"
+                    );
+
+                    assert!(
+                        governance_token_or_owner_token_address.amount() >= Decimal::one(),
+                        "Proposal creator must have at least one governance token to create a proposal"
+                    );
+                }
+                ProposalCreationRight::TOKEN_HOLDER_THRESHOLD(threshold) => {
+                    assert_eq!(
+                        governance_token_or_owner_token_address.resource_address(),
+                        self.dao_token_address,
+                        "wrong voting token supplied! please make sure that you supply DAO Governance Token"
+                    );
+
+                    assert!(
+                        governance_token_or_owner_token_address.amount() >= threshold,
+                        "Proposal creator does not have enough tokens to meet the threshold"
+                    );
+                }
+                ProposalCreationRight::ADMIN => {
+                    assert_eq!(
+                        governance_token_or_owner_token_address.resource_address(),
+                        self.owner_token_addresss,
+                        "Only the admin can create a proposal and If you are an Admin please make sure you pass OWNER TOKEN ADDRESS"
+                    );
+
+                    assert!(
+                        governance_token_or_owner_token_address.amount() >= Decimal::one(),
+                        "ADMIN must pass his/her OWNER TOKEN to create proposal"
+                    );
+                }
+            }
+        }
+
+        // step 1 of issuer onboarding: the account owner applies, the DAO then votes on the application
+        pub fn apply_as_issuer(
+            &mut self,
+            account: Global<Account>,
+            issuer_name: String,
+            description: String,
+        ) -> u64 {
+            let account_address = Self::authenticate_account(&account);
+
+            assert!(
+                !self.issuer_applications.values().any(|application| {
+                    application.account_address == account_address
+                        && application.status != IssuerApplicationStatus::Rejected
+                }),
+                "this account already has a pending or approved issuer application"
+            );
+
+            self.issuer_application_counter += 1;
+            let application_id = self.issuer_application_counter;
+
+            let now: Instant = Clock::current_time_rounded_to_seconds();
+
+            self.issuer_applications.insert(
+                application_id,
+                IssuerApplication {
+                    account_address,
+                    issuer_name: issuer_name.clone(),
+                    description: description.clone(),
+                    applied_at: now.seconds_since_unix_epoch as u64,
+                    status: IssuerApplicationStatus::Pending,
+                },
+            );
+
+            let event_metadata = IssuerApplied {
+                application_id,
+                account_address,
+                issuer_name,
+                description,
+            };
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::ISSUER_APPLIED,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::IssuerApplied(event_metadata),
+            });
+
+            application_id
+        }
+
+        pub fn get_issuer_applications(&self) -> HashMap<u64, IssuerApplication> {
+            self.issuer_applications.clone()
+        }
+
+        // open and token threshold policies admit at once, under the vote policy the badge arrives once an AdmitMember proposal passes
+        pub fn become_a_dao_member(
+            &mut self,
+            account: Global<Account>,
+            display_name: String,
+            governance_tokens: Option<Proof>,
+        ) -> Option<Bucket> {
+            let account_address = Self::authenticate_account(&account);
+
+            assert!(
+                !self.is_active_member(account_address),
+                "this account is already a member of the DAO"
+            );
+
+            match self.join_policy.clone() {
+                JoinPolicy::Open => {}
+                JoinPolicy::TokenThreshold(threshold) => {
+                    let governance_tokens = governance_tokens
+                        .expect("please supply a proof of your governance tokens")
+                        .check_with_message(
+                            self.dao_token_address,
+                            "wrong governance token supplied",
+                        );
+
+                    assert!(
+                        governance_tokens.amount() >= threshold,
+                        "at least {} governance tokens are needed to join the DAO",
+                        threshold
+                    );
+                }
+                JoinPolicy::Vote => {
+                    assert!(
+                        !self.membership_applications.values().any(|application| {
+                            application.account_address == account_address
+                                && application.status == MembershipApplicationStatus::Pending
+                        }),
+                        "this account already has a pending membership application"
+                    );
+
+                    self.membership_application_counter += 1;
+                    let application_id = self.membership_application_counter;
+
+                    let now: Instant = Clock::current_time_rounded_to_seconds();
+
+                    self.membership_applications.insert(
+                        application_id,
+                        MembershipApplication {
+                            account_address,
+                            display_name: display_name.clone(),
+                            applied_at: now.seconds_since_unix_epoch as u64,
+                            status: MembershipApplicationStatus::Pending,
+                        },
+                    );
+
+                    Runtime::emit_event(PandaoEvent {
+                        event_type: EventType::MEMBERSHIP_APPLIED,
+                        dao_type: DaoType::Investment,
+                        component_address: Runtime::global_address(),
+                        meta_data: DaoEvent::MembershipApplied(MembershipApplied {
+                            application_id,
+                            account_address,
+                            display_name,
+                        }),
+                    });
+
+                    return None;
+                }
+            }
+
+            Some(self.admit_member(account_address, display_name).into())
+        }
+
+        // badges can not be withdrawn, so like an expelled member's badge it stays and the registry no longer accepts it
+        pub fn leave_dao(&mut self, member_badge: Proof) {
+            let (member_id, account_address) = self.authenticate_member(member_badge);
+
+            self.members
+                .get_mut(&member_id)
+                .expect("no member found for this badge")
+                .status = MemberStatus::Left;
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::MEMBER_LEFT,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::MemberLeft(MemberLeft {
+                    member_id,
+                    account_address,
+                }),
+            });
+        }
+
+        // the badge stays where it is but the registry no longer accepts it, the owner can recall it
+        pub fn expel_member(&mut self, member_id: u64, reason: String) {
+            let record = self
+                .members
+                .get_mut(&member_id)
+                .expect("no member found with the given id");
+
+            assert!(record.status == MemberStatus::Active, "this membership has already ended");
+
+            record.status = MemberStatus::Expelled;
+
+            let event_metadata = MemberExpelled {
+                member_id,
+                account_address: record.account_address,
+                reason,
+            };
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::MEMBER_EXPELLED,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::MemberExpelled(event_metadata),
+            });
+        }
+
+        pub fn set_member_role_tags(&mut self, member_id: u64, role_tags: Vec<String>) {
+            let record = self
+                .members
+                .get(&member_id)
+                .expect("no member found with the given id");
+
+            assert!(record.status == MemberStatus::Active, "this membership has already ended");
+
+            self.member_badge_resource_manager.update_non_fungible_data(
+                &NonFungibleLocalId::integer(member_id),
+                "role_tags",
+                role_tags.clone(),
+            );
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::MEMBER_ROLE_TAGS_SET,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::MemberRoleTagsSet(MemberRoleTagsSet {
+                    member_id,
+                    role_tags,
+                }),
+            });
+        }
+
+        // pending applications keep waiting for their vote when the policy changes
+        pub fn set_join_policy(&mut self, policy: JoinPolicy) {
+            if let JoinPolicy::TokenThreshold(threshold) = &policy {
+                assert!(*threshold > Decimal::zero(), "the token threshold must be positive");
+            }
+
+            self.join_policy = policy.clone();
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::JOIN_POLICY_SET,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::JoinPolicySet(JoinPolicySet { policy }),
+            });
+        }
+
+        pub fn set_one_vote_per_member(&mut self, one_vote_per_member: bool) {
+            self.one_vote_per_member = one_vote_per_member;
+        }
+
+        /// One vote per member badge on an equality proposal, counted for the account the badge was minted to.
+        pub fn vote_as_member(&mut self, member_badge: Proof, againsts: bool, proposal_id: usize) {
+            assert!(
+                self.one_vote_per_member,
+                "this DAO counts equality votes per bucket, please use vote"
+            );
+
+            let (_, voter_address) = self.authenticate_member(member_badge);
+            self.assert_still_eligible(voter_address, Decimal::zero());
+
+            let proposal = self
+                .current_praposals
+                .values()
+                .find_map(|inner_map| inner_map.get(&proposal_id).cloned())
+                .expect("no active proposal with the given ID");
+
+            assert!(
+                proposal.get_status() == ProposalStatus::Active,
+                "proposal with id : {} is not open for voting, it is {:?}",
+                proposal_id,
+                proposal.get_status()
+            );
+
+            assert!(
+                !proposal.get_vote_caster_addresses().contains(&voter_address),
+                "You have already voted on this proposal."
+            );
+
+            let event_metadata = ProposalVote {
+                praposal_address: proposal.address(),
+                voting_amount: Decimal::one(),
+                voting_weight: Decimal::one(),
+                againts: againsts,
+                choices: Vec::new(),
+                voter_address,
+                proposal_id,
+            };
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::VOTE,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::PraposalVote(event_metadata),
+            });
+
+            self.override_delegate(proposal_id, &proposal, voter_address);
+
+            // the governance tokens in the member's account count towards turnout
+            let member_account: Global<Account> = voter_address.into();
+            proposal.vote_by_member(againsts, member_account.balance(self.dao_token_address));
+            proposal.set_vote_caster_address(voter_address);
+
+            self.cast_delegated_votes(proposal_id, &proposal, voter_address, againsts);
+        }
+
+        pub fn get_members(&self) -> HashMap<u64, MemberRecord> {
+            self.members.clone()
+        }
+
+        pub fn get_membership_applications(&self) -> HashMap<u64, MembershipApplication> {
+            self.membership_applications.clone()
+        }
+
+        pub fn get_join_policy(&self) -> JoinPolicy {
+            self.join_policy.clone()
+        }
+
+        // locked tokens are the weight a delegate votes with, they stay locked while that weight counts on an open proposal
+        pub fn lock_tokens_for_delegation(&mut self, account: Global<Account>, tokens: Bucket) {
+            let delegator = Self::authenticate_account(&account);
+
+            assert_eq!(
+                tokens.resource_address(),
+                self.dao_token_address,
+                "wrong voting token supplied"
+            );
+
+            let record = self.delegators.entry(delegator).or_insert(Delegator {
+                locked_tokens: Decimal::zero(),
+                delegate: None,
+                category_delegates: HashMap::new(),
+            });
+            record.locked_tokens += tokens.amount();
+
+            self.delegation_vault.put(tokens);
+        }
+
+        pub fn unlock_delegated_tokens(&mut self, account: Global<Account>, amount: Decimal) -> Bucket {
+            let delegator = Self::authenticate_account(&account);
+
+            assert!(
+                !self.delegated_weight_is_counted(delegator),
+                "a delegate voted with these tokens on a proposal that is still open"
+            );
+
+            let record = self
+                .delegators
+                .get_mut(&delegator)
+                .expect("this account has no tokens locked for delegation");
+
+            assert!(
+                amount > Decimal::zero() && amount <= record.locked_tokens,
+                "only {} tokens are locked for delegation",
+                record.locked_tokens
+            );
+
+            record.locked_tokens -= amount;
+
+            if record.locked_tokens == Decimal::zero()
+                && record.delegate.is_none()
+                && record.category_delegates.is_empty()
+            {
+                self.delegators.remove(&delegator);
+            }
+
+            self.delegation_vault.take(amount)
+        }
+
+        /// Delegates for every category when `category` is None, a category delegate takes precedence over it.
+        pub fn delegate_votes(
+            &mut self,
+            account: Global<Account>,
+            delegate: ComponentAddress,
+            category: Option<ProposalCategory>,
+        ) {
+            let delegator = Self::authenticate_account(&account);
+
+            assert!(delegator != delegate, "an account can not delegate to itself");
+            assert!(self.is_active_member(delegate), "votes can only be delegated to a member of the DAO");
+
+            let record = self.delegators.entry(delegator).or_insert(Delegator {
+                locked_tokens: Decimal::zero(),
+                delegate: None,
+                category_delegates: HashMap::new(),
+            });
+
+            match category {
+                Some(category) => {
+                    record.category_delegates.insert(category, delegate);
+                }
+                None => record.delegate = Some(delegate),
+            }
+
+            let event_metadata = DelegationChanged {
+                delegator,
+                delegate: Some(delegate),
+                category,
+                locked_tokens: record.locked_tokens,
+            };
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::DELEGATION_SET,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::DelegationChanged(event_metadata),
+            });
+        }
+
+        // weight a delegate already cast stays counted, the delegator can still override it by voting
+        pub fn revoke_delegation(&mut self, account: Global<Account>, category: Option<ProposalCategory>) {
+            let delegator = Self::authenticate_account(&account);
+
+            let record = self
+                .delegators
+                .get_mut(&delegator)
+                .expect("this account has not delegated its votes");
+
+            let revoked = match category {
+                Some(category) => record.category_delegates.remove(&category),
+                None => record.delegate.take(),
+            };
+
+            assert!(revoked.is_some(), "no delegation to revoke");
+
+            let event_metadata = DelegationChanged {
+                delegator,
+                delegate: None,
+                category,
+                locked_tokens: record.locked_tokens,
+            };
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::DELEGATION_REVOKED,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::DelegationChanged(event_metadata),
+            });
+        }
+
+        pub fn get_delegators(&self) -> HashMap<ComponentAddress, Delegator> {
+            self.delegators.clone()
+        }
+
+        // delegator -> the vote their delegate cast for them on this proposal
+        pub fn get_delegated_votes(&self, proposal_id: usize) -> HashMap<ComponentAddress, DelegatedVote> {
+            self.delegated_votes
+                .get(&proposal_id)
+                .cloned()
+                .unwrap_or_default()
+        }
+
+        // adds the weight of everyone who delegated this proposal's category to the voter and has not voted themselves
+        fn cast_delegated_votes(
+            &mut self,
+            proposal_id: usize,
+            proposal: &Global<TokenWeightProposal>,
+            delegate: ComponentAddress,
+            against: bool,
+        ) {
+            // delegates only vote for their delegators on yes/no proposals
+            if !self.is_active_member(delegate) || !proposal.get_options().is_empty() {
+                return;
+            }
+
+            let category = proposal.get_action().category();
+            let vote_caster_addresses = proposal.get_vote_caster_addresses();
+
+            // delegators of a quadratic proposal need their own member badge, like direct voters
+            let member_delegators_only = proposal.get_voting_type() == VotingType::Quadratic;
+            let member_delegators: HashSet<ComponentAddress> = self
+                .members
+                .values()
+                .filter(|record| record.status == MemberStatus::Active)
+                .map(|record| record.account_address)
+                .collect();
+
+            let proposal_votes = self.delegated_votes.entry(proposal_id).or_default();
+
+            let mut delegators = Vec::new();
+            let mut delegated_tokens = Decimal::zero();
+            let mut delegated_weight = Decimal::zero();
+
+            for (delegator, record) in &self.delegators {
+                if record.delegate_for(category) != Some(delegate)
+                    || record.locked_tokens == Decimal::zero()
+                    || vote_caster_addresses.contains(delegator)
+                    || proposal_votes.contains_key(delegator)
+                    || (member_delegators_only && !member_delegators.contains(delegator))
+                {
+                    continue;
+                }
+
+                delegated_weight += proposal.delegated_vote(*delegator, record.locked_tokens, against);
+                delegated_tokens += record.locked_tokens;
+                delegators.push(*delegator);
+
+                proposal_votes.insert(
+                    *delegator,
+                    DelegatedVote {
+                        delegate,
+                        tokens: record.locked_tokens,
+                        against,
+                    },
+                );
+            }
+
+            if delegators.is_empty() {
+                return;
+            }
+
+            let event_metadata = DelegatedVoteCast {
+                proposal_id,
+                praposal_address: proposal.address(),
+                delegate,
+                delegators,
+                delegated_tokens,
+                delegated_weight,
+                againts: against,
+            };
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::DELEGATED_VOTE,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::DelegatedVoteCast(event_metadata),
+            });
+        }
+
+        // a delegator voting themselves takes back the weight their delegate cast for them
+        fn override_delegate(
+            &mut self,
+            proposal_id: usize,
+            proposal: &Global<TokenWeightProposal>,
+            delegator: ComponentAddress,
+        ) {
+            let delegated_vote = match self
+                .delegated_votes
+                .get_mut(&proposal_id)
+                .and_then(|proposal_votes| proposal_votes.remove(&delegator))
+            {
+                Some(delegated_vote) => delegated_vote,
+                None => return,
+            };
+
+            let withdrawn_weight =
+                proposal.withdraw_delegated_vote(delegator, delegated_vote.tokens, delegated_vote.against);
+
+            let event_metadata = DelegateOverridden {
+                proposal_id,
+                praposal_address: proposal.address(),
+                delegator,
+                delegate: delegated_vote.delegate,
+                withdrawn_weight,
+            };
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::DELEGATE_OVERRIDDEN,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::DelegateOverridden(event_metadata),
+            });
+        }
+
+        fn delegated_weight_is_counted(&self, delegator: ComponentAddress) -> bool {
+            self.delegated_votes.iter().any(|(proposal_id, proposal_votes)| {
+                proposal_votes.contains_key(&delegator)
+                    && self
+                        .current_praposals
+                        .values()
+                        .find_map(|inner_map| inner_map.get(proposal_id))
+                        .is_some_and(|proposal| proposal.get_status() == ProposalStatus::Active)
+            })
+        }
+
+        fn admit_member(
+            &mut self,
+            account_address: ComponentAddress,
+            display_name: String,
+        ) -> NonFungibleBucket {
+            let now: Instant = Clock::current_time_rounded_to_seconds();
+            let joined_at = now.seconds_since_unix_epoch as u64;
+
+            self.member_counter += 1;
+            let member_id = self.member_counter;
+
+            let member_badge_bucket = self.member_badge_resource_manager.mint_non_fungible(
+                &NonFungibleLocalId::integer(member_id),
+                MemberBadge {
+                    display_name: display_name.clone(),
+                    account_address,
+                    joined_at,
+                    role_tags: Vec::new(),
+                },
+            );
+
+            self.members.insert(
+                member_id,
+                MemberRecord {
+                    account_address,
+                    display_name: display_name.clone(),
+                    joined_at,
+                    status: MemberStatus::Active,
+                },
+            );
+
+            let event_metadata = MemberJoined {
+                member_id,
+                account_address,
+                display_name,
+                join_policy: self.join_policy.clone(),
+            };
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::MEMBER_JOINED,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::MemberJoined(event_metadata),
+            });
+
+            member_badge_bucket
+        }
+
+        // (member id, account the badge was minted to) for a badge that is still valid
+        fn authenticate_member(&self, member_badge: Proof) -> (u64, ComponentAddress) {
+            let member_badge = member_badge
+                .check_with_message(
+                    self.member_badge_resource_manager.address(),
+                    "please supply a member badge of this DAO",
+                )
+                .as_non_fungible()
+                .non_fungible::<MemberBadge>();
+
+            let member_id = Self::member_id(member_badge.local_id());
+
+            let record = self
+                .members
+                .get(&member_id)
+                .expect("no member found for this badge");
+
+            assert!(record.status == MemberStatus::Active, "this member badge is no longer valid");
+
+            (member_id, record.account_address)
+        }
+
+        // members admitted on a token threshold have to meet it again whenever they vote,
+        // counting the tokens still in their account and the ones they are voting with
+        fn assert_still_eligible(&self, member_address: ComponentAddress, voting_tokens: Decimal) {
+            if let JoinPolicy::TokenThreshold(threshold) = self.join_policy {
+                let member_account: Global<Account> = member_address.into();
+
+                assert!(
+                    member_account.balance(self.dao_token_address) + voting_tokens >= threshold,
+                    "members need at least {} governance tokens to vote",
+                    threshold
+                );
+            }
+        }
+
+        fn is_active_member(&self, account_address: ComponentAddress) -> bool {
+            self.members.values().any(|record| {
+                record.account_address == account_address && record.status == MemberStatus::Active
+            })
+        }
+
+        fn member_id(member_badge_id: &NonFungibleLocalId) -> u64 {
+            match member_badge_id {
+                NonFungibleLocalId::Integer(id) => id.value(),
+                _ => panic!("member badges have integer ids"),
+            }
+        }
+
+        pub fn create_zero_coupon_bond(
+            &mut self,
+            contract_type: String,
+            contract_role: String,
+            contract_identifier: String,
+            nominal_interest_rate: Decimal,
+            currency: String,
+            initial_exchange_date: u64,
+            maturity_date: u64,
+            notional_principal: Decimal,
+            discount: u64,
+            bond_position: String,
+            price: u64,
+            number_of_bonds: Decimal,
+            issuer_badge: Proof,
+            nft_as_collateral: Bucket,
+            repayment_terms: RepaymentTerms,
+            call_put_terms: CallPutTerms,
+            conversion: Option<(ConversionTerms, Bucket)>,
+        ) -> Global<ZeroCouponBond> {
+            // bonds are bound to the issuer badge, the account comes from the badge and not from the caller
+            let issuer_badge = issuer_badge
+                .check_with_message(
+                    self.issuer_badge_resource_manager.address(),
+                    "only issuers approved by the DAO can create bonds, please present your issuer badge",
+                )
+                .as_non_fungible()
+                .non_fungible::<IssuerBadge>();
+
+            let issuer_badge_id = issuer_badge.local_id().clone();
+            let your_address = issuer_badge.data().account_address;
+
+            if let Some((_, conversion_tokens)) = &conversion {
+                assert!(
+                    conversion_tokens.resource_address() == self.dao_token_address,
+                    "convertible bonds can only convert into the DAO's governance token"
+                );
+            }
+
+            self.issue_zero_coupon_bond(
+                your_address,
+                issuer_badge_id,
+                contract_type,
+                contract_role,
+                contract_identifier,
+                nominal_interest_rate,
+                currency,
+                initial_exchange_date,
+                maturity_date,
+                notional_principal,
+                discount,
+                bond_position,
+                price,
+                number_of_bonds,
+                nft_as_collateral,
+                repayment_terms,
+                call_put_terms,
+                conversion,
+            )
+        }
+
+        // the DAO issues a convertible bond itself, conversion is backed by its unsold governance tokens
+        pub fn create_dao_convertible_bond(
+            &mut self,
+            contract_type: String,
+            contract_role: String,
+            contract_identifier: String,
+            nominal_interest_rate: Decimal,
+            currency: String,
+            initial_exchange_date: u64,
+            maturity_date: u64,
+            notional_principal: Decimal,
+            discount: u64,
+            bond_position: String,
+            price: u64,
+            number_of_bonds: Decimal,
+            nft_as_collateral: Bucket,
+            repayment_terms: RepaymentTerms,
+            call_put_terms: CallPutTerms,
+            conversion_terms: ConversionTerms,
+        ) -> Global<ZeroCouponBond> {
+            let tokens_required = conversion_terms.conversion_ratio * number_of_bonds;
+
+            assert!(
+                self.dao_token.amount() >= tokens_required,
+                "the DAO does not hold enough governance tokens to back the conversion"
+            );
+
+            let conversion_tokens = self.dao_token.take(tokens_required);
+
+            self.issue_zero_coupon_bond(
+                Runtime::global_address(),
+                NonFungibleLocalId::integer(DAO_ISSUER_BADGE_ID),
+                contract_type,
+                contract_role,
+                contract_identifier,
+                nominal_interest_rate,
+                currency,
+                initial_exchange_date,
+                maturity_date,
+                notional_principal,
+                discount,
+                bond_position,
+                price,
+                number_of_bonds,
+                nft_as_collateral,
+                repayment_terms,
+                call_put_terms,
+                Some((conversion_terms, conversion_tokens)),
+            )
+        }
+
+        fn issue_zero_coupon_bond(
+            &mut self,
+            your_address: ComponentAddress,
+            issuer_badge_id: NonFungibleLocalId,
+            contract_type: String,
+            contract_role: String,
+            contract_identifier: String,
+            nominal_interest_rate: Decimal,
+            currency: String,
+            initial_exchange_date: u64,
+            maturity_date: u64,
+            notional_principal: Decimal,
+            discount: u64,
+            bond_position: String,
+            price: u64,
+            number_of_bonds: Decimal,
+            nft_as_collateral: Bucket,
+            repayment_terms: RepaymentTerms,
+            call_put_terms: CallPutTerms,
+            conversion: Option<(ConversionTerms, Bucket)>,
+        ) -> Global<ZeroCouponBond> {
+            let collateral_resource_address = nft_as_collateral.resource_address();
+
+            // the bond takes the terms, the creation event gets a copy
+            let RepaymentTerms {
+                grace_period,
+                delinquency_period,
+                sinking_fund_schedule,
+            } = repayment_terms.clone();
+            let CallPutTerms { call_option, put_option } = call_put_terms.clone();
+            let conversion_terms = conversion.as_ref().map(|(terms, _)| terms.clone());
+
+            let bond_component = ZeroCouponBond::instantiate_zerocouponbond(
+                contract_type.clone(),
+                contract_role.clone(),
+                contract_identifier.clone(),
+                nominal_interest_rate,
+                currency.clone(),
+                initial_exchange_date,
+                maturity_date,
+                notional_principal,
+                discount,
+                bond_position.clone(),
+                price,
+                number_of_bonds,
+                nft_as_collateral,
+                repayment_terms,
+                issuer_badge_id.clone(),
+                call_put_terms,
+                conversion,
+                your_address,
+                NonFungibleGlobalId::new(
+                    self.issuer_badge_resource_manager.address(),
+                    issuer_badge_id.clone(),
+                ),
+                Runtime::global_address(),
             );
 
             self.zero_coupon_bond
                 .entry(your_address)
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(bond_component);
             // self.zero_coupon_bond = Some(bond_component);
 
-            // Emit the ZeroCouponBondCreation event
-            let event_metadata = ZeroCouponBondCreation {
-                component_address: bond_component.address(),
-                contract_type,
-                contract_role,
-                contract_identifier,
-                nominal_interest_rate,
-                currency,
-                initial_exchange_date,
-                maturity_date,
-                notional_principal,
-                discount,
-                bond_position,
-                price,
-                number_of_bonds,
-                creator_address: your_address,
-                collateral_resource_address,
+            self.issuer_registry
+                .entry(your_address)
+                .or_default()
+                .add_bond(IssuedBondRecord {
+                    bond_component_address: bond_component.address(),
+                    contract_identifier: contract_identifier.clone(),
+                    amount_issued: Decimal::from(price) * number_of_bonds,
+                    maturity_date,
+                    outcome: BondOutcome::Outstanding,
+                    amount_repaid: Decimal::zero(),
+                    collateral_liquidated: Decimal::zero(),
+                });
+
+            // Emit the ZeroCouponBondCreation event
+            let event_metadata = ZeroCouponBondCreation {
+                component_address: bond_component.address(),
+                contract_type,
+                contract_role,
+                contract_identifier,
+                nominal_interest_rate,
+                currency,
+                initial_exchange_date,
+                maturity_date,
+                notional_principal,
+                discount,
+                bond_position,
+                price,
+                number_of_bonds,
+                creator_address: your_address,
+                collateral_resource_address,
+                grace_period,
+                delinquency_period,
+                issuer_badge_id,
+                sinking_fund_schedule,
+                call_option,
+                put_option,
+                conversion_terms,
+            };
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::ZERO_COUPON_BOND_CREATION, // You can define a specific event type for bond creation if needed
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::ZeroCouponBondCreation(event_metadata),
+            });
+
+            bond_component
+        }
+
+        // New method to purchase a bond
+        // pub fn purchase_bond(&mut self, payment: Bucket) -> (Bucket, Bucket) {
+
+        //     assert!(self.zero_coupon_bond.is_some(), "ZeroCouponBond not initialized");
+
+        //     self.zero_coupon_bond.as_mut().unwrap().purchase_bond(payment)
+        // }
+
+        pub fn purchase_bond(
+            &mut self,
+            bond_creator_address: ComponentAddress,
+            // uid : Uid,
+            payment: Bucket,
+        ) -> Bucket {
+            assert!(
+                self.zero_coupon_bond.contains_key(&bond_creator_address),
+                "No bonds created by the specified address."
+            );
+
+            // Retrieve the most recent bond component created by the bond creator
+            let bond_components = self
+                .zero_coupon_bond
+                .get_mut(&bond_creator_address)
+                .unwrap();
+
+            //*we can restrict a creator in terms of bond creation
+            let latest_bond_component =
+                bond_components.last_mut().expect("No bond component found");
+
+            let amount_offered = payment.amount();
+
+            // Purchase bond from the latest bond component
+            let (purchased_bond, payment) = latest_bond_component.purchase_bond(payment);
+
+            let bond_resource_address = purchased_bond.resource_address();
+
+            *self
+                .bond_cost_basis
+                .entry(bond_resource_address)
+                .or_insert(Decimal::zero()) += amount_offered - payment.amount();
+
+            self.record_acquisition_time(bond_resource_address, purchased_bond.amount());
+
+            self.update_bond_vault_and_store(purchased_bond);
+            payment
+        }
+
+        // New method to sell a bond
+        pub fn sell_bond(
+            &mut self,
+            bond_creator_address: ComponentAddress,
+            // bond: Bucket,
+        ) {
+            assert!(
+                self.zero_coupon_bond.contains_key(&bond_creator_address),
+                "No bonds created by the specified address."
+            );
+
+            // Retrieve the most recent bond component created by the bond creator
+            let bond_components = self
+                .zero_coupon_bond
+                .get_mut(&bond_creator_address)
+                .unwrap();
+
+            let latest_bond_component =
+                bond_components.last_mut().expect("No bond component found");
+
+            // Sell bond from the latest bond component
+
+            //access the bond resouce address
+            let bond_resource_address = latest_bond_component.get_resource_address();
+
+            //access the bond
+            let vault = self.bonds.get_mut(&bond_resource_address).unwrap();
+
+            let held_before = vault.amount();
+            let purchased_bond = vault.take(1);
+
+            let principal_plus_interest = latest_bond_component.sell_the_bond(purchased_bond);
+
+            self.release_cost_basis(bond_resource_address, held_before, Decimal::one());
+
+            self.shares.put(principal_plus_interest);
+        }
+
+        // New method to check bond maturity
+        pub fn check_bond_maturity(&self, bond_creator_address: ComponentAddress) -> i64 {
+            assert!(
+                self.zero_coupon_bond.contains_key(&bond_creator_address),
+                "No bonds created by the specified address."
+            );
+
+            // Retrieve the most recent bond component created by the bond creator
+            let bond_components = self.zero_coupon_bond.get(&bond_creator_address).unwrap();
+            let latest_bond_component = bond_components.last().expect("No bond component found");
+
+            // Check bond maturity of the latest bond component
+            latest_bond_component.check_the_maturity_of_bonds()
+        }
+
+        // New method to get bond details
+        pub fn get_bond_details(&self, bond_creator_address: ComponentAddress) -> BondDetails {
+            assert!(
+                self.zero_coupon_bond.contains_key(&bond_creator_address),
+                "No bonds created by the specified address."
+            );
+
+            // Retrieve the most recent bond component created by the bond creator
+            let bond_components = self.zero_coupon_bond.get(&bond_creator_address).unwrap();
+            let latest_bond_component = bond_components.last().expect("No bond component found");
+
+            // Get bond details of the latest bond component
+            latest_bond_component.get_bond_details()
+        }
+
+        // Function to retrieve bond creators and their bond component addresses
+        pub fn get_bond_creators(&self) -> HashMap<ComponentAddress, Vec<Global<ZeroCouponBond>>> {
+            self.zero_coupon_bond.clone() // Return the HashMap of bond creators and their bonds
+        }
+
+        // New function to get all bond creator addresses
+        pub fn get_bond_creator_addresses(&self) -> Vec<ComponentAddress> {
+            self.zero_coupon_bond.keys().cloned().collect() // Return a list of bond creator addresses
+        }
+
+        // Function to get bond creator address and bond details
+        pub fn get_bond_creator_and_details(
+            &self,
+        ) -> Vec<(ComponentAddress, Vec<BondDetails>, Decimal)> {
+            let mut result = Vec::new();
+
+            // Iterate through each bond creator address and their bond components
+            for (creator_address, bonds) in &self.zero_coupon_bond {
+                let mut bond_details = Vec::new();
+                for bond in bonds {
+                    bond_details.push(bond.get_bond_details());
+                }
+                // Push the creator address, bond details and the creator's reputation to the result
+                result.push((
+                    *creator_address,
+                    bond_details,
+                    self.issuer_reputation_score(*creator_address),
+                ));
+            }
+
+            result
+        }
+
+        pub fn get_issuer_reputation(&self, bond_creator_address: ComponentAddress) -> IssuerReputation {
+            self.issuer_registry
+                .get(&bond_creator_address)
+                .cloned()
+                .unwrap_or_default()
+                .reputation(bond_creator_address)
+        }
+
+        pub fn get_issuer_record(
+            &self,
+            bond_creator_address: ComponentAddress,
+        ) -> Result<IssuerRecord, String> {
+            match self.issuer_registry.get(&bond_creator_address) {
+                Some(issuer_record) => Ok(issuer_record.clone()),
+                None => Err("this address has not issued any bonds".to_string()),
+            }
+        }
+
+        fn issuer_reputation_score(&self, bond_creator_address: ComponentAddress) -> Decimal {
+            self.get_issuer_reputation(bond_creator_address).score
+        }
+
+        fn record_bond_outcome(
+            &mut self,
+            bond_creator_address: ComponentAddress,
+            bond_component_address: ComponentAddress,
+            outcome: BondOutcome,
+            amount_repaid: Decimal,
+            collateral_liquidated: Decimal,
+        ) {
+            if let Some(issuer_record) = self.issuer_registry.get_mut(&bond_creator_address) {
+                if let Some(bond_record) = issuer_record.get_bond_mut(bond_component_address) {
+                    // a liquidation always wins, anything else only settles an outstanding bond
+                    if bond_record.outcome == BondOutcome::Outstanding
+                        || outcome == BondOutcome::CollateralLiquidated
+                    {
+                        bond_record.outcome = outcome;
+                    }
+                    bond_record.amount_repaid += amount_repaid;
+                    bond_record.collateral_liquidated += collateral_liquidated;
+                }
+            }
+        }
+
+        pub fn send_money_to_dao_treasury(
+            &mut self,
+            payment: Bucket,
+            account: Global<Account>,
+        ) -> Bucket {
+            // Ensure the payment is in XRD
+            assert_eq!(
+                payment.resource_address(),
+                XRD,
+                "Only XRD tokens are accepted for treasury contributions"
+            );
+
+            // Get the amount being sent
+            let amount = payment.amount();
+
+            // Get the sender's address
+            let sender_address = account.address();
+
+            // Store the payment in the dao_token vault
+            self.dao_token.put(payment);
+
+            // Update the contributor's record
+            self.update_contributor_record(sender_address, amount);
+
+            // Emit an event for the contribution
+            self.emit_contribution_event(sender_address, amount);
+
+            // Return an empty bucket
+            Bucket::new(XRD)
+        }
+
+        // Helper method to update the contributor's record
+        fn update_contributor_record(&mut self, address: ComponentAddress, amount: Decimal) {
+            *self.contributors.entry(address).or_insert(Decimal::zero()) += amount;
+        }
+
+        // Helper method to emit a contribution event
+        fn emit_contribution_event(&self, address: ComponentAddress, amount: Decimal) {
+            let event_metadata = TreasuryContribution {
+                contributor: address,
+                amount,
+                timestamp: Runtime::current_epoch().number(),
             };
 
             Runtime::emit_event(PandaoEvent {
-                event_type: EventType::ZERO_COUPON_BOND_CREATION, // You can define a specific event type for bond creation if needed
+                event_type: EventType::TREASURY_CONTRIBUTION,
                 dao_type: DaoType::Investment,
                 component_address: Runtime::global_address(),
-                meta_data: DaoEvent::ZeroCouponBondCreation(event_metadata),
+                meta_data: DaoEvent::TreasuryContribution(event_metadata),
             });
+        }
 
-            bond_component
+        // Method to get all contributors and their total contributions
+        pub fn get_all_contributors(&self) -> HashMap<ComponentAddress, Decimal> {
+            self.contributors.clone()
         }
 
-        // New method to purchase a bond
-        // pub fn purchase_bond(&mut self, payment: Bucket) -> (Bucket, Bucket) {
+        // averages the acquisition time of the bonds already held with the ones just bought
+        fn record_acquisition_time(&mut self, bond_resource_address: ResourceAddress, bought: Decimal) {
+            let current_time_seconds =
+                Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch as u64;
+
+            let held = self
+                .bonds
+                .get(&bond_resource_address)
+                .map(|vault| vault.amount())
+                .unwrap_or(Decimal::zero());
+
+            let acquired_at = match self.bond_acquired_at.get(&bond_resource_address) {
+                Some(acquired_at) if held > Decimal::zero() => {
+                    // the average moves towards now by the share of the position just bought
+                    let shift = Decimal::from(current_time_seconds.saturating_sub(*acquired_at)) * bought
+                        / (held + bought);
+
+                    let shift_seconds = shift
+                        .checked_floor()
+                        .and_then(|shift| u64::try_from(shift).ok())
+                        .unwrap_or(0);
+
+                    acquired_at + shift_seconds
+                }
+                _ => current_time_seconds,
+            };
+
+            self.bond_acquired_at.insert(bond_resource_address, acquired_at);
+        }
+
+        fn update_bond_vault_and_store(&mut self, desired_bond: Bucket) {
+            let desired_resource_address: ResourceAddress = desired_bond.resource_address();
+            self.bonds
+                .entry(desired_resource_address)
+                .or_insert_with(|| Vault::new(desired_resource_address))
+                .put(desired_bond);
+        }
+
+        // pub fn execute_proposal_for_pandao(&mut self){
+        //     match self.current_praposal{
+        //         Some(current_proposal) =>{
+        //             //earlier execute proposal was not taking any action but it was made to take action in financial dao case
+        //             println!("your proposal is executed successfully");
+        //             self.current_praposal = None;
+        //         },
+        //         // None => println!("there is not any proposal created")
+        //         None => assert!(false, "there is no any created proposal")
+        //     }
+        // }
+
+        // only reachable through an executed proposal
+        fn mint_more_tokens(&mut self, token_number_to_mint: usize) {
+            self.dao_token
+                .put(self.dao_token_resource_manager.mint(token_number_to_mint).into());
+        }
+
+        // only reachable through an executed proposal
+        fn set_price(&mut self, desired_token_price: Decimal, desired_buy_back_price: Decimal) {
+            self.token_price = desired_token_price;
+            self.buy_back_price = desired_buy_back_price;
+        }
+
+        pub fn get_back_the_collateral(&mut self, issuer_badge: Proof) -> Bucket {
+            let bond_creator_address = self.authenticate_issuer(issuer_badge);
+
+            assert!(
+                self.zero_coupon_bond.contains_key(&bond_creator_address),
+                "No bonds created by the specified address."
+            );
+
+            // Retrieve the most recent bond component created by the bond creator
+            let bond_components = self
+                .zero_coupon_bond
+                .get_mut(&bond_creator_address)
+                .unwrap();
+
+            let latest_bond_component =
+                bond_components.last_mut().expect("No bond component found");
+
+            let money_claim_status_by_community = latest_bond_component.get_money_claim_status();
+
+            let collateral_resource_address =
+                latest_bond_component.get_resource_address_of_collateral();
+
+            let bond_status = latest_bond_component.refresh_status();
+
+            if bond_status == BondStatus::Repaid {
+                let meta_data = GetBackTheCollateralEvent {
+                    bond_creator_address,
+                    is_given_money_claimed_by_community: money_claim_status_by_community,
+                    resource_address_of_collateral: collateral_resource_address,
+                    message: "collateral taken back successfully".to_string(),
+                };
+
+                Runtime::emit_event(PandaoEvent {
+                    event_type: EventType::COLLATERAL_GOT_BACK,
+                    dao_type: DaoType::Investment,
+                    meta_data: DaoEvent::GetBackTheCollateral(meta_data),
+                    component_address: Runtime::global_address(),
+                });
+
+                latest_bond_component.get_back_the_collateral()
+            } else {
+                let meta_data = GetBackTheCollateralEvent {
+                    bond_creator_address,
+                    is_given_money_claimed_by_community : money_claim_status_by_community,
+                    resource_address_of_collateral : collateral_resource_address,
+                    message : format!("you can not take your collateral back because the bond is {:?} and not Repaid", bond_status)
+                };
 
-        //     assert!(self.zero_coupon_bond.is_some(), "ZeroCouponBond not initialized");
+                Runtime::emit_event(PandaoEvent {
+                    event_type: EventType::FAILED_IN_GETTING_BACK_COLLATERAL,
+                    dao_type: DaoType::Investment,
+                    meta_data: DaoEvent::GetBackTheCollateral(meta_data),
+                    component_address: Runtime::global_address(),
+                });
 
-        //     self.zero_coupon_bond.as_mut().unwrap().purchase_bond(payment)
-        // }
+                Bucket::new(collateral_resource_address)
+            }
+        }
 
-        pub fn purchase_bond(
-            &mut self,
-            bond_creator_address: ComponentAddress,
-            // uid : Uid,
-            payment: Bucket,
-        ) -> Bucket {
+        pub fn liquidate_collateral(&mut self, bond_creator_address: ComponentAddress) {
             assert!(
                 self.zero_coupon_bond.contains_key(&bond_creator_address),
                 "No bonds created by the specified address."
@@ -1607,21 +4073,62 @@ mod radixdao {
                 .get_mut(&bond_creator_address)
                 .unwrap();
 
-            //*we can restrict a creator in terms of bond creation
             let latest_bond_component =
                 bond_components.last_mut().expect("No bond component found");
 
-            // Purchase bond from the latest bond component
-            let (purchased_bond, payment) = latest_bond_component.purchase_bond(payment);
-            self.update_bond_vault_and_store(purchased_bond);
-            payment
+            let bond_component_address = latest_bond_component.address();
+
+            //condition
+            let bond_status = latest_bond_component.refresh_status();
+
+            if bond_status == BondStatus::Default {
+                let redeemed_collateral = latest_bond_component.liquidate_collateral();
+
+                let collateral_resource_address =
+                    latest_bond_component.get_resource_address_of_collateral();
+
+                let liquidated_amount = redeemed_collateral.amount();
+
+                self.store_liquidated_collateral(redeemed_collateral);
+
+                self.record_bond_outcome(
+                    bond_creator_address,
+                    bond_component_address,
+                    BondOutcome::CollateralLiquidated,
+                    Decimal::zero(),
+                    liquidated_amount,
+                );
+
+                let event_metadata = LiquidatedCollateralEvent {
+                    bond_creator_address,
+                    liquidated_amount,
+                    collateral_resource_address,
+                };
+
+                Runtime::emit_event(PandaoEvent {
+                    event_type: EventType::LIQUIDATED_COLLATERAL,
+                    dao_type: DaoType::Investment,
+                    component_address: Runtime::global_address(),
+                    meta_data: DaoEvent::LiquidatedCollateral(event_metadata),
+                });
+            } else {
+                let event_metadata = CollateralLiquidationFailedEvent {
+                    bond_creator_address,
+                    bond_status,
+                };
+
+                Runtime::emit_event(PandaoEvent {
+                    event_type: EventType::COLLATERAL_LIQUIDATION_FAILED,
+                    dao_type: DaoType::Investment,
+                    component_address: Runtime::global_address(),
+                    meta_data: DaoEvent::CollateralLiquidationFailed(event_metadata),
+                });
+            }
         }
 
-        // New method to sell a bond
-        pub fn sell_bond(
+        pub fn claim_the_invested_XRDs_plus_interest(
             &mut self,
             bond_creator_address: ComponentAddress,
-            // bond: Bucket,
         ) {
             assert!(
                 self.zero_coupon_bond.contains_key(&bond_creator_address),
@@ -1637,405 +4144,324 @@ mod radixdao {
             let latest_bond_component =
                 bond_components.last_mut().expect("No bond component found");
 
-            // Sell bond from the latest bond component
+            // how much XRDs are required by the communty?
+            let balance_required_by_the_community =
+                latest_bond_component.balance_required_by_the_community();
 
-            //access the bond resouce address
-            let bond_resource_address = latest_bond_component.get_resource_address();
+            //balance in bond component
+            let balance_in_latest_bond_component =
+                latest_bond_component.check_the_balance_of_bond_issuer();
 
-            //access the bond
-            let vault = self.bonds.get_mut(&bond_resource_address).unwrap();
+            // the bond pays out only once the issuer has repaid it
+            if !latest_bond_component.is_redeemable() {
+                let collateral_resource_address =
+                    latest_bond_component.get_resource_address_of_collateral();
 
-            let purchased_bond = vault.take(1);
+                //perform liquidation, only once the bond is in default
+                let collateral_liquidated =
+                    latest_bond_component.refresh_status() == BondStatus::Default;
 
-            let principal_plus_interest = latest_bond_component.sell_the_bond(purchased_bond);
+                let liquidated_amount = if collateral_liquidated {
+                    let bond_component_address = latest_bond_component.address();
 
-            self.shares.put(principal_plus_interest);
-        }
+                    let redeemed_collateral = latest_bond_component.liquidate_collateral();
 
-        // New method to check bond maturity
-        pub fn check_bond_maturity(&self, bond_creator_address: ComponentAddress) -> i64 {
-            assert!(
-                self.zero_coupon_bond.contains_key(&bond_creator_address),
-                "No bonds created by the specified address."
-            );
+                    let liquidated_amount = redeemed_collateral.amount();
 
-            // Retrieve the most recent bond component created by the bond creator
-            let bond_components = self.zero_coupon_bond.get(&bond_creator_address).unwrap();
-            let latest_bond_component = bond_components.last().expect("No bond component found");
+                    self.store_liquidated_collateral(redeemed_collateral);
 
-            // Check bond maturity of the latest bond component
-            latest_bond_component.check_the_maturity_of_bonds()
-        }
+                    self.record_bond_outcome(
+                        bond_creator_address,
+                        bond_component_address,
+                        BondOutcome::CollateralLiquidated,
+                        Decimal::zero(),
+                        liquidated_amount,
+                    );
 
-        // New method to get bond details
-        pub fn get_bond_details(&self, bond_creator_address: ComponentAddress) -> BondDetails {
-            assert!(
-                self.zero_coupon_bond.contains_key(&bond_creator_address),
-                "No bonds created by the specified address."
-            );
+                    liquidated_amount
+                } else {
+                    Decimal::zero()
+                };
 
-            // Retrieve the most recent bond component created by the bond creator
-            let bond_components = self.zero_coupon_bond.get(&bond_creator_address).unwrap();
-            let latest_bond_component = bond_components.last().expect("No bond component found");
+                let event_metadata = ClaimInvestedXRDsPlusInterestErrorEvent {
+                    bond_creator_address,
+                    required_amount_by_the_community: balance_required_by_the_community,
+                    balance_of_bond_issuer: balance_in_latest_bond_component,
+                    collateral_liquidated,
+                    collateral_resource_address,
+                    liquidated_amount,
+                };
 
-            // Get bond details of the latest bond component
-            latest_bond_component.get_bond_details()
-        }
+                Runtime::emit_event(PandaoEvent {
+                    event_type: EventType::FAILED_CLAIM_INVESTED_XRDs_PLUS_INTEREST,
+                    dao_type: DaoType::Investment,
+                    component_address: Runtime::global_address(),
+                    meta_data: DaoEvent::ClaimInvestedXRDsPlusInterestError(event_metadata),
+                });
+            } else {
+                // every bond the DAO holds is handed back and burnt, so it can not be claimed twice
+                let bond_resource_address = latest_bond_component.get_resource_address();
 
-        // Function to retrieve bond creators and their bond component addresses
-        pub fn get_bond_creators(&self) -> HashMap<ComponentAddress, Vec<Global<ZeroCouponBond>>> {
-            self.zero_coupon_bond.clone() // Return the HashMap of bond creators and their bonds
-        }
+                let vault = self
+                    .bonds
+                    .get_mut(&bond_resource_address)
+                    .expect("the DAO holds no bonds of this issuer");
 
-        // New function to get all bond creator addresses
-        pub fn get_bond_creator_addresses(&self) -> Vec<ComponentAddress> {
-            self.zero_coupon_bond.keys().cloned().collect() // Return a list of bond creator addresses
-        }
+                let held_before = vault.amount();
+                let held_bonds = vault.take_all();
 
-        // Function to get bond creator address and bond details
-        pub fn get_bond_creator_and_details(&self) -> Vec<(ComponentAddress, Vec<BondDetails>)> {
-            let mut result = Vec::new();
+                assert!(!held_bonds.is_empty(), "the DAO holds no bonds of this issuer");
 
-            // Iterate through each bond creator address and their bond components
-            for (creator_address, bonds) in &self.zero_coupon_bond {
-                let mut bond_details = Vec::new();
-                for bond in bonds {
-                    bond_details.push(bond.get_bond_details());
-                }
-                // Push the creator address and corresponding bond details to the result
-                result.push((*creator_address, bond_details));
-            }
+                let claimed_invested_xrd_plus_interest =
+                    latest_bond_component.claim_the_invested_XRDs_plus_interest(held_bonds);
 
-            result
-        }
+                let claimed_amount = claimed_invested_xrd_plus_interest.amount();
 
-        pub fn send_money_to_dao_treasury(
-            &mut self,
-            payment: Bucket,
-            account: Global<Account>,
-        ) -> Bucket {
-            // Ensure the payment is in XRD
-            assert_eq!(
-                payment.resource_address(),
-                XRD,
-                "Only XRD tokens are accepted for treasury contributions"
-            );
+                self.shares.put(claimed_invested_xrd_plus_interest);
 
-            // Get the amount being sent
-            let amount = payment.amount();
+                //claimed successful
+                latest_bond_component.change_community_claim_status(true);
 
-            // Get the sender's address
-            let sender_address = account.address();
+                self.release_cost_basis(bond_resource_address, held_before, held_before);
 
-            // Store the payment in the dao_token vault
-            self.dao_token.put(payment);
+                let event_metadata = ClaimInvestedXRDsPlusInterestEvent {
+                    bond_creator_address,
+                    claimed_amount,
+                    amount_required_by_the_community: balance_required_by_the_community,
+                    collateral_liquidated: false,
+                };
 
-            // Update the contributor's record
-            self.update_contributor_record(sender_address, amount);
+                Runtime::emit_event(PandaoEvent {
+                    event_type: EventType::CLAIM_INVESTED_XRDs_PLUS_INTEREST,
+                    dao_type: DaoType::Investment,
+                    component_address: Runtime::global_address(),
+                    meta_data: DaoEvent::ClaimInvestedXRDsPlusInterest(event_metadata),
+                });
+            }
+        }
 
-            // Emit an event for the contribution
-            self.emit_contribution_event(sender_address, amount);
+        //FOR BOND ISSUER TO TAKE OUT COMMUNITY INVESTMENT
+        pub fn take_out_the_invested_XRDs_by_the_community(&mut self, issuer_badge: Proof) -> Bucket {
+            let bond_creator_address = self.authenticate_issuer(issuer_badge);
 
-            // Return an empty bucket
-            Bucket::new(XRD)
-        }
+            assert!(
+                self.zero_coupon_bond.contains_key(&bond_creator_address),
+                "No bonds created by the specified address."
+            );
 
-        // Helper method to update the contributor's record
-        fn update_contributor_record(&mut self, address: ComponentAddress, amount: Decimal) {
-            *self.contributors.entry(address).or_insert(Decimal::zero()) += amount;
-        }
+            // Retrieve the most recent bond component created by the bond creator
+            let bond_components = self
+                .zero_coupon_bond
+                .get_mut(&bond_creator_address)
+                .unwrap();
 
-        // Helper method to emit a contribution event
-        fn emit_contribution_event(&self, address: ComponentAddress, amount: Decimal) {
-            let event_metadata = TreasuryContribution {
-                contributor: address,
-                amount: amount,
-                timestamp: Runtime::current_epoch().number(),
+            let latest_bond_component =
+                bond_components.last_mut().expect("No bond component found");
+
+            // let bond_creator_money_taken_status = latest_bond_component.bond_creator_money_status();
+
+            let taken_out_invested_amount =
+                latest_bond_component.take_out_the_invested_XRDs_by_the_community();
+
+            let event_metadata = TakeOutInvestedXRDsEvent {
+                bond_creator_address,
+                taken_out_amount: taken_out_invested_amount.amount(),
             };
 
             Runtime::emit_event(PandaoEvent {
-                event_type: EventType::TREASURY_CONTRIBUTION,
+                event_type: EventType::TAKE_OUT_INVESTED_XRDs,
                 dao_type: DaoType::Investment,
                 component_address: Runtime::global_address(),
-                meta_data: DaoEvent::TreasuryContribution(event_metadata),
+                meta_data: DaoEvent::TakeOutInvestedXRDs(event_metadata),
             });
-        }
 
-        // Method to get all contributors and their total contributions
-        pub fn get_all_contributors(&self) -> HashMap<ComponentAddress, Decimal> {
-            self.contributors.clone()
+            taken_out_invested_amount
         }
 
-        fn update_bond_vault_and_store(&mut self, desired_bond: Bucket) {
-            let desired_resource_address: ResourceAddress = desired_bond.resource_address();
-            if !self.bonds.contains_key(&desired_resource_address) {
-                self.bonds.insert(
-                    desired_resource_address,
-                    Vault::new(desired_resource_address),
-                );
-            }
-            let vault = self.bonds.get_mut(&desired_resource_address).unwrap();
-            vault.put(desired_bond);
-        }
+        pub fn put_in_money_plus_interest_for_the_community_to_redeem(
+            &mut self,
+            issuer_badge: Proof,
+            borrowed_xrd_with_interest: Bucket,
+        ) -> (Bucket, Bucket) {
+            let bond_creator_address = self.authenticate_issuer(issuer_badge);
+
+            assert!(
+                self.zero_coupon_bond.contains_key(&bond_creator_address),
+                "No bonds created by the specified address."
+            );
+
+            // Retrieve the most recent bond component created by the bond creator
+            let bond_components = self
+                .zero_coupon_bond
+                .get_mut(&bond_creator_address)
+                .unwrap();
+
+            let latest_bond_component =
+                bond_components.last_mut().expect("No bond component found");
+
+            let amount_getting_deposited = borrowed_xrd_with_interest.amount();
+
+            // Get Required Amount
+            let required_amount = latest_bond_component.get_amount_owed_by_issuer();
+
+            let extra_money = latest_bond_component
+                .put_in_money_plus_interest_for_the_community_to_redeem(borrowed_xrd_with_interest);
 
-        // pub fn execute_proposal_for_pandao(&mut self){
-        //     match self.current_praposal{
-        //         Some(current_proposal) =>{
-        //             //earlier execute proposal was not taking any action but it was made to take action in financial dao case
-        //             println!("your proposal is executed successfully");
-        //             self.current_praposal = None;
-        //         },
-        //         // None => println!("there is not any proposal created")
-        //         None => assert!(false, "there is no any created proposal")
-        //     }
-        // }
+            // only the issuer's own deposits count, not what holders paid for their bonds
+            if latest_bond_component.get_recorded_status() == BondStatus::Repaid {
+                let bond_component_address = latest_bond_component.address();
 
-        pub fn create_proposal_to_mint_more_dao_tokens(
-            &mut self,
-            title: String,
-            description: String,
-            minimun_quorum: u8,
-            amount_of_tokens_should_be_minted: Option<usize>,
-            start_time: scrypto::time::UtcDateTime,
-            end_time: scrypto::time::UtcDateTime,
-            proposal_creator_address: Option<ComponentAddress>,
-            governance_token_or_owner_token_address: Bucket,
-            voting_type: VotingType,
-        ) -> (
-            Global<crate::proposal::pandao_praposal::TokenWeightProposal>,
-            String,
-            Bucket,
-        ) {
-            match self.proposal_creation_right {
-                ProposalCreationRight::EVERYONE => {
-                    assert_eq!(
-                        governance_token_or_owner_token_address.resource_address(),
-                        self.dao_token_address,
-                        "wrong voting token supplied! please make sure that you supply DAO Governance Token"
-                    );
+                let repaid_on_time = match latest_bond_component.get_bond_details().repaid_at {
+                    Some(repaid_at) => repaid_at <= latest_bond_component.get_maturity_data(),
+                    None => false,
+                };
 
-                    assert!(
-                        governance_token_or_owner_token_address.amount() >= Decimal::one(),
-                        "Proposal creator must have at least one governance token to create a proposal"
-                    );
+                let collateral_being_taken_back = latest_bond_component.get_back_the_collateral();
 
-                    //allow proposal creation
-                }
-                ProposalCreationRight::TOKEN_HOLDER_THRESHOLD(threshold) => {
-                    assert_eq!(
-                        governance_token_or_owner_token_address.resource_address(),
-                        self.dao_token_address,
-                        "wrong voting token supplied! please make sure that you supply DAO Governance Token"
-                    );
+                let outcome = if repaid_on_time {
+                    BondOutcome::RepaidOnTime
+                } else {
+                    BondOutcome::RepaidLate
+                };
 
-                    assert!(
-                        governance_token_or_owner_token_address.amount() >= threshold,
-                        "Proposal creator does not have enough tokens to meet the threshold"
-                    );
-                }
-                ProposalCreationRight::ADMIN => {
-                    assert_eq!(
-                        governance_token_or_owner_token_address.resource_address(),
-                        self.owner_token_addresss,
-                        "Only the admin can create a proposal and If you are an Admin please make sure you pass OWNER TOKEN ADDRESS"
-                    );
+                self.record_bond_outcome(
+                    bond_creator_address,
+                    bond_component_address,
+                    outcome,
+                    required_amount,
+                    Decimal::zero(),
+                );
 
-                    assert!(
-                        governance_token_or_owner_token_address.amount() >= Decimal::one(),
-                        "ADMIN must pass his/her OWNER TOKEN to create proposal"
-                    );
-                }
-            }
+                let extra_money_amount = extra_money.amount();
 
-            let address_issued_bonds_to_sell: Option<ComponentAddress> = None;
-            let target_xrd_amount: Option<Decimal> = None;
-            let desired_token_price: Option<Decimal> = None;
-            let desired_token_buy_back_price: Option<Decimal> = None;
-
-            let global_proposal_component: Global<TokenWeightProposal>;
-
-            match voting_type {
-                VotingType::ResourceHold => {
-                    (global_proposal_component, _) = TokenWeightProposal::new(
-                        title.clone(),
-                        description.clone(),
-                        minimun_quorum,
-                        start_time,
-                        end_time,
-                        self.owner_token_addresss.clone(),
-                        self.dao_token_address.clone(),
-                        address_issued_bonds_to_sell.clone(),
-                        target_xrd_amount.clone(),
-                        proposal_creator_address,
-                        amount_of_tokens_should_be_minted,
-                        VotingType::ResourceHold,
-                        desired_token_price,
-                        desired_token_buy_back_price,
-                    );
-                }
-                VotingType::Equality => {
-                    (global_proposal_component, _) = TokenWeightProposal::new(
-                        title.clone(),
-                        description.clone(),
-                        minimun_quorum,
-                        start_time,
-                        end_time,
-                        self.owner_token_addresss.clone(),
-                        self.dao_token_address.clone(),
-                        address_issued_bonds_to_sell.clone(),
-                        target_xrd_amount.clone(),
-                        proposal_creator_address,
-                        amount_of_tokens_should_be_minted,
-                        VotingType::Equality,
-                        desired_token_price,
-                        desired_token_buy_back_price,
-                    );
-                }
-            }
+                // let required_now = latest_bond_component.balance_required_by_the_community();
 
-            //start date
-            //end date
-            let start_time_ts: i64 = start_time.to_instant().seconds_since_unix_epoch;
-            let end_time_ts: i64 = end_time.to_instant().seconds_since_unix_epoch;
+                let event_metadata_if = PutInMoneyPlusInterestEvent {
+                    bond_creator_address,
+                    amount_getting_deposited,
+                    amount_required_by_the_community: required_amount,
+                    amount_taken_by_the_community: required_amount,
+                    extra_amount_given_back_to_the_sender: extra_money_amount,
+                    more_xrd_amount_required_by_the_community: Decimal::zero(),
+                    collateral_given_back: true,
+                };
 
-            let proposal_id: usize = Self::get_proposal_id()
-                .try_into()
-                .expect("couldn't get called successfully");
+                Runtime::emit_event(PandaoEvent {
+                    event_type: EventType::PUT_IN_MONEY_PLUS_INTEREST,
+                    dao_type: DaoType::Investment,
+                    component_address: Runtime::global_address(),
+                    meta_data: DaoEvent::PutInMoneyPlusInterest(event_metadata_if),
+                });
 
-            let inner_map = self
-                .current_praposals
-                .entry(proposal_creator_address.unwrap())
-                .or_insert_with(HashMap::new);
+                (extra_money, collateral_being_taken_back)
+            } else {
+                let extra_money_amount = extra_money.amount();
 
-            inner_map.insert(proposal_id, global_proposal_component);
+                //event emission
 
-            match voting_type {
-                VotingType::ResourceHold => {
-                    let praposal_metadata = PraposalMetadata {
-                        title,
-                        description,
-                        minimum_quorum: minimun_quorum.into(),
-                        end_time_ts,
-                        start_time_ts,
-                        owner_token_address: self.owner_token_addresss.clone(),
-                        component_address: global_proposal_component.address(),
-                        address_issued_bonds_to_sell,
-                        target_xrd_amount,
-                        proposal_creator_address,
-                        amount_of_tokens_should_be_minted,
-                        proposal_id,
-                        governance_token_or_owner_token_address:
-                            governance_token_or_owner_token_address.resource_address(),
-                        token_type: VotingType::ResourceHold,
-                        desired_token_price,
-                        desired_token_buy_back_price,
+                if amount_getting_deposited >= required_amount {
+                    let event_metadata_if = PutInMoneyPlusInterestEvent {
+                        bond_creator_address,
+                        amount_getting_deposited,
+                        amount_required_by_the_community: required_amount,
+                        amount_taken_by_the_community: required_amount,
+                        extra_amount_given_back_to_the_sender: extra_money_amount,
+                        more_xrd_amount_required_by_the_community: Decimal::zero(),
+                        collateral_given_back: false,
                     };
 
-                    let component_address = Runtime::global_address();
-
                     Runtime::emit_event(PandaoEvent {
-                        event_type: EventType::PROPOSAL_TO_MINT_MORE_TOKENS,
+                        event_type: EventType::PUT_IN_MONEY_PLUS_INTEREST,
                         dao_type: DaoType::Investment,
-                        meta_data: DaoEvent::PraposalDeployment(praposal_metadata),
-                        component_address,
+                        component_address: Runtime::global_address(),
+                        meta_data: DaoEvent::PutInMoneyPlusInterest(event_metadata_if),
                     });
-                }
-                VotingType::Equality => {
-                    let praposal_metadata = PraposalMetadata {
-                        title,
-                        description,
-                        minimum_quorum: minimun_quorum.into(),
-                        end_time_ts,
-                        start_time_ts,
-                        owner_token_address: self.owner_token_addresss.clone(),
-                        component_address: global_proposal_component.address(),
-                        address_issued_bonds_to_sell,
-                        target_xrd_amount,
-                        proposal_creator_address,
-                        amount_of_tokens_should_be_minted,
-                        proposal_id,
-                        governance_token_or_owner_token_address:
-                            governance_token_or_owner_token_address.resource_address(),
-                        token_type: VotingType::Equality,
-                        desired_token_price,
-                        desired_token_buy_back_price,
-                    };
+                } else {
+                    let more_xrd_amount_required_by_the_community =
+                        required_amount - amount_getting_deposited;
 
-                    let component_address = Runtime::global_address();
+                    let event_metadata_else = PutInMoneyPlusInterestEvent {
+                        bond_creator_address,
+                        amount_getting_deposited,
+                        amount_required_by_the_community: required_amount,
+                        amount_taken_by_the_community: amount_getting_deposited,
+                        extra_amount_given_back_to_the_sender: extra_money_amount,
+                        more_xrd_amount_required_by_the_community,
+                        collateral_given_back: false,
+                    };
 
                     Runtime::emit_event(PandaoEvent {
-                        event_type: EventType::PROPOSAL_TO_MINT_MORE_TOKENS,
+                        event_type: EventType::PUT_IN_LESS_MONEY_PLUS_INTEREST,
                         dao_type: DaoType::Investment,
-                        meta_data: DaoEvent::PraposalDeployment(praposal_metadata),
-                        component_address,
+                        component_address: Runtime::global_address(),
+                        meta_data: DaoEvent::PutInMoneyPlusInterest(event_metadata_else),
                     });
                 }
-            }
 
-            let mut message = String::new();
-            message = format!("Proposal created with id : {}", proposal_id);
+                let collateral_resource_address =
+                    latest_bond_component.get_resource_address_of_collateral();
 
-            (
-                global_proposal_component,
-                message,
-                governance_token_or_owner_token_address,
-            )
-        }
+                let empty_bucket = Bucket::new(collateral_resource_address);
 
-        pub fn mint_more_tokens(&mut self, token_number_to_mint: usize) {
-            self.dao_token
-                .put(self.dao_token_resource_manager.mint(token_number_to_mint));
+                (extra_money, empty_bucket)
+            }
         }
 
-        pub fn set_price(&mut self, desired_token_price: Decimal, desired_buy_back_price: Decimal) {
-            self.token_price = desired_token_price;
-            self.buy_back_price = desired_buy_back_price;
-        }
+        pub fn refresh_bond_status(&mut self, bond_creator_address: ComponentAddress) -> BondStatus {
+            assert!(
+                self.zero_coupon_bond.contains_key(&bond_creator_address),
+                "No bonds created by the specified address."
+            );
 
-        pub fn execute_proposal_to_mint_more_tokens(
-            &mut self,
-            proposal_id: usize,
-        ) -> Result<String, String> {
-            for (_, inner_map) in &self.current_praposals {
-                let proposal = inner_map.get(&proposal_id);
+            // Retrieve the most recent bond component created by the bond creator
+            let bond_components = self
+                .zero_coupon_bond
+                .get_mut(&bond_creator_address)
+                .unwrap();
 
-                let now: Instant = Clock::current_time_rounded_to_seconds();
-                let current_time_seconds: i64 = now.seconds_since_unix_epoch;
+            let latest_bond_component =
+                bond_components.last_mut().expect("No bond component found");
 
-                let last_time = proposal.unwrap().get_last_time();
-                let end_time_seconds = last_time.to_instant().seconds_since_unix_epoch;
+            let bond_component_address = latest_bond_component.address();
 
-                // Debug statements to verify the times
-                println!("Current time (epoch seconds): {}", current_time_seconds);
-                println!("Proposal end time (epoch seconds): {}", end_time_seconds);
+            let previous_status = latest_bond_component.get_recorded_status();
+            let current_status = latest_bond_component.refresh_status();
 
-                assert!(
-                    current_time_seconds > end_time_seconds,
-                    "Proposal can only be executed after the specified end time"
+            if current_status == BondStatus::Default {
+                self.record_bond_outcome(
+                    bond_creator_address,
+                    bond_component_address,
+                    BondOutcome::Defaulted,
+                    Decimal::zero(),
+                    Decimal::zero(),
                 );
+            }
 
-                match proposal {
-                    Some(proposal) => {
-                        if let Some(how_much_amount) = proposal.get_token_mint_amount() {
-                            self.mint_more_tokens(how_much_amount);
-
-                            let message = "proposal executed successfully".to_string();
+            if previous_status != current_status {
+                let event_metadata = BondStatusUpdated {
+                    bond_creator_address,
+                    bond_component_address,
+                    previous_status,
+                    current_status,
+                };
 
-                            return Ok(message);
-                        } else {
-                            return Err(format!("token_mint_amount is not present in a proposal with id : {proposal_id} and it seems not to be a proposal to mint more tokens"));
-                        }
-                    }
-                    None => return Err(format!("proposal with id : {proposal_id} not found")),
-                }
+                Runtime::emit_event(PandaoEvent {
+                    event_type: EventType::BOND_STATUS_UPDATED,
+                    dao_type: DaoType::Investment,
+                    component_address: Runtime::global_address(),
+                    meta_data: DaoEvent::BondStatusUpdated(event_metadata),
+                });
             }
-            Ok("execute fn called successfully".to_string())
+
+            current_status
         }
 
-        pub fn get_back_the_collateral(
+        // issuer pays part of the bond back ahead of maturity, following the sinking fund schedule
+        pub fn deposit_to_sinking_fund(
             &mut self,
             bond_creator_address: ComponentAddress,
-        ) -> Bucket {
+            deposit: Bucket,
+        ) -> SinkingFundStatus {
             assert!(
                 self.zero_coupon_bond.contains_key(&bond_creator_address),
                 "No bonds created by the specified address."
@@ -2050,47 +4476,177 @@ mod radixdao {
             let latest_bond_component =
                 bond_components.last_mut().expect("No bond component found");
 
-            let money_claim_status_by_community = latest_bond_component.get_money_claim_status();
+            let amount_deposited = deposit.amount();
 
-            let collateral_resource_address =
-                latest_bond_component.get_resource_address_of_collateral();
+            latest_bond_component.deposit_to_sinking_fund(deposit);
 
-            if money_claim_status_by_community == true {
-                let meta_data = GetBackTheCollateralEvent {
+            let sinking_fund_status = latest_bond_component.get_sinking_fund_status();
+
+            let event_metadata = SinkingFundDeposit {
+                bond_creator_address,
+                bond_component_address: latest_bond_component.address(),
+                amount_deposited,
+                deposited_by_issuer: sinking_fund_status.deposited_by_issuer,
+                required_to_date: sinking_fund_status.required_to_date,
+                coverage_ratio: sinking_fund_status.coverage_ratio,
+                bond_status: latest_bond_component.get_recorded_status(),
+            };
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::SINKING_FUND_DEPOSIT,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::SinkingFundDeposit(event_metadata),
+            });
+
+            sinking_fund_status
+        }
+
+        // anyone can call this to report instalments the issuer has missed since the last check
+        pub fn check_sinking_fund(&mut self, bond_creator_address: ComponentAddress) -> SinkingFundStatus {
+            assert!(
+                self.zero_coupon_bond.contains_key(&bond_creator_address),
+                "No bonds created by the specified address."
+            );
+
+            // Retrieve the most recent bond component created by the bond creator
+            let bond_components = self
+                .zero_coupon_bond
+                .get_mut(&bond_creator_address)
+                .unwrap();
+
+            let latest_bond_component =
+                bond_components.last_mut().expect("No bond component found");
+
+            let bond_component_address = latest_bond_component.address();
+
+            let newly_missed = latest_bond_component.report_missed_instalments();
+
+            let sinking_fund_status = latest_bond_component.get_sinking_fund_status();
+            let bond_status = latest_bond_component.get_status();
+
+            for instalment in newly_missed {
+                let event_metadata = SinkingFundInstalmentMissed {
                     bond_creator_address,
-                    is_given_money_claimed_by_community: money_claim_status_by_community,
-                    resource_address_of_collateral: collateral_resource_address,
-                    message: "collateral taken back successfully".to_string(),
+                    bond_component_address,
+                    instalment,
+                    deposited_by_issuer: sinking_fund_status.deposited_by_issuer,
+                    required_to_date: sinking_fund_status.required_to_date,
+                    bond_status,
                 };
 
                 Runtime::emit_event(PandaoEvent {
-                    event_type: EventType::COLLATERAL_GOT_BACK,
+                    event_type: EventType::SINKING_FUND_INSTALMENT_MISSED,
                     dao_type: DaoType::Investment,
-                    meta_data: DaoEvent::GetBackTheCollateral(meta_data),
                     component_address: Runtime::global_address(),
+                    meta_data: DaoEvent::SinkingFundInstalmentMissed(event_metadata),
                 });
+            }
+
+            // keep the issuer registry and status events in line with what the missed instalments did to the bond
+            self.refresh_bond_status(bond_creator_address);
+
+            sinking_fund_status
+        }
+
+        // the badge must be one the DAO minted and the one the issuer's latest bond is bound to
+        fn authenticate_issuer(&self, issuer_badge: Proof) -> ComponentAddress {
+            let issuer_badge = issuer_badge
+                .check_with_message(
+                    self.issuer_badge_resource_manager.address(),
+                    "only the issuer of the bond can do this, please present your issuer badge",
+                )
+                .as_non_fungible()
+                .non_fungible::<IssuerBadge>();
+
+            let bond_creator_address = issuer_badge.data().account_address;
+
+            let latest_bond_component = self
+                .zero_coupon_bond
+                .get(&bond_creator_address)
+                .and_then(|bond_components| bond_components.last())
+                .expect("No bonds created by the specified address.");
+
+            assert!(
+                latest_bond_component.get_bond_details().issuer_badge_id == *issuer_badge.local_id(),
+                "this issuer badge is not bound to the bond"
+            );
+
+            bond_creator_address
+        }
+
+        // issuer calls its latest bond early, funding every outstanding bond at the call price
+        pub fn exercise_bond_call(&mut self, issuer_badge: Proof, payment: Bucket) -> (Bucket, Bucket) {
+            let issuer_badge = issuer_badge
+                .check_with_message(
+                    self.issuer_badge_resource_manager.address(),
+                    "only the issuer of the bond can call it, please present your issuer badge",
+                )
+                .as_non_fungible()
+                .non_fungible::<IssuerBadge>();
+
+            let bond_creator_address = issuer_badge.data().account_address;
+
+            assert!(
+                self.zero_coupon_bond.contains_key(&bond_creator_address),
+                "No bonds created by the specified address."
+            );
+
+            // Retrieve the most recent bond component created by the bond creator
+            let bond_components = self
+                .zero_coupon_bond
+                .get_mut(&bond_creator_address)
+                .unwrap();
+
+            let latest_bond_component =
+                bond_components.last_mut().expect("No bond component found");
+
+            let bond_details = latest_bond_component.get_bond_details();
+
+            assert!(
+                bond_details.issuer_badge_id == *issuer_badge.local_id(),
+                "this issuer badge is not bound to the bond"
+            );
+
+            let call_option = bond_details.call_option.expect("this bond has no call option");
+
+            let bond_component_address = latest_bond_component.address();
+            let outstanding_bonds = latest_bond_component.get_outstanding_bonds();
+            let amount_paid = call_option.call_price * outstanding_bonds;
+
+            let (change, collateral) = latest_bond_component.exercise_call(payment);
+
+            let collateral_released = collateral.amount();
+
+            self.record_bond_outcome(
+                bond_creator_address,
+                bond_component_address,
+                BondOutcome::RepaidOnTime,
+                amount_paid,
+                Decimal::zero(),
+            );
 
-                latest_bond_component.get_back_the_collateral()
-            } else {
-                let meta_data = GetBackTheCollateralEvent {
-                    bond_creator_address,
-                    is_given_money_claimed_by_community : money_claim_status_by_community,
-                    resource_address_of_collateral : collateral_resource_address,
-                    message : "you can not take your collateral back because community has not claimed the amount".to_string()
-                };
+            let event_metadata = BondCalled {
+                bond_creator_address,
+                bond_component_address,
+                call_price: call_option.call_price,
+                outstanding_bonds,
+                amount_paid,
+                collateral_released,
+            };
 
-                Runtime::emit_event(PandaoEvent {
-                    event_type: EventType::FAILED_IN_GETTING_BACK_COLLATERAL,
-                    dao_type: DaoType::Investment,
-                    meta_data: DaoEvent::GetBackTheCollateral(meta_data),
-                    component_address: Runtime::global_address(),
-                });
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::BOND_CALLED,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::BondCalled(event_metadata),
+            });
 
-                latest_bond_component.get_back_the_collateral()
-            }
+            (change, collateral)
         }
 
-        pub fn liquidate_collateral(&mut self, bond_creator_address: ComponentAddress) {
+        // hands the DAO's called bonds back and moves the call price into the treasury
+        pub fn redeem_called_bonds(&mut self, bond_creator_address: ComponentAddress) -> Decimal {
             assert!(
                 self.zero_coupon_bond.contains_key(&bond_creator_address),
                 "No bonds created by the specified address."
@@ -2105,54 +4661,43 @@ mod radixdao {
             let latest_bond_component =
                 bond_components.last_mut().expect("No bond component found");
 
-            //condition
-            let now: Instant = Clock::current_time_rounded_to_seconds();
-            let current_time_seconds: u64 = now.seconds_since_unix_epoch as u64;
-
-            let maturity_date = latest_bond_component.get_maturity_data();
-
-            if maturity_date < current_time_seconds {
-                let redeemed_collateral = latest_bond_component.liquidate_collateral();
+            let bond_resource_address = latest_bond_component.get_resource_address();
 
-                let collateral_resource_address =
-                    latest_bond_component.get_resource_address_of_collateral();
+            let held_bonds = self
+                .bonds
+                .get_mut(&bond_resource_address)
+                .expect("the DAO does not hold any of these bonds")
+                .take_all();
 
-                let liquidated_amount = redeemed_collateral.amount();
+            let bonds_redeemed = held_bonds.amount();
 
-                self.liquidated_collateral = Vault::new(redeemed_collateral.resource_address());
+            let redemption = latest_bond_component.redeem_called_bond(held_bonds);
+            let amount_received = redemption.amount();
 
-                self.liquidated_collateral.put(redeemed_collateral);
+            let event_metadata = BondRedeemedEarly {
+                bond_creator_address,
+                bond_component_address: latest_bond_component.address(),
+                bonds_redeemed,
+                amount_received,
+                bond_status: latest_bond_component.get_recorded_status(),
+            };
 
-                let event_metadata = LiquidatedCollateralEvent {
-                    bond_creator_address,
-                    liquidated_amount,
-                    collateral_resource_address,
-                };
+            self.shares.put(redemption);
+            self.bond_cost_basis.remove(&bond_resource_address);
+            self.bond_acquired_at.remove(&bond_resource_address);
 
-                Runtime::emit_event(PandaoEvent {
-                    event_type: EventType::LIQUIDATED_COLLATERAL,
-                    dao_type: DaoType::Investment,
-                    component_address: Runtime::global_address(),
-                    meta_data: DaoEvent::LiquidatedCollateral(event_metadata),
-                });
-            } else {
-                let event_metadata = CollateralLiquidationFailedEvent {
-                    bond_creator_address,
-                };
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::CALLED_BONDS_REDEEMED,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::BondRedeemedEarly(event_metadata),
+            });
 
-                Runtime::emit_event(PandaoEvent {
-                    event_type: EventType::COLLATERAL_LIQUIDATION_FAILED,
-                    dao_type: DaoType::Investment,
-                    component_address: Runtime::global_address(),
-                    meta_data: DaoEvent::CollateralLiquidationFailed(event_metadata),
-                });
-            }
+            amount_received
         }
 
-        pub fn claim_the_invested_XRDs_plus_interest(
-            &mut self,
-            bond_creator_address: ComponentAddress,
-        ) {
+        // puts the DAO's bonds back to the issuer at the put price during an open put window
+        pub fn exercise_bond_put(&mut self, bond_creator_address: ComponentAddress) -> Decimal {
             assert!(
                 self.zero_coupon_bond.contains_key(&bond_creator_address),
                 "No bonds created by the specified address."
@@ -2167,74 +4712,65 @@ mod radixdao {
             let latest_bond_component =
                 bond_components.last_mut().expect("No bond component found");
 
-            // how much XRDs are required by the communty?
-            let balance_required_by_the_community =
-                latest_bond_component.balance_required_by_the_community();
-
-            //balance in bond component
-            let balance_in_latest_bond_component =
-                latest_bond_component.check_the_balance_of_bond_issuer();
+            let bond_component_address = latest_bond_component.address();
+            let bond_resource_address = latest_bond_component.get_resource_address();
 
-            if balance_in_latest_bond_component < balance_required_by_the_community {
-                //perform liquidation
-                let redeemed_collateral = latest_bond_component.liquidate_collateral();
+            let vault = self
+                .bonds
+                .get_mut(&bond_resource_address)
+                .expect("the DAO does not hold any of these bonds");
 
-                let collateral_resource_address =
-                    latest_bond_component.get_resource_address_of_collateral();
+            let held_bonds = vault.take_all();
+            let bonds_put = held_bonds.amount();
 
-                let liquidated_amount = redeemed_collateral.amount();
+            let (payout, returned_bonds) = latest_bond_component.exercise_put(held_bonds);
 
-                self.liquidated_collateral = Vault::new(redeemed_collateral.resource_address());
+            let amount_received = payout.amount();
+            let bonds_redeemed = bonds_put - returned_bonds.amount();
+            let bond_status = latest_bond_component.get_recorded_status();
 
-                self.liquidated_collateral.put(redeemed_collateral);
+            // unpaid bonds stay with the DAO
+            vault.put(returned_bonds);
+            self.shares.put(payout);
 
-                let event_metadata = ClaimInvestedXRDsPlusInterestErrorEvent {
-                    bond_creator_address,
-                    required_amount_by_the_community: balance_required_by_the_community,
-                    balance_of_bond_issuer: balance_in_latest_bond_component,
-                    collateral_liquidated: true,
-                    collateral_resource_address,
-                    liquidated_amount,
-                };
+            self.release_cost_basis(bond_resource_address, bonds_put, bonds_redeemed);
 
-                Runtime::emit_event(PandaoEvent {
-                    event_type: EventType::FAILED_CLAIM_INVESTED_XRDs_PLUS_INTEREST,
-                    dao_type: DaoType::Investment,
-                    component_address: Runtime::global_address(),
-                    meta_data: DaoEvent::ClaimInvestedXRDsPlusInterestError(event_metadata),
-                });
+            let event_type = if bonds_redeemed > Decimal::zero() {
+                EventType::BOND_PUT_EXERCISED
             } else {
-                let claimed_invested_xrd_plus_interest =
-                    latest_bond_component.claim_the_invested_XRDs_plus_interest();
-
-                let claimed_amount = claimed_invested_xrd_plus_interest.amount();
+                EventType::BOND_PUT_NOT_PAID
+            };
 
-                self.shares.put(claimed_invested_xrd_plus_interest);
+            if bond_status == BondStatus::Repaid {
+                self.record_bond_outcome(
+                    bond_creator_address,
+                    bond_component_address,
+                    BondOutcome::RepaidOnTime,
+                    amount_received,
+                    Decimal::zero(),
+                );
+            }
 
-                //claimed successful
-                latest_bond_component.change_community_claim_status(true);
+            let event_metadata = BondRedeemedEarly {
+                bond_creator_address,
+                bond_component_address,
+                bonds_redeemed,
+                amount_received,
+                bond_status,
+            };
 
-                let event_metadata = ClaimInvestedXRDsPlusInterestEvent {
-                    bond_creator_address,
-                    claimed_amount,
-                    amount_required_by_the_community: balance_required_by_the_community,
-                    collateral_liquidated: false,
-                };
+            Runtime::emit_event(PandaoEvent {
+                event_type,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::BondRedeemedEarly(event_metadata),
+            });
 
-                Runtime::emit_event(PandaoEvent {
-                    event_type: EventType::CLAIM_INVESTED_XRDs_PLUS_INTEREST,
-                    dao_type: DaoType::Investment,
-                    component_address: Runtime::global_address(),
-                    meta_data: DaoEvent::ClaimInvestedXRDsPlusInterest(event_metadata),
-                });
-            }
+            amount_received
         }
 
-        //FOR BOND ISSUER TO TAKE OUT COMMUNITY INVESTMENT
-        pub fn take_out_the_invested_XRDs_by_the_community(
-            &mut self,
-            bond_creator_address: ComponentAddress,
-        ) -> Bucket {
+        // converts the bonds the DAO holds into governance tokens, which go back into the DAO's token vault
+        pub fn convert_held_bonds(&mut self, bond_creator_address: ComponentAddress) -> Decimal {
             assert!(
                 self.zero_coupon_bond.contains_key(&bond_creator_address),
                 "No bonds created by the specified address."
@@ -2249,31 +4785,45 @@ mod radixdao {
             let latest_bond_component =
                 bond_components.last_mut().expect("No bond component found");
 
-            // let bond_creator_money_taken_status = latest_bond_component.bond_creator_money_status();
+            let bond_resource_address = latest_bond_component.get_resource_address();
 
-            let taken_out_invested_amount =
-                latest_bond_component.take_out_the_invested_XRDs_by_the_community();
+            let held_bonds = self
+                .bonds
+                .get_mut(&bond_resource_address)
+                .expect("the DAO does not hold any of these bonds")
+                .take_all();
 
-            let event_metadata = TakeOutInvestedXRDsEvent {
+            let bonds_converted = held_bonds.amount();
+
+            let tokens = latest_bond_component.convert(held_bonds);
+            let tokens_delivered = tokens.amount();
+
+            let event_metadata = BondsConverted {
                 bond_creator_address,
-                taken_out_amount: taken_out_invested_amount.amount(),
+                bond_component_address: latest_bond_component.address(),
+                bonds_converted,
+                tokens_delivered,
+                conversion_stats: latest_bond_component
+                    .get_conversion_stats()
+                    .expect("this bond is not convertible"),
             };
 
+            self.dao_token.put(tokens);
+            self.bond_cost_basis.remove(&bond_resource_address);
+            self.bond_acquired_at.remove(&bond_resource_address);
+
             Runtime::emit_event(PandaoEvent {
-                event_type: EventType::TAKE_OUT_INVESTED_XRDs,
+                event_type: EventType::BONDS_CONVERTED,
                 dao_type: DaoType::Investment,
                 component_address: Runtime::global_address(),
-                meta_data: DaoEvent::TakeOutInvestedXRDs(event_metadata),
+                meta_data: DaoEvent::BondsConverted(event_metadata),
             });
 
-            taken_out_invested_amount
+            tokens_delivered
         }
 
-        pub fn put_in_money_plus_interest_for_the_community_to_redeem(
-            &mut self,
-            bond_creator_address: ComponentAddress,
-            borrowed_xrd_with_interest: Bucket,
-        ) -> (Bucket, Bucket) {
+        // once conversion has ended the unused tokens go back to whoever funded them
+        pub fn reclaim_conversion_tokens(&mut self, bond_creator_address: ComponentAddress) -> Decimal {
             assert!(
                 self.zero_coupon_bond.contains_key(&bond_creator_address),
                 "No bonds created by the specified address."
@@ -2288,97 +4838,303 @@ mod radixdao {
             let latest_bond_component =
                 bond_components.last_mut().expect("No bond component found");
 
-            let amount_getting_deposited = borrowed_xrd_with_interest.amount();
+            let bond_component_address = latest_bond_component.address();
 
-            // Get Required Amount
-            let required_amount = latest_bond_component.balance_required_by_the_community();
+            let unused_tokens = latest_bond_component.take_back_conversion_tokens();
+            let amount_returned = unused_tokens.amount();
 
-            let extra_money = latest_bond_component
-                .put_in_money_plus_interest_for_the_community_to_redeem(borrowed_xrd_with_interest);
+            if bond_creator_address == Runtime::global_address() {
+                self.dao_token.put(unused_tokens);
+            } else {
+                let mut issuer_account: Global<Account> = bond_creator_address.into();
+                issuer_account.try_deposit_or_abort(unused_tokens, None);
+            }
 
-            let balance_of_bond_component =
-                latest_bond_component.check_the_balance_of_bond_issuer();
+            let event_metadata = ConversionTokensReclaimed {
+                bond_creator_address,
+                bond_component_address,
+                amount_returned,
+            };
 
-            //required amount?
-            let balance_required_by_community =
-                latest_bond_component.balance_required_by_the_community();
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::CONVERSION_TOKENS_RECLAIMED,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::ConversionTokensReclaimed(event_metadata),
+            });
 
-            if balance_of_bond_component >= balance_required_by_community {
-                let collateral_being_taken_back = latest_bond_component.get_back_the_collateral();
+            amount_returned
+        }
 
-                let extra_money_amount = extra_money.amount();
+        // conversion figures of every convertible bond, for treasury accounting
+        pub fn get_conversion_report(&self) -> Vec<(ComponentAddress, ComponentAddress, ConversionStats)> {
+            let mut report = Vec::new();
 
-                // let required_now = latest_bond_component.balance_required_by_the_community();
+            for (bond_creator_address, bond_components) in &self.zero_coupon_bond {
+                for bond_component in bond_components {
+                    if let Some(conversion_stats) = bond_component.get_conversion_stats() {
+                        report.push((*bond_creator_address, bond_component.address(), conversion_stats));
+                    }
+                }
+            }
 
-                let event_metadata_if = PutInMoneyPlusInterestEvent {
-                    bond_creator_address,
-                    amount_getting_deposited,
-                    amount_required_by_the_community: required_amount,
-                    amount_taken_by_the_community: required_amount,
-                    extra_amount_given_back_to_the_sender: extra_money_amount,
-                    more_xrd_amount_required_by_the_community: Decimal::zero(),
-                    collateral_given_back: true,
-                };
+            report
+        }
 
-                Runtime::emit_event(PandaoEvent {
-                    event_type: EventType::PUT_IN_MONEY_PLUS_INTEREST,
-                    dao_type: DaoType::Investment,
-                    component_address: Runtime::global_address(),
-                    meta_data: DaoEvent::PutInMoneyPlusInterest(event_metadata_if),
-                });
+        // drops the share of the cost basis that belonged to bonds which left the DAO's vault
+        fn release_cost_basis(
+            &mut self,
+            bond_resource_address: ResourceAddress,
+            held_before: Decimal,
+            released: Decimal,
+        ) {
+            if held_before == Decimal::zero() || released == Decimal::zero() {
+                return;
+            }
 
-                (extra_money, collateral_being_taken_back)
-            } else {
-                let extra_money_amount = extra_money.amount();
+            if let Some(cost_basis) = self.bond_cost_basis.get_mut(&bond_resource_address) {
+                *cost_basis -= *cost_basis * released / held_before;
+            }
+        }
 
-                //event emission
+        // every bond the DAO holds with its cost, accrued interest and value, plus portfolio totals
+        pub fn get_bond_portfolio(&self) -> PortfolioReport {
+            let now: Instant = Clock::current_time_rounded_to_seconds();
+            let current_time_seconds: u64 = now.seconds_since_unix_epoch as u64;
 
-                if amount_getting_deposited >= required_amount {
-                    let event_metadata_if = PutInMoneyPlusInterestEvent {
-                        bond_creator_address,
-                        amount_getting_deposited,
-                        amount_required_by_the_community: required_amount,
-                        amount_taken_by_the_community: required_amount,
-                        extra_amount_given_back_to_the_sender: extra_money_amount,
-                        more_xrd_amount_required_by_the_community: Decimal::zero(),
-                        collateral_given_back: false,
+            let mut holdings = Vec::new();
+
+            for (bond_creator_address, bond_components) in &self.zero_coupon_bond {
+                for bond_component in bond_components {
+                    let bond_resource_address = bond_component.get_resource_address();
+
+                    let quantity = match self.bonds.get(&bond_resource_address) {
+                        Some(vault) if !vault.is_empty() => vault.amount(),
+                        _ => continue,
+                    };
+
+                    let market_price = self
+                        .price_oracle
+                        .as_ref()
+                        .and_then(|oracle| oracle.get_price(bond_resource_address));
+
+                    holdings.push(BondHolding::new(
+                        *bond_creator_address,
+                        bond_component.address(),
+                        bond_resource_address,
+                        &bond_component.get_bond_details(),
+                        quantity,
+                        self.bond_cost_basis
+                            .get(&bond_resource_address)
+                            .cloned()
+                            .unwrap_or(Decimal::zero()),
+                        self.bond_acquired_at
+                            .get(&bond_resource_address)
+                            .cloned()
+                            .unwrap_or(current_time_seconds),
+                        market_price,
+                        current_time_seconds,
+                    ));
+                }
+            }
+
+            PortfolioReport::from_holdings(holdings)
+        }
+
+        // known future treasury flows: bonds the DAO holds pay in, withdrawals and payment streams pay out
+        fn projected_cash_flows(&self, current_time_seconds: u64, until: u64) -> Vec<CashFlow> {
+            let mut flows = Vec::new();
+
+            for bond_components in self.zero_coupon_bond.values() {
+                for bond_component in bond_components {
+                    let quantity = match self.bonds.get(&bond_component.get_resource_address()) {
+                        Some(vault) if !vault.is_empty() => vault.amount(),
+                        _ => continue,
                     };
 
-                    Runtime::emit_event(PandaoEvent {
-                        event_type: EventType::PUT_IN_MONEY_PLUS_INTEREST,
-                        dao_type: DaoType::Investment,
-                        component_address: Runtime::global_address(),
-                        meta_data: DaoEvent::PutInMoneyPlusInterest(event_metadata_if),
-                    });
-                } else {
-                    let more_xrd_amount_required_by_the_community =
-                        required_amount - amount_getting_deposited;
+                    let bond_details = bond_component.get_bond_details();
+
+                    if bond_details.status == BondStatus::Default
+                        || bond_details.status == BondStatus::Closed
+                    {
+                        continue;
+                    }
+
+                    // sinking fund deposits pay down the bond before maturity, in proportion to the bonds held
+                    let share = quantity / bond_details.number_of_bonds;
+                    let mut paid_before_maturity = Decimal::zero();
+
+                    for instalment in &bond_details.sinking_fund_schedule {
+                        let amount = instalment.amount * share;
+                        paid_before_maturity += amount;
+
+                        if instalment.due_date >= current_time_seconds {
+                            flows.push(CashFlow {
+                                date: instalment.due_date,
+                                amount,
+                                kind: CashFlowKind::SinkingFundDeposit,
+                            });
+                        }
+                    }
+
+                    let redemption_value = schedule::redemption_value(
+                        Decimal::from(bond_details.price),
+                        bond_details.nominal_interest_rate,
+                    ) * quantity;
+
+                    flows.push(CashFlow {
+                        date: bond_details.maturity_date,
+                        amount: (redemption_value - paid_before_maturity).max(Decimal::zero()),
+                        kind: CashFlowKind::BondMaturity,
+                    });
+                }
+            }
+
+            // pending withdrawal requests may be paid out at any time
+            for withdrawal_request in self.withdraw_requests.values() {
+                flows.push(CashFlow {
+                    date: current_time_seconds,
+                    amount: -withdrawal_request.requested_amount,
+                    kind: CashFlowKind::WithdrawalRequest,
+                });
+            }
+
+            for payment_stream in self.payment_streams.values() {
+                for date in payment_stream.payment_dates(current_time_seconds, until) {
+                    flows.push(CashFlow {
+                        date,
+                        amount: -payment_stream.amount_per_period,
+                        kind: CashFlowKind::PaymentStream,
+                    });
+                }
+            }
+
+            flows
+        }
+
+        fn project_liquidity_ladder(
+            &self,
+            opening_balance: Decimal,
+            interval: LadderInterval,
+            number_of_rungs: u32,
+        ) -> LiquidityLadder {
+            let now: Instant = Clock::current_time_rounded_to_seconds();
+            let current_time_seconds: u64 = now.seconds_since_unix_epoch as u64;
+
+            let until = current_time_seconds + interval.seconds() * number_of_rungs as u64;
+
+            LiquidityLadder::build(
+                opening_balance,
+                &self.projected_cash_flows(current_time_seconds, until),
+                interval,
+                number_of_rungs,
+                current_time_seconds,
+            )
+        }
+
+        // treasury balance projected forward in weekly or monthly rungs
+        pub fn get_liquidity_ladder(
+            &self,
+            interval: LadderInterval,
+            number_of_rungs: u32,
+        ) -> LiquidityLadder {
+            assert!(number_of_rungs > 0, "the ladder needs at least one rung");
+
+            self.project_liquidity_ladder(self.shares.amount(), interval, number_of_rungs)
+        }
+
+        pub fn set_liquidity_policy(
+            &mut self,
+            minimum_liquidity: Decimal,
+            interval: LadderInterval,
+            number_of_rungs: u32,
+        ) {
+            assert!(
+                minimum_liquidity >= Decimal::zero(),
+                "minimum liquidity can not be negative"
+            );
+            assert!(number_of_rungs > 0, "the ladder needs at least one rung");
+
+            self.minimum_liquidity = minimum_liquidity;
+            self.liquidity_ladder_interval = interval;
+            self.liquidity_ladder_rungs = number_of_rungs;
+
+            let event_metadata = LiquidityPolicySet {
+                minimum_liquidity,
+                interval,
+                number_of_rungs,
+            };
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::LIQUIDITY_POLICY_SET,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::LiquidityPolicySet(event_metadata),
+            });
+        }
+
+        pub fn add_payment_stream(
+            &mut self,
+            recipient: ComponentAddress,
+            amount_per_period: Decimal,
+            period: u64,
+            start_time: u64,
+            end_time: u64,
+            description: String,
+        ) -> u64 {
+            assert!(
+                amount_per_period > Decimal::zero(),
+                "stream amount must be greater than zero"
+            );
+            assert!(period > 0, "stream period must be greater than zero");
+            assert!(end_time >= start_time, "stream can not end before it starts");
+
+            self.payment_stream_counter += 1;
+            let stream_id = self.payment_stream_counter;
+
+            let payment_stream = PaymentStream {
+                recipient,
+                amount_per_period,
+                period,
+                start_time,
+                end_time,
+                description,
+            };
 
-                    let event_metadata_else = PutInMoneyPlusInterestEvent {
-                        bond_creator_address,
-                        amount_getting_deposited,
-                        amount_required_by_the_community: required_amount,
-                        amount_taken_by_the_community: amount_getting_deposited,
-                        extra_amount_given_back_to_the_sender: extra_money_amount,
-                        more_xrd_amount_required_by_the_community,
-                        collateral_given_back: false,
-                    };
+            self.payment_streams.insert(stream_id, payment_stream.clone());
 
-                    Runtime::emit_event(PandaoEvent {
-                        event_type: EventType::PUT_IN_LESS_MONEY_PLUS_INTEREST,
-                        dao_type: DaoType::Investment,
-                        component_address: Runtime::global_address(),
-                        meta_data: DaoEvent::PutInMoneyPlusInterest(event_metadata_else),
-                    });
-                }
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::PAYMENT_STREAM_ADDED,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::PaymentStreamUpdated(PaymentStreamUpdated {
+                    stream_id,
+                    payment_stream,
+                }),
+            });
 
-                let collateral_resource_address =
-                    latest_bond_component.get_resource_address_of_collateral();
+            stream_id
+        }
 
-                let empty_bucket = Bucket::new(collateral_resource_address);
+        pub fn remove_payment_stream(&mut self, stream_id: u64) {
+            let payment_stream = self
+                .payment_streams
+                .remove(&stream_id)
+                .expect("no payment stream found with this id");
 
-                (extra_money, empty_bucket)
-            }
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::PAYMENT_STREAM_REMOVED,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::PaymentStreamUpdated(PaymentStreamUpdated {
+                    stream_id,
+                    payment_stream,
+                }),
+            });
+        }
+
+        pub fn get_payment_streams(&self) -> HashMap<u64, PaymentStream> {
+            self.payment_streams.clone()
         }
 
         pub fn check_the_balance_of_bond_issuer(
@@ -2433,6 +5189,11 @@ mod radixdao {
             let latest_bond_component =
                 bond_components.last_mut().expect("No bond component found");
 
+            assert!(
+                latest_bond_component.refresh_status() == BondStatus::Default,
+                "XRDs can only be transferred to the community vault once the bond is in default"
+            );
+
             //required xrds
             let required_xrds = latest_bond_component.balance_required_by_the_community();
             let bond_component_balance = latest_bond_component.check_the_balance_of_bond_issuer();
@@ -2456,287 +5217,389 @@ mod radixdao {
             self.shares.put(creator_xrds);
         }
 
-        pub fn create_proposal_to_change_token_price(
+        pub fn set_price_oracle(
             &mut self,
-            title: String,
-            description: String,
-            minimun_quorum: u8,
-            start_time: scrypto::time::UtcDateTime,
-            end_time: scrypto::time::UtcDateTime,
-            proposal_creator_address: Option<ComponentAddress>,
-            governance_token_or_owner_token_address: Bucket,
-            voting_type: VotingType,
-            desired_token_price: Option<Decimal>,
-            desired_token_buy_back_price: Option<Decimal>,
-        ) -> (
-            Global<crate::proposal::pandao_praposal::TokenWeightProposal>,
-            String,
-            Bucket,
+            oracle: Global<PriceOracle>,
+            maintenance_ratio: Decimal,
+            margin_call_window: u64,
         ) {
-            match self.proposal_creation_right {
-                ProposalCreationRight::EVERYONE => {
-                    assert_eq!(
-                        governance_token_or_owner_token_address.resource_address(),
-                        self.dao_token_address,
-                        "wrong voting token supplied! please make sure that you supply DAO Governance Token"
-                    );
+            assert!(
+                maintenance_ratio >= Decimal::one(),
+                "maintenance ratio must be at least 1"
+            );
 
-                    assert!(
-                        governance_token_or_owner_token_address.amount() >= Decimal::one(),
-                        "Proposal creator must have at least one governance token to create a proposal"
-                    );
+            let oracle_address = oracle.address();
 
-                    //allow proposal creation
-                }
-                ProposalCreationRight::TOKEN_HOLDER_THRESHOLD(threshold) => {
-                    assert_eq!(
-                        governance_token_or_owner_token_address.resource_address(),
-                        self.dao_token_address,
-                        "wrong voting token supplied! please make sure that you supply DAO Governance Token"
-                    );
+            self.price_oracle = Some(oracle);
+            self.maintenance_ratio = maintenance_ratio;
+            self.margin_call_window = margin_call_window;
 
-                    assert!(
-                        governance_token_or_owner_token_address.amount() >= threshold,
-                        "Proposal creator does not have enough tokens to meet the threshold"
-                    );
+            let event_metadata = PriceOracleSet {
+                oracle_address,
+                maintenance_ratio,
+                margin_call_window,
+            };
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::PRICE_ORACLE_SET,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::PriceOracleSet(event_metadata),
+            });
+        }
+
+        pub fn get_margin_call_start(&self, bond_creator_address: ComponentAddress) -> Option<u64> {
+            assert!(
+                self.zero_coupon_bond.contains_key(&bond_creator_address),
+                "No bonds created by the specified address."
+            );
+
+            let bond_components = self.zero_coupon_bond.get(&bond_creator_address).unwrap();
+            let latest_bond_component = bond_components.last().expect("No bond component found");
+
+            self.margin_calls.get(&latest_bond_component.address()).cloned()
+        }
+
+        //anyone can call this to check the collateral of every bond of an issuer against the oracle price
+        //1. collateral value below maintenance ratio -> margin call window opens
+        //2. value restored within the window -> margin call is cured
+        //3. window passed without a cure -> collateral is liquidated before maturity
+        pub fn monitor_margin(&mut self, bond_creator_address: ComponentAddress) {
+            assert!(
+                self.zero_coupon_bond.contains_key(&bond_creator_address),
+                "No bonds created by the specified address."
+            );
+
+            assert!(
+                self.price_oracle.is_some(),
+                "price oracle is not set for this DAO"
+            );
+
+            let bond_components = self
+                .zero_coupon_bond
+                .get(&bond_creator_address)
+                .unwrap()
+                .clone();
+
+            for bond_component in bond_components {
+                let status = bond_component.refresh_status();
+
+                // repaid, closed and defaulted bonds have nothing left to secure
+                if status == BondStatus::Repaid
+                    || status == BondStatus::Closed
+                    || status == BondStatus::Default
+                {
+                    continue;
                 }
-                ProposalCreationRight::ADMIN => {
-                    assert_eq!(
-                        governance_token_or_owner_token_address.resource_address(),
-                        self.owner_token_addresss,
-                        "Only the admin can create a proposal and If you are an Admin please make sure you pass OWNER TOKEN ADDRESS"
-                    );
 
-                    assert!(
-                        governance_token_or_owner_token_address.amount() >= Decimal::one(),
-                        "ADMIN must pass his/her OWNER TOKEN to create proposal"
-                    );
+                if bond_component.get_collateral_amount() == Decimal::zero() {
+                    continue;
                 }
+
+                self.monitor_bond_margin(bond_creator_address, bond_component);
             }
+        }
 
-            let address_issued_bonds_to_sell: Option<ComponentAddress> = None;
-            let target_xrd_amount: Option<Decimal> = None;
-            let amount_of_tokens_should_be_minted: Option<usize> = None;
-
-            let global_proposal_component: Global<TokenWeightProposal>;
-
-            match voting_type {
-                VotingType::ResourceHold => {
-                    (global_proposal_component, _) = TokenWeightProposal::new(
-                        title.clone(),
-                        description.clone(),
-                        minimun_quorum,
-                        start_time,
-                        end_time,
-                        self.owner_token_addresss.clone(),
-                        self.dao_token_address.clone(),
-                        address_issued_bonds_to_sell.clone(),
-                        target_xrd_amount.clone(),
-                        proposal_creator_address,
-                        amount_of_tokens_should_be_minted,
-                        VotingType::ResourceHold,
-                        desired_token_price,
-                        desired_token_buy_back_price,
-                    );
+        fn monitor_bond_margin(
+            &mut self,
+            bond_creator_address: ComponentAddress,
+            bond_component: Global<ZeroCouponBond>,
+        ) {
+            let collateral_price_oracle = self
+                .price_oracle
+                .as_ref()
+                .expect("price oracle is not set for this DAO");
+
+            let bond_component_address = bond_component.address();
+
+            let collateral_resource_address =
+                bond_component.get_resource_address_of_collateral();
+
+            let collateral_amount = bond_component.get_collateral_amount();
+
+            let collateral_price = collateral_price_oracle
+                .get_price(collateral_resource_address)
+                .expect("oracle has no fresh price for the collateral resource");
+
+            let collateral_value = collateral_amount * collateral_price;
+
+            // principal plus interest on every outstanding bond, less what the issuer already deposited
+            let outstanding_amount = bond_component.get_amount_owed_by_issuer();
+
+            let required_collateral_value = outstanding_amount * self.maintenance_ratio;
+
+            let now: Instant = Clock::current_time_rounded_to_seconds();
+            let current_time_seconds: u64 = now.seconds_since_unix_epoch as u64;
+
+            let margin_call_started_at = self.margin_calls.get(&bond_component_address).cloned();
+
+            let event_type;
+            let mut liquidated_collateral: Option<Bucket> = None;
+
+            if collateral_value >= required_collateral_value {
+                if margin_call_started_at.is_some() {
+                    self.margin_calls.remove(&bond_component_address);
+                    event_type = EventType::MARGIN_CALL_CURED;
+                } else {
+                    event_type = EventType::MARGIN_CHECK_PASSED;
                 }
-                VotingType::Equality => {
-                    (global_proposal_component, _) = TokenWeightProposal::new(
-                        title.clone(),
-                        description.clone(),
-                        minimun_quorum,
-                        start_time,
-                        end_time,
-                        self.owner_token_addresss.clone(),
-                        self.dao_token_address.clone(),
-                        address_issued_bonds_to_sell.clone(),
-                        target_xrd_amount.clone(),
-                        proposal_creator_address,
-                        amount_of_tokens_should_be_minted,
-                        VotingType::Equality,
-                        desired_token_price,
-                        desired_token_buy_back_price,
-                    );
+            } else {
+                match margin_call_started_at {
+                    None => {
+                        self.margin_calls
+                            .insert(bond_component_address, current_time_seconds);
+                        event_type = EventType::MARGIN_CALL_ISSUED;
+                    }
+                    Some(started_at) => {
+                        if current_time_seconds > started_at.saturating_add(self.margin_call_window) {
+                            liquidated_collateral =
+                                Some(bond_component.liquidate_collateral_on_margin_call());
+                            self.margin_calls.remove(&bond_component_address);
+                            event_type = EventType::MARGIN_CALL_LIQUIDATION;
+                        } else {
+                            event_type = EventType::MARGIN_CALL_STILL_OPEN;
+                        }
+                    }
                 }
             }
 
-            //start date
-            //end date
-            let start_time_ts: i64 = start_time.to_instant().seconds_since_unix_epoch;
-            let end_time_ts: i64 = end_time.to_instant().seconds_since_unix_epoch;
+            let margin_call_started_at = match event_type {
+                EventType::MARGIN_CALL_ISSUED => Some(current_time_seconds),
+                EventType::MARGIN_CHECK_PASSED => None,
+                _ => margin_call_started_at,
+            };
 
-            let proposal_id: usize = Self::get_proposal_id()
-                .try_into()
-                .expect("couldn't get called successfully");
+            let event_metadata = MarginCallEvent {
+                bond_creator_address,
+                bond_component_address,
+                collateral_resource_address,
+                collateral_amount,
+                collateral_price,
+                collateral_value,
+                outstanding_amount,
+                required_collateral_value,
+                maintenance_ratio: self.maintenance_ratio,
+                margin_call_started_at,
+                margin_call_deadline: margin_call_started_at
+                    .map(|started_at| started_at.saturating_add(self.margin_call_window)),
+            };
 
-            let inner_map = self
-                .current_praposals
-                .entry(proposal_creator_address.unwrap())
-                .or_insert_with(HashMap::new);
+            Runtime::emit_event(PandaoEvent {
+                event_type,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::MarginCall(event_metadata),
+            });
 
-            inner_map.insert(proposal_id, global_proposal_component);
+            if let Some(redeemed_collateral) = liquidated_collateral {
+                self.store_liquidated_collateral(redeemed_collateral);
 
-            match voting_type {
-                VotingType::ResourceHold => {
-                    let praposal_metadata = PraposalMetadata {
-                        title,
-                        description,
-                        minimum_quorum: minimun_quorum.into(),
-                        end_time_ts,
-                        start_time_ts,
-                        owner_token_address: self.owner_token_addresss.clone(),
-                        component_address: global_proposal_component.address(),
-                        address_issued_bonds_to_sell,
-                        target_xrd_amount,
-                        proposal_creator_address,
-                        amount_of_tokens_should_be_minted,
-                        proposal_id,
-                        governance_token_or_owner_token_address:
-                            governance_token_or_owner_token_address.resource_address(),
-                        token_type: VotingType::ResourceHold,
-                        desired_token_price,
-                        desired_token_buy_back_price,
-                    };
+                self.record_bond_outcome(
+                    bond_creator_address,
+                    bond_component_address,
+                    BondOutcome::CollateralLiquidated,
+                    Decimal::zero(),
+                    collateral_amount,
+                );
+            }
+        }
 
-                    let component_address = Runtime::global_address();
+        fn store_liquidated_collateral(&mut self, seized_collateral: Bucket) {
+            let collateral_resource_address: ResourceAddress = seized_collateral.resource_address();
+            self.liquidated_collateral
+                .entry(collateral_resource_address)
+                .or_insert_with(|| Vault::new(collateral_resource_address))
+                .put(seized_collateral);
+        }
 
-                    Runtime::emit_event(PandaoEvent {
-                        event_type: EventType::PROPOSAL_TO_CHANGE_TOKEN_PRICE,
-                        dao_type: DaoType::Investment,
-                        meta_data: DaoEvent::PraposalDeployment(praposal_metadata),
-                        component_address,
-                    });
-                }
-                VotingType::Equality => {
-                    let praposal_metadata = PraposalMetadata {
-                        title,
-                        description,
-                        minimum_quorum: minimun_quorum.into(),
-                        end_time_ts,
-                        start_time_ts,
-                        owner_token_address: self.owner_token_addresss.clone(),
-                        component_address: global_proposal_component.address(),
-                        address_issued_bonds_to_sell,
-                        target_xrd_amount,
-                        proposal_creator_address,
-                        amount_of_tokens_should_be_minted,
-                        proposal_id,
-                        governance_token_or_owner_token_address:
-                            governance_token_or_owner_token_address.resource_address(),
-                        token_type: VotingType::Equality,
-                        desired_token_price,
-                        desired_token_buy_back_price,
-                    };
+        pub fn get_liquidated_collateral(&self) -> HashMap<ResourceAddress, Decimal> {
+            self.liquidated_collateral
+                .iter()
+                .map(|(resource_address, vault)| (*resource_address, vault.amount()))
+                .collect()
+        }
 
-                    let component_address = Runtime::global_address();
+        pub fn start_collateral_auction(
+            &mut self,
+            collateral_resource_address: ResourceAddress,
+            amount: Decimal,
+            start_price: Decimal,
+            floor_price: Decimal,
+            decay_curve: DecayCurve,
+            duration: u64,
+            proceeds_destination: AuctionProceedsDestination,
+        ) -> Global<DutchAuction> {
+            if let AuctionProceedsDestination::Bondholders(bond_component_address) =
+                &proceeds_destination
+            {
+                let source_bond = self
+                    .find_bond(*bond_component_address)
+                    .expect("no bond of this DAO found at the specified address");
 
-                    Runtime::emit_event(PandaoEvent {
-                        event_type: EventType::PROPOSAL_TO_CHANGE_TOKEN_PRICE,
-                        dao_type: DaoType::Investment,
-                        meta_data: DaoEvent::PraposalDeployment(praposal_metadata),
-                        component_address,
-                    });
-                }
+                assert!(
+                    source_bond.get_resource_address_of_collateral() == collateral_resource_address,
+                    "this collateral was not seized from the specified bond"
+                );
             }
 
-            let mut message = String::new();
-            message = format!("Proposal created with id : {}", proposal_id);
+            let vault = self
+                .liquidated_collateral
+                .get_mut(&collateral_resource_address)
+                .expect("no liquidated collateral held for this resource");
 
-            (
-                global_proposal_component,
-                message,
-                governance_token_or_owner_token_address,
-            )
+            assert!(
+                amount <= vault.amount(),
+                "not enough liquidated collateral to auction"
+            );
+
+            let collateral = vault.take(amount);
+
+            let auction = DutchAuction::instantiate_dutch_auction(
+                Runtime::global_address(),
+                collateral,
+                start_price,
+                floor_price,
+                decay_curve.clone(),
+                duration,
+            );
+
+            let auction_address = auction.address();
+
+            self.collateral_auctions.insert(
+                auction_address,
+                CollateralAuction {
+                    collateral_resource_address,
+                    proceeds_destination: proceeds_destination.clone(),
+                },
+            );
+
+            let event_metadata = CollateralAuctionStarted {
+                auction_address,
+                collateral_resource_address,
+                amount,
+                start_price,
+                floor_price,
+                decay_curve,
+                duration,
+                proceeds_destination,
+            };
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::COLLATERAL_AUCTION_STARTED,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::CollateralAuctionStarted(event_metadata),
+            });
+
+            auction
         }
 
-        pub fn execute_proposal_to_change_token_price(
-            &mut self,
-            proposal_id: usize,
-        ) -> Result<String, String> {
-            for (_, inner_map) in self.current_praposals.clone() {
-                let proposal = inner_map.get(&proposal_id);
+        // moves whatever the auction has collected so far to its destination
+        pub fn settle_collateral_auction(&mut self, auction_address: ComponentAddress) -> Decimal {
+            let collateral_auction = self
+                .collateral_auctions
+                .get(&auction_address)
+                .cloned()
+                .expect("no collateral auction found at this address");
 
-                let now: Instant = Clock::current_time_rounded_to_seconds();
-                let current_time_seconds: i64 = now.seconds_since_unix_epoch;
+            let auction: Global<DutchAuction> = auction_address.into();
 
-                let last_time = proposal.unwrap().get_last_time();
-                let end_time_seconds = last_time.to_instant().seconds_since_unix_epoch;
+            let proceeds = auction.withdraw_proceeds();
+            let proceeds_amount = proceeds.amount();
 
-                // Debug statements to verify the times
-                println!("Current time (epoch seconds): {}", current_time_seconds);
-                println!("Proposal end time (epoch seconds): {}", end_time_seconds);
+            self.route_auction_proceeds(&collateral_auction.proceeds_destination, proceeds);
 
-                assert!(
-                    current_time_seconds > end_time_seconds,
-                    "Proposal can only be executed after the specified end time"
-                );
+            let event_metadata = CollateralAuctionSettled {
+                auction_address,
+                collateral_resource_address: collateral_auction.collateral_resource_address,
+                proceeds: proceeds_amount,
+                proceeds_destination: collateral_auction.proceeds_destination,
+                unsold_collateral_returned: Decimal::zero(),
+            };
 
-                match proposal {
-                    Some(proposal) => {
-                        let number_of_voters = proposal.get_number_of_voters(); //inline attribute on fn definition
-                        let minimum_quorum = proposal.get_minimum_quorum();
-
-                        if let Some(desired_price) = proposal.get_desired_token_price() {
-                            if let Some(buy_back) = proposal.get_desired_token_buy_back_price() {
-                                if number_of_voters < minimum_quorum {
-                                    // Emit an event indicating that the proposal cannot be executed due to insufficient participation
-                                    let event_metadata = PriceChangeProposalQuorumNotMet {
-                                        proposal_id,
-                                        minimum_quorum: proposal.get_minimum_quorum(),
-                                        number_of_voters,
-                                        desired_price,
-                                        desired_token_buy_back_price: buy_back,
-                                        proposal_type: EventType::PROPOSAL_TO_CHANGE_TOKEN_PRICE,
-                                    };
-
-                                    let component_address = Runtime::global_address();
-
-                                    Runtime::emit_event(PandaoEvent {
-                                        event_type:
-                                            EventType::PRICE_CHANGE_QUORUM_NOT_MET_AND_FAILED,
-                                        dao_type: DaoType::Investment,
-                                        component_address,
-                                        meta_data: DaoEvent::PriceChangeProposalQuorumNotMet(
-                                            event_metadata,
-                                        ),
-                                    });
-                                }
-
-                                self.set_price(desired_price, buy_back);
-
-                                let event_metadata = PriceChangeProposalQuorumMet {
-                                    proposal_id,
-                                    minimum_quorum: proposal.get_minimum_quorum(),
-                                    number_of_voters,
-                                    desired_token_price: desired_price,
-                                    desired_token_buy_back_price: buy_back,
-                                    proposal_type: EventType::PROPOSAL_TO_CHANGE_TOKEN_PRICE,
-                                };
-
-                                let component_address = Runtime::global_address();
-
-                                Runtime::emit_event(PandaoEvent {
-                                    event_type: EventType::PRICE_CHANGE_QUORUM_MET_AND_SUCCESS,
-                                    dao_type: DaoType::Investment,
-                                    component_address,
-                                    meta_data: DaoEvent::PriceChangeProposalQuorumMet(
-                                        event_metadata,
-                                    ),
-                                });
-
-                                let message = "proposal executed successfully".to_string();
-
-                                return Ok(message);
-                            } else {
-                                return Err(format!("desired token buy back price is not present in a proposal with id : {proposal_id}"));
-                            }
-                        } else {
-                            return Err(format!("desired token price is not present in a proposal with id : {proposal_id}"));
-                        }
-                    }
-                    None => return Err(format!("proposal with id : {proposal_id} not found")),
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::COLLATERAL_AUCTION_SETTLED,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::CollateralAuctionSettled(event_metadata),
+            });
+
+            proceeds_amount
+        }
+
+        // ends the auction, settles the proceeds and takes back unsold collateral
+        pub fn close_collateral_auction(&mut self, auction_address: ComponentAddress) {
+            let collateral_auction = self
+                .collateral_auctions
+                .remove(&auction_address)
+                .expect("no collateral auction found at this address");
+
+            let auction: Global<DutchAuction> = auction_address.into();
+
+            let (proceeds, unsold_collateral) = auction.close_auction();
+            let proceeds_amount = proceeds.amount();
+            let unsold_collateral_returned = unsold_collateral.amount();
+
+            self.route_auction_proceeds(&collateral_auction.proceeds_destination, proceeds);
+
+            if unsold_collateral.is_empty() {
+                unsold_collateral.drop_empty();
+            } else {
+                self.store_liquidated_collateral(unsold_collateral);
+            }
+
+            let event_metadata = CollateralAuctionSettled {
+                auction_address,
+                collateral_resource_address: collateral_auction.collateral_resource_address,
+                proceeds: proceeds_amount,
+                proceeds_destination: collateral_auction.proceeds_destination,
+                unsold_collateral_returned,
+            };
+
+            Runtime::emit_event(PandaoEvent {
+                event_type: EventType::COLLATERAL_AUCTION_CLOSED,
+                dao_type: DaoType::Investment,
+                component_address: Runtime::global_address(),
+                meta_data: DaoEvent::CollateralAuctionSettled(event_metadata),
+            });
+        }
+
+        pub fn get_collateral_auctions(&self) -> Vec<(ComponentAddress, AuctionDetails)> {
+            let mut result = Vec::new();
+
+            for auction_address in self.collateral_auctions.keys() {
+                let auction: Global<DutchAuction> = (*auction_address).into();
+                result.push((*auction_address, auction.get_auction_details()));
+            }
+
+            result
+        }
+
+        fn route_auction_proceeds(
+            &mut self,
+            proceeds_destination: &AuctionProceedsDestination,
+            proceeds: Bucket,
+        ) {
+            match proceeds_destination {
+                AuctionProceedsDestination::Treasury => {
+                    self.shares.put(proceeds);
+                }
+                AuctionProceedsDestination::Bondholders(bond_component_address) => {
+                    let source_bond = self
+                        .find_bond(*bond_component_address)
+                        .expect("no bond of this DAO found at the specified address");
+
+                    source_bond.deposit_recovery_proceeds(proceeds);
                 }
             }
-            Ok("execute fn called successfully".to_string())
+        }
+
+        fn find_bond(&self, bond_component_address: ComponentAddress) -> Option<Global<ZeroCouponBond>> {
+            self.zero_coupon_bond
+                .values()
+                .flatten()
+                .find(|bond_component| bond_component.address() == bond_component_address)
+                .cloned()
         }
     }
 }
@@ -2882,6 +5745,3 @@ mod radixdao {
 //* DO WE REALLY NEED TO HAVE A COMMUNITY TOKEN FOR PROPOSAL CREATION?*/
 //*hustlepreet secondry account
 // account_tdx_2_128e6fmjkhjqx0n8h9562rrvstl883wq22pzea4ucnnx0762ptlch4s
-
-//*missing functions
-// become_a_dao_member
@@ -0,0 +1,122 @@
+use scrypto::prelude::*;
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct PriceEntry {
+    /// Price of one unit of the resource, quoted in XRD.
+    pub price: Decimal,
+
+    /// Ledger time (seconds since unix epoch) of the last update.
+    pub updated_at: u64,
+}
+
+#[blueprint]
+mod price_oracle {
+
+    enable_method_auth! {
+        roles {
+            feeder => updatable_by: [OWNER];
+        },
+
+        methods {
+            update_price => restrict_to: [feeder];
+            remove_price => restrict_to: [feeder];
+            set_max_price_age => restrict_to: [OWNER];
+            get_price => PUBLIC;
+            get_price_entry => PUBLIC;
+            get_max_price_age => PUBLIC;
+        }
+    }
+
+    pub struct PriceOracle {
+        /// Latest price reported by the feeder for each resource.
+        pub prices: HashMap<ResourceAddress, PriceEntry>,
+
+        /// Prices older than this (in seconds) are considered stale and are not served.
+        pub max_price_age: u64,
+    }
+
+    impl PriceOracle {
+
+        // resim call-function <package> PriceOracle instantiate_price_oracle 3600
+        // returns the owner badge, which sets the price age and the feeder, and the feeder badge, which reports prices
+        pub fn instantiate_price_oracle(max_price_age: u64) -> (Global<PriceOracle>, Bucket, Bucket) {
+
+            let owner_badge: Bucket = ResourceBuilder::new_fungible(OwnerRole::None)
+                .divisibility(DIVISIBILITY_NONE)
+                .metadata(metadata!(
+                    init {
+                        "name" => "Price Oracle Owner Badge", locked;
+                        "symbol" => "ORACLE", locked;
+                    }
+                ))
+                .mint_initial_supply(1)
+                .into();
+
+            let feeder_badge: Bucket = ResourceBuilder::new_fungible(OwnerRole::None)
+                .divisibility(DIVISIBILITY_NONE)
+                .metadata(metadata!(
+                    init {
+                        "name" => "Price Oracle Feeder Badge", locked;
+                        "symbol" => "FEED", locked;
+                    }
+                ))
+                .mint_initial_supply(1)
+                .into();
+
+            let component = Self {
+                prices: HashMap::new(),
+                max_price_age,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Fixed(rule!(require(
+                owner_badge.resource_address()
+            ))))
+            .roles(roles!(
+                feeder => rule!(require(feeder_badge.resource_address()));
+            ))
+            .globalize();
+
+            (component, owner_badge, feeder_badge)
+        }
+
+        pub fn update_price(&mut self, resource_address: ResourceAddress, price: Decimal) {
+            assert!(price > Decimal::zero(), "price must be greater than zero");
+
+            let now: Instant = Clock::current_time_rounded_to_seconds();
+            let updated_at: u64 = now.seconds_since_unix_epoch as u64;
+
+            self.prices
+                .insert(resource_address, PriceEntry { price, updated_at });
+        }
+
+        pub fn remove_price(&mut self, resource_address: ResourceAddress) {
+            self.prices.remove(&resource_address);
+        }
+
+        pub fn set_max_price_age(&mut self, max_price_age: u64) {
+            self.max_price_age = max_price_age;
+        }
+
+        /// Returns the latest price if one exists and it is not stale.
+        pub fn get_price(&self, resource_address: ResourceAddress) -> Option<Decimal> {
+            let entry = self.prices.get(&resource_address)?;
+
+            let now: Instant = Clock::current_time_rounded_to_seconds();
+            let current_time_seconds: u64 = now.seconds_since_unix_epoch as u64;
+
+            if current_time_seconds > entry.updated_at.saturating_add(self.max_price_age) {
+                None
+            } else {
+                Some(entry.price)
+            }
+        }
+
+        pub fn get_price_entry(&self, resource_address: ResourceAddress) -> Option<PriceEntry> {
+            self.prices.get(&resource_address).cloned()
+        }
+
+        pub fn get_max_price_age(&self) -> u64 {
+            self.max_price_age
+        }
+    }
+}
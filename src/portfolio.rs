@@ -0,0 +1,178 @@
+use actus_schedule as schedule;
+use crate::zerocouponbond::{BondDetails, BondStatus};
+use scrypto::prelude::*;
+
+const SECONDS_IN_DAY: i64 = 24 * 60 * 60;
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct BondHolding {
+    pub bond_creator_address: ComponentAddress,
+    pub bond_component_address: ComponentAddress,
+    pub bond_resource_address: ResourceAddress,
+    pub contract_identifier: String,
+    pub quantity: Decimal,
+    // XRD paid for the bonds still held
+    pub cost_basis: Decimal,
+    // when the DAO bought the bonds, averaged over its purchases
+    pub acquired_at: u64,
+    // gap between cost basis and redemption value earned so far, accrued linearly from acquisition to maturity
+    pub accrued_interest: Decimal,
+    // principal plus interest due at maturity
+    pub expected_redemption_value: Decimal,
+    // cost basis plus accrued interest
+    pub carrying_value: Decimal,
+    // oracle price * quantity, None when no fresh price is available
+    pub market_value: Option<Decimal>,
+    pub days_to_maturity: i64,
+    pub status: BondStatus,
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct PortfolioReport {
+    pub holdings: Vec<BondHolding>,
+    pub total_quantity: Decimal,
+    pub total_cost_basis: Decimal,
+    pub total_accrued_interest: Decimal,
+    pub total_expected_redemption_value: Decimal,
+    pub total_carrying_value: Decimal,
+    // market value where a price exists, carrying value otherwise
+    pub net_asset_value: Decimal,
+    // number of holdings valued with a market price
+    pub holdings_marked_to_market: usize,
+}
+
+impl BondHolding {
+    pub fn new(
+        bond_creator_address: ComponentAddress,
+        bond_component_address: ComponentAddress,
+        bond_resource_address: ResourceAddress,
+        bond_details: &BondDetails,
+        quantity: Decimal,
+        cost_basis: Decimal,
+        acquired_at: u64,
+        market_price: Option<Decimal>,
+        current_time_seconds: u64,
+    ) -> Self {
+        let price = Decimal::from(bond_details.price);
+
+        let expected_redemption_value =
+            schedule::redemption_value(price, bond_details.nominal_interest_rate) * quantity;
+
+        let accrued_interest = accrued_since_acquisition(
+            cost_basis,
+            expected_redemption_value,
+            acquired_at,
+            bond_details.maturity_date,
+            current_time_seconds,
+        );
+
+        Self {
+            bond_creator_address,
+            bond_component_address,
+            bond_resource_address,
+            contract_identifier: bond_details.contract_identifier.clone(),
+            quantity,
+            cost_basis,
+            acquired_at,
+            accrued_interest,
+            expected_redemption_value,
+            carrying_value: cost_basis + accrued_interest,
+            market_value: market_price.map(|market_price| market_price * quantity),
+            days_to_maturity: (bond_details.maturity_date as i64 - current_time_seconds as i64)
+                / SECONDS_IN_DAY,
+            status: bond_details.status,
+        }
+    }
+}
+
+// what the DAO earns on bonds bought for `cost_basis` and redeemed for `redemption_value`,
+// accrued linearly from `acquired_at` to maturity, so a purchase never starts with interest it did not pay for
+pub fn accrued_since_acquisition(
+    cost_basis: Decimal,
+    redemption_value: Decimal,
+    acquired_at: u64,
+    maturity_date: u64,
+    time: u64,
+) -> Decimal {
+    let total_gain = (redemption_value - cost_basis).max(Decimal::zero());
+
+    if time >= maturity_date || acquired_at >= maturity_date {
+        return total_gain;
+    }
+
+    let elapsed = time.saturating_sub(acquired_at);
+
+    total_gain * Decimal::from(elapsed) / Decimal::from(maturity_date - acquired_at)
+}
+
+impl PortfolioReport {
+    pub fn from_holdings(holdings: Vec<BondHolding>) -> Self {
+        let mut report = Self {
+            holdings: Vec::new(),
+            total_quantity: Decimal::zero(),
+            total_cost_basis: Decimal::zero(),
+            total_accrued_interest: Decimal::zero(),
+            total_expected_redemption_value: Decimal::zero(),
+            total_carrying_value: Decimal::zero(),
+            net_asset_value: Decimal::zero(),
+            holdings_marked_to_market: 0,
+        };
+
+        for holding in &holdings {
+            report.total_quantity += holding.quantity;
+            report.total_cost_basis += holding.cost_basis;
+            report.total_accrued_interest += holding.accrued_interest;
+            report.total_expected_redemption_value += holding.expected_redemption_value;
+            report.total_carrying_value += holding.carrying_value;
+
+            match holding.market_value {
+                Some(market_value) => {
+                    report.net_asset_value += market_value;
+                    report.holdings_marked_to_market += 1;
+                }
+                None => report.net_asset_value += holding.carrying_value,
+            }
+        }
+
+        report.holdings = holdings;
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nothing_is_accrued_on_the_day_of_purchase() {
+        assert_eq!(
+            accrued_since_acquisition(dec!("40"), dec!("40.8"), 500, 1000, 500),
+            Decimal::zero()
+        );
+    }
+
+    #[test]
+    fn interest_accrues_from_acquisition_not_issue() {
+        // bought half way to maturity at a price that already included half of the interest
+        assert_eq!(
+            accrued_since_acquisition(dec!("40.4"), dec!("40.8"), 500, 1000, 750),
+            dec!("0.2")
+        );
+    }
+
+    #[test]
+    fn everything_is_accrued_at_maturity() {
+        assert_eq!(
+            accrued_since_acquisition(dec!("40"), dec!("40.8"), 500, 1000, 2000),
+            dec!("0.8")
+        );
+    }
+
+    #[test]
+    fn a_purchase_above_redemption_value_accrues_nothing() {
+        assert_eq!(
+            accrued_since_acquisition(dec!("41"), dec!("40.8"), 500, 1000, 750),
+            Decimal::zero()
+        );
+    }
+}
@@ -2,9 +2,49 @@ use scrypto::prelude::*;
 
 #[blueprint]
 mod pandao_praposal {
-    use std::path::Component;
-
-    use crate::VotingType;
+    use crate::{
+        Ballot, CountingMethod, OptionCount, PassingCheck, PassingRule, ProposalAction, ProposalOption,
+        ProposalStatus, VoteTally, VotingType,
+    };
+
+    enable_method_auth! {
+        roles {
+            dao => updatable_by: [];
+        },
+
+        methods {
+            vote => restrict_to: [dao];
+            vote_by_member => restrict_to: [dao];
+            vote_on_options => restrict_to: [dao];
+            delegated_vote => restrict_to: [dao];
+            withdraw_delegated_vote => restrict_to: [dao];
+            set_vote_caster_address => restrict_to: [dao];
+            queue => restrict_to: [dao];
+            mark_executed => restrict_to: [dao];
+            cancel => restrict_to: [dao];
+            close => restrict_to: [dao];
+            release_escrow => restrict_to: [dao];
+            get_action => PUBLIC;
+            get_options => PUBLIC;
+            get_counting_method => PUBLIC;
+            get_option_count => PUBLIC;
+            get_voting_type => PUBLIC;
+            vote_weight => PUBLIC;
+            get_vote_caster_addresses => PUBLIC;
+            get_last_time => PUBLIC;
+            get_number_of_voters => PUBLIC;
+            get_minimum_quorum => PUBLIC;
+            get_votes => PUBLIC;
+            get_vote_tally => PUBLIC;
+            get_passing_rule => PUBLIC;
+            get_passing_check => PUBLIC;
+            get_status => PUBLIC;
+            is_open => PUBLIC;
+            time_remaining => PUBLIC;
+            is_executable => PUBLIC;
+            get_escrowed_amount => PUBLIC;
+        }
+    }
 
     pub struct TokenWeightProposal {
         /// A simple string representing the current proposal.
@@ -36,17 +76,58 @@ mod pandao_praposal {
     
         // A mapping of addresses to their respective vote weights.
         // pub votes: HashMap<Address, Decimal>,
-        pub address_issued_bonds_to_sell : Option<ComponentAddress>,
-        pub target_xrd_amount: Option<Decimal>,
         pub vote_caster_addresses : HashSet<ComponentAddress>,
         pub proposal_creator_address : Option<ComponentAddress>,
-        pub amount_of_tokens_should_be_minted : Option<usize>,
         pub voting_type: VotingType,
-        pub proposal_creation_status : bool,
-        pub proposal_execution_status : bool,
-        pub proposal_denied_status : bool,
-        pub desired_token_price : Option<Decimal>,
-        pub desired_buy_back_price : Option<Decimal>
+
+        /// Only the explicit transitions are stored here: Queued, Executed, Cancelled and the recorded final states.
+        /// Pending, Active, Succeeded, Defeated and Expired otherwise follow from time and tallies.
+        pub status : ProposalStatus,
+
+        /// Seconds a queued proposal waits before it can be executed.
+        pub execution_delay : u64,
+
+        /// Seconds after the end time during which a passed proposal can still be executed.
+        pub execution_window : u64,
+
+        pub queued_at : Option<u64>,
+
+        /// Rule the proposal has to meet, copied from the DAO's rule for its category at creation.
+        pub passing_rule : PassingRule,
+
+        /// Governance tokens outside the DAO's vault when the proposal was created, for the turnout.
+        pub circulating_supply : Decimal,
+
+        /// Tokens presented by all voters, whatever the voting type.
+        pub tokens_voted : Decimal,
+
+        /// Raw tokens behind voted_for and voted_against, they differ from the weight unless voting by resource hold.
+        pub tokens_for : Decimal,
+
+        pub tokens_against : Decimal,
+
+        /// Voting tokens are locked here until voting has ended, so the same tokens can not vote twice.
+        pub escrow : Vault,
+
+        pub closed_at : Option<u64>,
+
+        /// Part of voted_for and voted_against cast by delegates on behalf of their delegators.
+        pub delegated_for : Decimal,
+
+        pub delegated_against : Decimal,
+
+        /// Delegators whose weight is counted, they count as voters for the quorum.
+        pub delegators : HashSet<ComponentAddress>,
+
+        /// What the DAO does with this proposal once it is executed, the first option's action on a proposal with options.
+        pub action : ProposalAction,
+
+        /// Labelled alternatives voted on with ballots instead of for and against, empty on yes/no proposals.
+        pub options : Vec<ProposalOption>,
+
+        pub counting_method : Option<CountingMethod>,
+
+        pub ballots : Vec<Ballot>
         // pub number_of_people_voted: i32
     }
 
@@ -60,22 +141,21 @@ mod pandao_praposal {
             end_time: scrypto::time::UtcDateTime,
             owner_badge_address: ResourceAddress,
             voter_badge_address: ResourceAddress,
-            address_issued_bonds_to_sell : Option<ComponentAddress>,
-            target_xrd_amount : Option<Decimal>,
             proposal_creator_address : Option<ComponentAddress>,
-            amount_of_tokens_should_be_minted : Option<usize>,
             voting_type: VotingType,
-            desired_token_price : Option<Decimal>,
-            desired_buy_back_price : Option<Decimal>
+            action : ProposalAction,
+            options : Vec<ProposalOption>,
+            counting_method : Option<CountingMethod>,
+            execution_delay : u64,
+            execution_window : u64,
+            dao_address : ComponentAddress,
+            passing_rule : PassingRule,
+            circulating_supply : Decimal
         ) -> (Global<TokenWeightProposal >, GlobalAddressReservation) {
             
             let (address_reservation, _) =
                 Runtime::allocate_component_address(TokenWeightProposal ::blueprint_id());
 
-            let proposal_creation_status = true;
-            let proposal_execution_status = false;
-            let proposal_denied_status = false; 
-
             let proposal = TokenWeightProposal {
                 title,
                 description,
@@ -86,20 +166,33 @@ mod pandao_praposal {
                 start_time,
                 owner_token_address:owner_badge_address,
                 voter_badge_address,
-                address_issued_bonds_to_sell,
-                target_xrd_amount,
                 vote_caster_addresses : HashSet::new(),
                 proposal_creator_address,
-                amount_of_tokens_should_be_minted,
                 voting_type,
-                proposal_creation_status,
-                proposal_execution_status,
-                proposal_denied_status,
-                desired_token_price,
-                desired_buy_back_price
+                status: ProposalStatus::Pending,
+                execution_delay,
+                execution_window,
+                queued_at: None,
+                passing_rule,
+                circulating_supply,
+                tokens_voted: Decimal::zero(),
+                tokens_for: Decimal::zero(),
+                tokens_against: Decimal::zero(),
+                escrow: Vault::new(voter_badge_address),
+                closed_at: None,
+                delegated_for: Decimal::zero(),
+                delegated_against: Decimal::zero(),
+                delegators: HashSet::new(),
+                action,
+                options,
+                counting_method,
+                ballots: Vec::new()
             }
             .instantiate()
             .prepare_to_globalize(OwnerRole::None)
+            .roles(roles!(
+                dao => rule!(require(global_caller(dao_address)));
+            ))
             .with_address(address_reservation.clone())
             .globalize();
 
@@ -110,18 +203,13 @@ mod pandao_praposal {
         //     self.desired_token_price = desired_price;
         // }
 
-        pub fn vote(&mut self, token: Bucket, against: bool) -> Bucket {
+        // the tokens are locked in the escrow until voting has ended
+        pub fn vote(&mut self, token: Bucket, against: bool) {
 
-            let mut amount : Decimal = Default::default();
+            self.assert_open();
+            self.assert_yes_no();
 
-            match self.voting_type {
-                VotingType::ResourceHold => {
-                    amount = token.amount();
-                }
-                VotingType::Equality => {
-                    amount = Decimal::one();
-                }
-            }
+            let amount = self.vote_weight(token.amount());
 
             assert_eq!(
                 token.resource_address(),
@@ -132,23 +220,134 @@ mod pandao_praposal {
 
             // let amount = token.amount();
 
+            self.tokens_voted += token.amount();
+
             if against {
                 
                 self.voted_against += amount;
+                self.tokens_against += token.amount();
                 // self.number_of_people_voted +=1 ; 
 
-                token
-
             } else {
 
                 self.voted_for += amount;
+                self.tokens_for += token.amount();
                 // self.number_of_people_voted +=1 ; 
 
-                token
+            }
+
+            self.escrow.put(token);
+        }
+
+        // a ballot on a proposal with options, choices are option indexes in order of preference
+        pub fn vote_on_options(&mut self, token: Bucket, choices: Vec<usize>) {
+            self.assert_open();
 
+            let counting_method = self
+                .counting_method
+                .expect("this is a yes/no proposal, vote for or against it");
+
+            assert_eq!(
+                token.resource_address(),
+                self.voter_badge_address,
+                "wrong voting token supplied"
+            );
+
+            counting_method.assert_valid_choices(self.options.len(), &choices);
+
+            let tokens = token.amount();
+
+            self.tokens_voted += tokens;
+            self.ballots.push(Ballot {
+                choices,
+                tokens,
+                weight: self.vote_weight(tokens),
+            });
+
+            self.escrow.put(token);
+        }
+
+        // one vote for a member badge holder, the DAO checks the badge before calling this
+        // the member's `tokens` count towards turnout only, the vote itself weighs one
+        pub fn vote_by_member(&mut self, against: bool, tokens: Decimal) {
+            self.assert_open();
+            self.assert_yes_no();
+
+            assert_eq!(
+                self.voting_type,
+                VotingType::Equality,
+                "only equality proposals count one vote per member"
+            );
+
+            self.tokens_voted += tokens;
+
+            if against {
+                self.voted_against += Decimal::one();
+            } else {
+                self.voted_for += Decimal::one();
             }
+        }
 
-            
+        // a delegate votes with the weight of a delegator, whose tokens stay locked in the DAO
+        pub fn delegated_vote(&mut self, delegator: ComponentAddress, tokens: Decimal, against: bool) -> Decimal {
+            self.assert_open();
+            self.assert_yes_no();
+
+            assert!(
+                !self.vote_caster_addresses.contains(&delegator) && !self.delegators.contains(&delegator),
+                "this delegator's vote is already counted"
+            );
+
+            let amount = self.vote_weight(tokens);
+
+            self.tokens_voted += tokens;
+            self.delegators.insert(delegator);
+
+            if against {
+                self.voted_against += amount;
+                self.delegated_against += amount;
+                self.tokens_against += tokens;
+            } else {
+                self.voted_for += amount;
+                self.delegated_for += amount;
+                self.tokens_for += tokens;
+            }
+
+            amount
+        }
+
+        // the delegator votes themselves, so the weight their delegate cast for them is taken back out
+        pub fn withdraw_delegated_vote(&mut self, delegator: ComponentAddress, tokens: Decimal, against: bool) -> Decimal {
+            self.assert_open();
+
+            assert!(self.delegators.remove(&delegator), "this delegator's vote is not counted");
+
+            let amount = self.vote_weight(tokens);
+
+            self.tokens_voted -= tokens;
+
+            if against {
+                self.voted_against -= amount;
+                self.delegated_against -= amount;
+                self.tokens_against -= tokens;
+            } else {
+                self.voted_for -= amount;
+                self.delegated_for -= amount;
+                self.tokens_for -= tokens;
+            }
+
+            amount
+        }
+
+        // what the given tokens count for in the tallies of this proposal
+        pub fn vote_weight(&self, tokens: Decimal) -> Decimal {
+            match self.voting_type {
+                VotingType::ResourceHold => tokens,
+                VotingType::Equality => Decimal::one(),
+                VotingType::Quadratic => tokens
+                    .checked_sqrt()
+                    .expect("could not take the square root of the voting tokens"),
+            }
         }
 
         // pub fn get_the_collateral_back(&self)
@@ -175,22 +374,25 @@ mod pandao_praposal {
             
         // }
 
-        pub fn get_address_issued_bonds(&self) -> ComponentAddress {
-
-            if let Some(address_issued_bonds_to_sell) =self.address_issued_bonds_to_sell{
-                address_issued_bonds_to_sell
-            }else{
-                panic!("address issued bonds to sell is None")
+        // on a proposal with options this is the action of the option currently winning
+        pub fn get_action(&self) -> ProposalAction {
+            match self.get_option_count().and_then(|count| count.winner) {
+                Some(winner) => self.options[winner].action.clone(),
+                None => self.action.clone(),
             }
         }
 
-        pub fn get_target_xrd_amount(&self) -> Decimal {
+        pub fn get_options(&self) -> Vec<ProposalOption> {
+            self.options.clone()
+        }
+
+        pub fn get_counting_method(&self) -> Option<CountingMethod> {
+            self.counting_method
+        }
 
-            if let Some(target_xrd_amount) =self.target_xrd_amount{
-                target_xrd_amount
-            }else{
-                panic!("target xrd amount is None")
-            }
+        pub fn get_option_count(&self) -> Option<OptionCount> {
+            self.counting_method
+                .map(|counting_method| counting_method.count(self.options.len(), &self.ballots))
         }
 
         pub fn get_vote_caster_addresses(&self) -> HashSet<ComponentAddress> {
@@ -202,27 +404,207 @@ mod pandao_praposal {
         }
 
         pub fn get_last_time(&self) -> scrypto::time::UtcDateTime {
-            self.end_time.clone()
+            self.end_time
         }
 
-        pub fn get_token_mint_amount(&self) -> Option<usize> {
-            self.amount_of_tokens_should_be_minted
+        pub fn get_voting_type(&self) -> VotingType {
+            self.voting_type.clone()
         }
 
         pub fn get_number_of_voters(&self) -> usize {
-            self.vote_caster_addresses.len()
+            self.vote_caster_addresses.len() + self.delegators.len()
         }
 
         pub fn get_minimum_quorum(&self) -> usize {
             self.minimum_quorum
         }
 
-        pub fn get_desired_token_price(&self) -> Option<Decimal>{
-            self.desired_token_price
+        // (voted_for, voted_against), on a proposal with options the winner against the strongest other option
+        pub fn get_votes(&self) -> (Decimal, Decimal) {
+            match self.get_option_count() {
+                Some(count) => count.decisive_votes(),
+                None => (self.voted_for, self.voted_against),
+            }
+        }
+
+        pub fn get_vote_tally(&self) -> VoteTally {
+            VoteTally {
+                direct_for: self.voted_for - self.delegated_for,
+                direct_against: self.voted_against - self.delegated_against,
+                delegated_for: self.delegated_for,
+                delegated_against: self.delegated_against,
+                tokens_for: self.tokens_for,
+                tokens_against: self.tokens_against,
+                direct_voters: self.vote_caster_addresses.len(),
+                delegators: self.delegators.len(),
+            }
+        }
+
+        fn now() -> i64 {
+            let now: Instant = Clock::current_time_rounded_to_seconds();
+            now.seconds_since_unix_epoch
         }
 
-        pub fn get_desired_token_buy_back_price(&self) -> Option<Decimal>{
-            self.desired_buy_back_price
+        fn passed(&self) -> bool {
+            self.get_passing_check().passed
+        }
+
+        pub fn get_passing_rule(&self) -> PassingRule {
+            self.passing_rule.clone()
+        }
+
+        pub fn get_passing_check(&self) -> PassingCheck {
+            let (voted_for, voted_against) = self.get_votes();
+
+            self.passing_rule.evaluate(
+                self.get_number_of_voters(),
+                self.minimum_quorum,
+                voted_for,
+                voted_against,
+                self.tokens_voted,
+                self.circulating_supply,
+            )
+        }
+
+        fn execution_deadline(&self) -> i64 {
+            self.end_time
+                .to_instant()
+                .seconds_since_unix_epoch
+                .saturating_add(self.execution_window.min(i64::MAX as u64) as i64)
+        }
+
+        pub fn get_status(&self) -> ProposalStatus {
+            let now = Self::now();
+
+            match self.status {
+                ProposalStatus::Queued => {
+                    if now > self.execution_deadline() {
+                        ProposalStatus::Expired
+                    } else {
+                        ProposalStatus::Queued
+                    }
+                }
+                ProposalStatus::Pending | ProposalStatus::Active | ProposalStatus::Succeeded => {
+                    if now < self.start_time.to_instant().seconds_since_unix_epoch {
+                        ProposalStatus::Pending
+                    } else if now <= self.end_time.to_instant().seconds_since_unix_epoch {
+                        ProposalStatus::Active
+                    } else if !self.passed() {
+                        ProposalStatus::Defeated
+                    } else if now > self.execution_deadline() {
+                        ProposalStatus::Expired
+                    } else {
+                        ProposalStatus::Succeeded
+                    }
+                }
+                status => status,
+            }
+        }
+
+        fn transition(&mut self, next: ProposalStatus) -> ProposalStatus {
+            let current = self.get_status();
+
+            assert!(
+                current.can_transition_to(next),
+                "a {:?} proposal can not become {:?}",
+                current,
+                next
+            );
+
+            self.status = next;
+
+            if next.is_final() {
+                self.closed_at = Some(Self::now() as u64);
+            }
+
+            current
+        }
+
+        // each returns the status the proposal had before the transition
+        pub fn queue(&mut self) -> ProposalStatus {
+            let previous = self.transition(ProposalStatus::Queued);
+            self.queued_at = Some(Self::now() as u64);
+            previous
+        }
+
+        pub fn mark_executed(&mut self) -> ProposalStatus {
+            assert!(self.is_executable(), "the proposal can not be executed yet");
+            self.transition(ProposalStatus::Executed)
+        }
+
+        pub fn cancel(&mut self) -> ProposalStatus {
+            self.transition(ProposalStatus::Cancelled)
+        }
+
+        // records Defeated or Expired once they follow from time and tallies
+        pub fn close(&mut self) -> ProposalStatus {
+            let status = self.get_status();
+
+            assert!(
+                !self.status.is_final()
+                    && matches!(status, ProposalStatus::Defeated | ProposalStatus::Expired),
+                "only a defeated or expired proposal can be closed"
+            );
+
+            self.transition(status)
+        }
+
+        pub fn is_open(&self) -> bool {
+            self.get_status() == ProposalStatus::Active
+        }
+
+        // seconds left to vote, 0 once voting has closed
+        pub fn time_remaining(&self) -> u64 {
+            if !self.is_open() {
+                return 0;
+            }
+
+            (self.end_time.to_instant().seconds_since_unix_epoch - Self::now()).max(0) as u64
+        }
+
+        fn assert_yes_no(&self) {
+            assert!(
+                self.options.is_empty(),
+                "this proposal has several options, please vote with a ballot"
+            );
+        }
+
+        fn assert_open(&self) {
+            match self.get_status() {
+                ProposalStatus::Active => {}
+                ProposalStatus::Pending => panic!(
+                    "voting on this proposal has not started yet, it opens at {}",
+                    self.start_time.to_instant().seconds_since_unix_epoch
+                ),
+                ProposalStatus::Cancelled => panic!("the proposal was cancelled, votes are not accepted"),
+                _ => panic!(
+                    "late vote: voting on this proposal closed at {}",
+                    self.end_time.to_instant().seconds_since_unix_epoch
+                ),
+            }
+        }
+
+        // escrowed tokens can be taken out once no more votes are accepted
+        pub fn release_escrow(&mut self, amount: Decimal) -> Bucket {
+            assert!(
+                !matches!(self.get_status(), ProposalStatus::Pending | ProposalStatus::Active),
+                "voting tokens stay locked until voting on the proposal has ended"
+            );
+
+            self.escrow.take(amount)
+        }
+
+        pub fn get_escrowed_amount(&self) -> Decimal {
+            self.escrow.amount()
+        }
+
+        pub fn is_executable(&self) -> bool {
+            match (self.get_status(), self.queued_at) {
+                (ProposalStatus::Queued, Some(queued_at)) => {
+                    Self::now() as u64 >= queued_at + self.execution_delay
+                }
+                _ => false,
+            }
         }
 
     }
@@ -0,0 +1,250 @@
+use scrypto::prelude::*;
+
+const SECONDS_IN_WEEK: u64 = 7 * 24 * 60 * 60;
+const SECONDS_IN_MONTH: u64 = 30 * 24 * 60 * 60;
+
+#[derive(ScryptoSbor, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LadderInterval {
+    Weekly,
+    // 30 day months
+    Monthly,
+}
+
+impl LadderInterval {
+    pub fn seconds(&self) -> u64 {
+        match self {
+            LadderInterval::Weekly => SECONDS_IN_WEEK,
+            LadderInterval::Monthly => SECONDS_IN_MONTH,
+        }
+    }
+}
+
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub enum CashFlowKind {
+    BondMaturity,
+    SinkingFundDeposit,
+    WithdrawalRequest,
+    PaymentStream,
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct CashFlow {
+    // flows dated in the past are counted in the first rung
+    pub date: u64,
+    // positive for inflows, negative for outflows
+    pub amount: Decimal,
+    pub kind: CashFlowKind,
+}
+
+// a recurring payment the DAO has committed to, used for projections
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct PaymentStream {
+    pub recipient: ComponentAddress,
+    pub amount_per_period: Decimal,
+    pub period: u64,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub description: String,
+}
+
+impl PaymentStream {
+    // payment dates in [from, until)
+    pub fn payment_dates(&self, from: u64, until: u64) -> Vec<u64> {
+        let mut dates = Vec::new();
+        let mut date = self.start_time;
+
+        while date < until && date <= self.end_time {
+            if date >= from {
+                dates.push(date);
+            }
+            date += self.period;
+        }
+
+        dates
+    }
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct LadderRung {
+    pub start_time: u64,
+    pub end_time: u64,
+    pub inflows: Decimal,
+    pub outflows: Decimal,
+    pub net_flow: Decimal,
+    pub closing_balance: Decimal,
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct LiquidityLadder {
+    pub interval: LadderInterval,
+    pub opening_balance: Decimal,
+    pub rungs: Vec<LadderRung>,
+    pub lowest_balance: Decimal,
+    // flows beyond the last rung, not part of the ladder
+    pub flows_beyond_horizon: Decimal,
+}
+
+impl LiquidityLadder {
+    pub fn build(
+        opening_balance: Decimal,
+        flows: &[CashFlow],
+        interval: LadderInterval,
+        number_of_rungs: u32,
+        start_time: u64,
+    ) -> Self {
+        let rung_length = interval.seconds();
+
+        let mut rungs: Vec<LadderRung> = (0..number_of_rungs as u64)
+            .map(|index| LadderRung {
+                start_time: start_time + index * rung_length,
+                end_time: start_time + (index + 1) * rung_length,
+                inflows: Decimal::zero(),
+                outflows: Decimal::zero(),
+                net_flow: Decimal::zero(),
+                closing_balance: Decimal::zero(),
+            })
+            .collect();
+
+        let mut flows_beyond_horizon = Decimal::zero();
+
+        for flow in flows {
+            let index = (flow.date.saturating_sub(start_time) / rung_length) as usize;
+
+            match rungs.get_mut(index) {
+                Some(rung) => {
+                    if flow.amount >= Decimal::zero() {
+                        rung.inflows += flow.amount;
+                    } else {
+                        rung.outflows -= flow.amount;
+                    }
+                }
+                None => flows_beyond_horizon += flow.amount,
+            }
+        }
+
+        let mut balance = opening_balance;
+        let mut lowest_balance = opening_balance;
+
+        for rung in rungs.iter_mut() {
+            rung.net_flow = rung.inflows - rung.outflows;
+            balance += rung.net_flow;
+            rung.closing_balance = balance;
+            lowest_balance = lowest_balance.min(balance);
+        }
+
+        Self {
+            interval,
+            opening_balance,
+            rungs,
+            lowest_balance,
+            flows_beyond_horizon,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: u64 = 1_000_000;
+
+    fn flow(date: u64, amount: Decimal) -> CashFlow {
+        CashFlow {
+            date,
+            amount,
+            kind: CashFlowKind::BondMaturity,
+        }
+    }
+
+    fn stream(start_time: u64, end_time: u64) -> PaymentStream {
+        PaymentStream {
+            recipient: ComponentAddress::new_or_panic([EntityType::GlobalGenericComponent as u8; NodeId::LENGTH]),
+            amount_per_period: dec!("10"),
+            period: 100,
+            start_time,
+            end_time,
+            description: "salary".to_string(),
+        }
+    }
+
+    #[test]
+    fn flows_are_placed_in_the_rung_they_fall_in() {
+        let flows = [
+            flow(START, dec!("50")),
+            flow(START + SECONDS_IN_WEEK - 1, dec!("-20")),
+            flow(START + SECONDS_IN_WEEK, dec!("-70")),
+        ];
+
+        let ladder = LiquidityLadder::build(dec!("100"), &flows, LadderInterval::Weekly, 2, START);
+
+        assert_eq!(ladder.rungs[0].inflows, dec!("50"));
+        assert_eq!(ladder.rungs[0].outflows, dec!("20"));
+        assert_eq!(ladder.rungs[0].closing_balance, dec!("130"));
+        assert_eq!(ladder.rungs[1].outflows, dec!("70"));
+        assert_eq!(ladder.rungs[1].closing_balance, dec!("60"));
+        assert_eq!(ladder.lowest_balance, dec!("60"));
+        assert_eq!(ladder.flows_beyond_horizon, Decimal::zero());
+    }
+
+    #[test]
+    fn flows_before_the_start_count_in_the_first_rung() {
+        let ladder = LiquidityLadder::build(dec!("100"), &[flow(START - 1, dec!("-30"))], LadderInterval::Weekly, 2, START);
+
+        assert_eq!(ladder.rungs[0].outflows, dec!("30"));
+        assert_eq!(ladder.lowest_balance, dec!("70"));
+    }
+
+    #[test]
+    fn flows_from_the_end_of_the_last_rung_are_beyond_the_horizon() {
+        let horizon = START + 2 * SECONDS_IN_MONTH;
+        let flows = [flow(horizon - 1, dec!("-10")), flow(horizon, dec!("-500")), flow(horizon + 1, dec!("40"))];
+
+        let ladder = LiquidityLadder::build(dec!("100"), &flows, LadderInterval::Monthly, 2, START);
+
+        assert_eq!(ladder.rungs[1].outflows, dec!("10"));
+        assert_eq!(ladder.flows_beyond_horizon, dec!("-460"));
+        // the outflow past the horizon does not drag the ladder down
+        assert_eq!(ladder.lowest_balance, dec!("90"));
+    }
+
+    #[test]
+    fn the_lowest_balance_is_never_above_the_opening_balance() {
+        let ladder = LiquidityLadder::build(dec!("100"), &[flow(START, dec!("25"))], LadderInterval::Weekly, 1, START);
+
+        assert_eq!(ladder.rungs[0].closing_balance, dec!("125"));
+        assert_eq!(ladder.lowest_balance, dec!("100"));
+    }
+
+    #[test]
+    fn a_ladder_without_rungs_puts_every_flow_beyond_the_horizon() {
+        let ladder = LiquidityLadder::build(dec!("100"), &[flow(START, dec!("-30"))], LadderInterval::Weekly, 0, START);
+
+        assert!(ladder.rungs.is_empty());
+        assert_eq!(ladder.flows_beyond_horizon, dec!("-30"));
+    }
+
+    #[test]
+    fn payment_dates_include_the_window_start_but_not_its_end() {
+        assert_eq!(stream(START, START + 1_000).payment_dates(START + 100, START + 300), vec![START + 100, START + 200]);
+    }
+
+    #[test]
+    fn payment_dates_include_the_stream_end() {
+        assert_eq!(stream(START, START + 200).payment_dates(START, START + 1_000), vec![START, START + 100, START + 200]);
+    }
+
+    #[test]
+    fn payment_dates_skip_a_window_before_the_stream() {
+        assert!(stream(START, START + 200).payment_dates(0, START).is_empty());
+    }
+
+    #[test]
+    fn payment_dates_skip_a_window_after_the_stream() {
+        assert!(stream(START, START + 200).payment_dates(START + 201, START + 1_000).is_empty());
+    }
+
+    #[test]
+    fn payment_dates_between_payments_are_empty() {
+        assert!(stream(START, START + 1_000).payment_dates(START + 101, START + 200).is_empty());
+    }
+}
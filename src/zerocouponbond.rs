@@ -1,4 +1,87 @@
     use scrypto::prelude::*;
+    use crate::events::*;
+    use actus_schedule as schedule;
+
+    // performance state of a bond issue
+    // Performing -> (maturity) Delayed -> (grace) Delinquent -> (delinquency) Default
+    // Repaid once the issuer has deposited principal plus interest, Closed once it is settled
+    #[derive(ScryptoSbor, Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum BondStatus {
+        Performing,
+        Delayed,
+        Delinquent,
+        Default,
+        Repaid,
+        Closed,
+    }
+
+    // a deposit the issuer has to make into the bond before `due_date`
+    #[derive(ScryptoSbor, Clone, Debug)]
+    pub struct SinkingFundInstalment {
+        pub due_date: u64,
+        pub amount: Decimal,
+    }
+
+    // issuer may redeem every outstanding bond at `call_price` once `lockout_date` has passed
+    #[derive(ScryptoSbor, Clone, Debug)]
+    pub struct CallOption {
+        pub call_price: Decimal,
+        pub lockout_date: u64,
+    }
+
+    // holders may hand bonds back at `put_price` during `exercise_window` seconds from each put date
+    #[derive(ScryptoSbor, Clone, Debug)]
+    pub struct PutOption {
+        pub put_price: Decimal,
+        pub put_dates: Vec<u64>,
+        pub exercise_window: u64,
+    }
+
+    // holders may swap each bond for `conversion_ratio` governance tokens until `conversion_deadline`
+    #[derive(ScryptoSbor, Clone, Debug)]
+    pub struct ConversionTerms {
+        pub conversion_ratio: Decimal,
+        pub conversion_deadline: u64,
+    }
+
+    // when a late repayment turns delinquent and then into a default, and the deposits due before maturity
+    #[derive(ScryptoSbor, Clone, Debug)]
+    pub struct RepaymentTerms {
+        pub grace_period: u64,
+        pub delinquency_period: u64,
+        pub sinking_fund_schedule: Vec<SinkingFundInstalment>,
+    }
+
+    // early redemption terms, either side may be left out
+    #[derive(ScryptoSbor, Clone, Debug)]
+    pub struct CallPutTerms {
+        pub call_option: Option<CallOption>,
+        pub put_option: Option<PutOption>,
+    }
+
+    #[derive(ScryptoSbor, Clone, Debug)]
+    pub struct ConversionStats {
+        pub conversion_resource_address: ResourceAddress,
+        pub conversion_ratio: Decimal,
+        pub conversion_deadline: u64,
+        pub bonds_converted: Decimal,
+        pub tokens_delivered: Decimal,
+        // tokens still held by the bond for future conversions
+        pub tokens_remaining: Decimal,
+    }
+
+    #[derive(ScryptoSbor, Debug)]
+    pub struct SinkingFundStatus {
+        pub schedule: Vec<SinkingFundInstalment>,
+        pub deposited_by_issuer: Decimal,
+        // sum of all instalments that are due by now
+        pub required_to_date: Decimal,
+        // deposited / required to date, None while nothing is due yet
+        pub coverage_ratio: Option<Decimal>,
+        // indexes into `schedule`
+        pub missed_instalments: Vec<usize>,
+        pub next_instalment: Option<SinkingFundInstalment>,
+    }
 
     #[derive(ScryptoSbor, Debug)]
     pub struct BondDetails {
@@ -15,12 +98,80 @@
         pub price: u64,
         pub amount: Decimal,
         pub maturity_days_left: i64,
-       
+        pub status: BondStatus,
+        pub grace_period: u64,
+        pub delinquency_period: u64,
+        pub repaid_at: Option<u64>,
+        pub issuer_badge_id: NonFungibleLocalId,
+        pub sinking_fund_schedule: Vec<SinkingFundInstalment>,
+        pub deposited_by_issuer: Decimal,
+        pub number_of_bonds: Decimal,
+        pub outstanding_bonds: Decimal,
+        pub call_option: Option<CallOption>,
+        pub put_option: Option<PutOption>,
+        pub called_at: Option<u64>,
+        pub missed_put_date: Option<u64>,
+        pub conversion_terms: Option<ConversionTerms>,
+        pub bonds_converted: Decimal,
     }
 
     #[blueprint]
+    #[events(PandaoEvent, DaoEvent, DaoType, EventType)]
     mod zerocouponbond {
 
+        enable_method_auth! {
+            roles {
+                issuer => updatable_by: [];
+                dao => updatable_by: [];
+            },
+
+            methods {
+                get_resource_address => PUBLIC;
+                check_the_maturity_of_bonds => PUBLIC;
+                get_bond_details => PUBLIC;
+                get_status => PUBLIC;
+                get_recorded_status => PUBLIC;
+                get_collateral_amount => PUBLIC;
+                get_maturity_data => PUBLIC;
+                get_money_claim_status => PUBLIC;
+                is_redeemable => PUBLIC;
+                get_outstanding_bonds => PUBLIC;
+                get_conversion_stats => PUBLIC;
+                get_resource_address_of_collateral => PUBLIC;
+                balance_required_by_the_community => PUBLIC;
+                get_amount_owed_by_issuer => PUBLIC;
+                bond_creator_money_status => PUBLIC;
+                get_sinking_fund_status => PUBLIC;
+                check_the_balance_of_bond_issuer => PUBLIC;
+                get_bond_u_id => PUBLIC;
+                refresh_status => PUBLIC;
+
+                // holder operations
+                purchase_bond => PUBLIC;
+                sell_the_bond => PUBLIC;
+                redeem_called_bond => PUBLIC;
+                exercise_put => PUBLIC;
+                convert => PUBLIC;
+
+                // issuer operations, the DAO may act for the issuer
+                get_back_the_collateral => restrict_to: [issuer, dao];
+                take_out_the_invested_XRDs_by_the_community => restrict_to: [issuer, dao];
+                put_in_money_plus_interest_for_the_community_to_redeem => restrict_to: [issuer, dao];
+                deposit_to_sinking_fund => restrict_to: [issuer, dao];
+                exercise_call => restrict_to: [issuer, dao];
+                take_back_conversion_tokens => restrict_to: [issuer, dao];
+
+                // funding DAO operations
+                liquidate_collateral => restrict_to: [dao];
+                liquidate_collateral_on_margin_call => restrict_to: [dao];
+                claim_the_invested_XRDs_plus_interest => restrict_to: [dao];
+                change_community_claim_status => restrict_to: [dao];
+                report_missed_instalments => restrict_to: [dao];
+                deposit_recovery_proceeds => restrict_to: [dao];
+                force_transfer_deposited_xrds => restrict_to: [dao];
+            }
+        }
+
         struct ZeroCouponBond {
             contract_type: String,
             contract_role: String,
@@ -38,7 +189,36 @@
             pub bond_resourse_address : ResourceAddress,
             pub collateral : Vault,
             pub money_taken_by_creator : bool,
-            pub successful_claim_by_community : bool
+            pub successful_claim_by_community : bool,
+            pub status : BondStatus,
+            pub grace_period : u64,
+            pub delinquency_period : u64,
+            pub repaid_at : Option<u64>,
+            pub issuer_badge_id : NonFungibleLocalId,
+            pub sinking_fund_schedule : Vec<SinkingFundInstalment>,
+            // everything the issuer has paid back so far, sinking fund and final repayment
+            pub deposited_by_issuer : Decimal,
+            // part of the issuer's deposits already paid out to holders through puts
+            pub paid_from_issuer_deposits : Decimal,
+            // number of missed instalments that were already reported
+            pub missed_instalments_reported : usize,
+            pub number_of_bonds : Decimal,
+            pub call_option : Option<CallOption>,
+            pub put_option : Option<PutOption>,
+            // set once the issuer has called the bonds, holders are then paid out of `redemption_xrd`
+            pub called_at : Option<u64>,
+            pub redemption_xrd : Vault,
+            // put date on which a holder could not be paid, counts as a missed payment until a put is paid again
+            pub missed_put_date : Option<u64>,
+            pub conversion_terms : Option<ConversionTerms>,
+            // governance tokens set aside for conversion, only for convertible bonds
+            pub conversion_tokens : Option<Vault>,
+            pub bonds_converted : Decimal,
+            pub tokens_delivered : Decimal,
+            // called bonds handed back and burnt
+            pub bonds_redeemed_on_call : Decimal,
+            // repaid bonds handed back and burnt
+            pub bonds_redeemed_at_maturity : Decimal
         }
 
         impl ZeroCouponBond {
@@ -67,22 +247,122 @@
                 price: u64,                 // price per bond
                 number_of_bonds: Decimal,       // number of bonds to mint
                 nft_as_collateral: Bucket,      // collateral for bonds
+                repayment_terms: RepaymentTerms, // grace and delinquency periods, sinking fund schedule
+                issuer_badge_id: NonFungibleLocalId, // issuer badge the bond is bound to
+                call_put_terms: CallPutTerms,    // issuer call and holder put terms
+                conversion: Option<(ConversionTerms, Bucket)>, // convertible bond terms and the governance tokens backing them
+                issuer_address: ComponentAddress,          // account of the issuer, shown in the term sheet
+                issuer_badge: NonFungibleGlobalId,         // badge that holds the issuer role
+                dao_address: ComponentAddress,             // DAO funding the bond, holds the dao role
             ) -> Global<ZeroCouponBond> {
+                let RepaymentTerms {
+                    grace_period,
+                    delinquency_period,
+                    sinking_fund_schedule,
+                } = repayment_terms;
+                let CallPutTerms { call_option, put_option } = call_put_terms;
+
+                let mut previous_due_date = initial_exchange_date;
+                for instalment in &sinking_fund_schedule {
+                    assert!(
+                        instalment.due_date >= previous_due_date && instalment.due_date <= maturity_date,
+                        "sinking fund instalments must be in order and due before maturity"
+                    );
+                    assert!(instalment.amount > Decimal::zero(), "sinking fund instalment must be greater than zero");
+                    previous_due_date = instalment.due_date;
+                }
+
+                if let Some(call_option) = &call_option {
+                    assert!(call_option.call_price > Decimal::zero(), "call price must be greater than zero");
+                    assert!(
+                        call_option.lockout_date >= initial_exchange_date && call_option.lockout_date <= maturity_date,
+                        "call lockout date must be between the initial exchange date and maturity"
+                    );
+                }
+
+                if let Some(put_option) = &put_option {
+                    assert!(put_option.put_price > Decimal::zero(), "put price must be greater than zero");
+                    assert!(put_option.exercise_window > 0, "put exercise window must be greater than zero");
+                    assert!(!put_option.put_dates.is_empty(), "put option needs at least one put date");
+
+                    let mut previous_put_date = initial_exchange_date;
+                    for put_date in &put_option.put_dates {
+                        assert!(
+                            *put_date >= previous_put_date && *put_date <= maturity_date,
+                            "put dates must be in order and before maturity"
+                        );
+                        previous_put_date = *put_date;
+                    }
+                }
+
+                let (conversion_terms, conversion_tokens) = match conversion {
+                    Some((terms, tokens)) => {
+                        assert!(terms.conversion_ratio > Decimal::zero(), "conversion ratio must be greater than zero");
+                        assert!(
+                            terms.conversion_deadline >= initial_exchange_date && terms.conversion_deadline <= maturity_date,
+                            "conversion deadline must be between the initial exchange date and maturity"
+                        );
+                        assert!(
+                            tokens.amount() >= terms.conversion_ratio * number_of_bonds,
+                            "not enough tokens to convert every bond"
+                        );
+                        (Some(terms), Some(Vault::with_bucket(tokens)))
+                    }
+                    None => (None, None),
+                };
+
+                // reserve the component address so the bond can burn converted bonds
+                let (address_reservation, component_address) =
+                    Runtime::allocate_component_address(ZeroCouponBond::blueprint_id());
+
+                // term sheet shown by wallets, only the status changes over the life of the bond
+                let name = format!("ZeroCouponBond {}", contract_identifier);
+                let description = format!(
+                    "Zero coupon bond {} paying {}% in {}, maturing at {}",
+                    contract_identifier, nominal_interest_rate, currency, maturity_date
+                );
+                let collateral_resource_address = nft_as_collateral.resource_address();
+                let initial_status = format!("{:?}", BondStatus::Performing);
+
                 let bucket_of_bonds: Bucket = ResourceBuilder::new_fungible(OwnerRole::None)
                     .divisibility(DIVISIBILITY_NONE)
                     .metadata(metadata!(
+                        roles {
+                            metadata_setter => rule!(require(global_caller(component_address)));
+                            metadata_setter_updater => rule!(deny_all);
+                            metadata_locker => rule!(deny_all);
+                            metadata_locker_updater => rule!(deny_all);
+                        },
                         init {
-                            "name" => "ZeroCouponBond", locked;
+                            "name" => name.clone(), locked;
                             "symbol" => "ZCB", locked;
-                            "description" => "A Zero Coupon Bond", locked;
+                            "description" => description.clone(), locked;
+                            "issuer" => GlobalAddress::from(issuer_address), locked;
+                            "contract_identifier" => contract_identifier.clone(), locked;
+                            "currency" => currency.clone(), locked;
+                            "face_value" => notional_principal, locked;
+                            "interest_rate" => nominal_interest_rate, locked;
+                            "issue_date" => Instant::new(initial_exchange_date as i64), locked;
+                            "maturity_date" => Instant::new(maturity_date as i64), locked;
+                            "collateral_resource" => GlobalAddress::from(collateral_resource_address), locked;
+                            "status" => initial_status.clone(), updatable;
                         }
                     ))  
+                    .burn_roles(burn_roles! {
+                        burner => rule!(require(global_caller(component_address)));
+                        burner_updater => rule!(deny_all);
+                    })
                     .mint_initial_supply(number_of_bonds)
                     .into();
 
                 let bond_resourse_address = bucket_of_bonds.resource_address();
 
-                Self {
+                let contract_identifier_for_metadata = contract_identifier.clone();
+                let contract_identifier_for_event = contract_identifier.clone();
+                let collateral_amount = nft_as_collateral.amount();
+                let currency_for_metadata = currency.clone();
+
+                let component = Self {
                     contract_type,
                     contract_role,
                     contract_identifier,
@@ -99,22 +379,145 @@
                     bond_resourse_address,
                     collateral: Vault::with_bucket(nft_as_collateral),
                     money_taken_by_creator : false,
-                    successful_claim_by_community : false
+                    successful_claim_by_community : false,
+                    status : BondStatus::Performing,
+                    grace_period,
+                    delinquency_period,
+                    repaid_at : None,
+                    issuer_badge_id,
+                    sinking_fund_schedule,
+                    deposited_by_issuer : Decimal::zero(),
+                    paid_from_issuer_deposits : Decimal::zero(),
+                    missed_instalments_reported : 0,
+                    number_of_bonds,
+                    call_option,
+                    put_option,
+                    called_at : None,
+                    redemption_xrd : Vault::new(XRD),
+                    missed_put_date : None,
+                    conversion_terms,
+                    conversion_tokens,
+                    bonds_converted : Decimal::zero(),
+                    tokens_delivered : Decimal::zero(),
+                    bonds_redeemed_on_call : Decimal::zero(),
+                    bonds_redeemed_at_maturity : Decimal::zero()
                 }
                 .instantiate()
                 .prepare_to_globalize(OwnerRole::None)
-                .globalize()
+                .roles(roles!(
+                    issuer => rule!(require(issuer_badge));
+                    dao => rule!(require(global_caller(dao_address)));
+                ))
+                .metadata(metadata!(
+                    roles {
+                        metadata_setter => rule!(require(global_caller(component_address)));
+                        metadata_setter_updater => rule!(deny_all);
+                        metadata_locker => rule!(deny_all);
+                        metadata_locker_updater => rule!(deny_all);
+                    },
+                    init {
+                        "name" => name, locked;
+                        "description" => description, locked;
+                        "issuer" => GlobalAddress::from(issuer_address), locked;
+                        "contract_identifier" => contract_identifier_for_metadata, locked;
+                        "currency" => currency_for_metadata, locked;
+                        "face_value" => notional_principal, locked;
+                        "interest_rate" => nominal_interest_rate, locked;
+                        "issue_date" => Instant::new(initial_exchange_date as i64), locked;
+                        "maturity_date" => Instant::new(maturity_date as i64), locked;
+                        "collateral_resource" => GlobalAddress::from(collateral_resource_address), locked;
+                        "bond_resource" => GlobalAddress::from(bond_resourse_address), locked;
+                        "status" => initial_status, updatable;
+                    }
+                ))
+                .with_address(address_reservation)
+                .globalize();
+
+                let event_metadata = BondIssued {
+                    bond_component_address: component_address,
+                    bond_resource_address: bond_resourse_address,
+                    issuer_address,
+                    contract_identifier: contract_identifier_for_event,
+                    number_of_bonds,
+                    price,
+                    maturity_date,
+                    collateral_resource_address,
+                    collateral_amount,
+                };
+
+                Runtime::emit_event(PandaoEvent {
+                    event_type: EventType::BOND_ISSUED,
+                    dao_type: DaoType::Investment,
+                    component_address,
+                    meta_data: DaoEvent::BondIssued(event_metadata),
+                });
+
+                component
+            }
+
+            fn emit_bond_event(event_type: EventType, meta_data: DaoEvent) {
+                Runtime::emit_event(PandaoEvent {
+                    event_type,
+                    dao_type: DaoType::Investment,
+                    component_address: Runtime::global_address(),
+                    meta_data,
+                });
+            }
+
+            fn emit_redeemed(&self, redemption_kind: BondRedemptionKind, bonds_redeemed: Decimal, payout: &Bucket) {
+                Self::emit_bond_event(
+                    EventType::BOND_REDEEMED,
+                    DaoEvent::BondRedeemed(BondRedeemed {
+                        bond_component_address: Runtime::global_address(),
+                        redemption_kind,
+                        bonds_redeemed,
+                        amount_paid: payout.amount(),
+                        paid_in: payout.resource_address(),
+                    }),
+                );
+            }
 
+            fn emit_collateral_movement(&self, event_type: EventType, collateral: &Bucket) {
+                let event_metadata = BondCollateralMovement {
+                    bond_component_address: Runtime::global_address(),
+                    collateral_resource_address: collateral.resource_address(),
+                    amount: collateral.amount(),
+                    bond_status: self.status,
+                };
+
+                let meta_data = match event_type {
+                    EventType::BOND_COLLATERAL_SEIZED => DaoEvent::BondCollateralSeized(event_metadata),
+                    _ => DaoEvent::BondCollateralReturned(event_metadata),
+                };
+
+                Self::emit_bond_event(event_type, meta_data);
             }
 
             pub fn get_resource_address(&self) -> ResourceAddress{
-                return self.bond_resourse_address;
+                self.bond_resourse_address
             }
 
             pub fn purchase_bond(&mut self, mut payment: Bucket) -> (Bucket, Bucket) {
+                assert!(self.called_at.is_none(), "bond has been called and is no longer for sale");
+                assert!(self.status != BondStatus::Closed, "bond is closed and is no longer for sale");
+
                 let our_share = payment.take(self.price);
+                let amount_paid = our_share.amount();
                 self.collected_xrd.put(our_share);
-                (self.bonds.take(1), payment)
+
+                let bond = self.bonds.take(1);
+
+                Self::emit_bond_event(
+                    EventType::BOND_PURCHASED,
+                    DaoEvent::BondPurchased(BondPurchased {
+                        bond_component_address: Runtime::global_address(),
+                        bonds_purchased: bond.amount(),
+                        amount_paid,
+                        bonds_left: self.bonds.amount(),
+                    }),
+                );
+
+                (bond, payment)
             }
 
             pub fn sell_the_bond(&mut self, bond: Bucket) -> Bucket {
@@ -127,21 +530,32 @@
                     "Invalid bond resource."
                 );
 
-                // Bond should only be sold after the maturity period
-                // Appllllllllly the condition
+                // Bond should only be sold after the maturity period, once the issuer has repaid it
+                assert!(
+                    Self::current_time_seconds() >= self.maturity_date,
+                    "bonds can only be sold back after maturity"
+                );
+                assert!(self.is_redeemable(), "bonds can only be sold back once the issuer has repaid them");
 
+                // sold bonds are settled for good, they are burnt rather than put back up for sale
+                bond.burn();
+                self.bonds_redeemed_at_maturity += Decimal::one();
 
-                self.bonds.put(bond);
                 let refund_amount = self.notional_principal + Decimal::from(self.discount);
-                self.collected_xrd.take(refund_amount)
+                let refund = self.collected_xrd.take(refund_amount);
+
+                self.paid_from_issuer_deposits += refund.amount();
+
+                self.emit_redeemed(BondRedemptionKind::Sale, Decimal::one(), &refund);
+
+                refund
             }
 
             pub fn check_the_maturity_of_bonds(&self) -> i64 {
                 
                 let current_epoch = Runtime::current_epoch().number();
                 let seconds_in_day = 24 * 60 * 60;
-                let days_left = (self.maturity_date as i64 - current_epoch as i64) / seconds_in_day;
-                days_left
+                (self.maturity_date as i64 - current_epoch as i64) / seconds_in_day
             }
 
             pub fn get_bond_details(&self) -> BondDetails {
@@ -163,6 +577,137 @@
                     price: self.price,
                     amount: self.bonds.amount(),
                     maturity_days_left: days_left,
+                    status: self.get_status(),
+                    grace_period: self.grace_period,
+                    delinquency_period: self.delinquency_period,
+                    repaid_at: self.repaid_at,
+                    issuer_badge_id: self.issuer_badge_id.clone(),
+                    sinking_fund_schedule: self.sinking_fund_schedule.clone(),
+                    deposited_by_issuer: self.deposited_by_issuer,
+                    number_of_bonds: self.number_of_bonds,
+                    outstanding_bonds: self.get_outstanding_bonds(),
+                    call_option: self.call_option.clone(),
+                    put_option: self.put_option.clone(),
+                    called_at: self.called_at,
+                    missed_put_date: self.missed_put_date,
+                    conversion_terms: self.conversion_terms.clone(),
+                    bonds_converted: self.bonds_converted,
+                }
+            }
+
+            fn current_time_seconds() -> u64 {
+                let now: Instant = Clock::current_time_rounded_to_seconds();
+                now.seconds_since_unix_epoch as u64
+            }
+
+            // indexes of the sinking fund instalments that are past due and not covered by deposits
+            fn missed_instalments(&self, current_time_seconds: u64) -> Vec<usize> {
+                let mut missed = Vec::new();
+                let mut cumulative_required = Decimal::zero();
+
+                for (index, instalment) in self.sinking_fund_schedule.iter().enumerate() {
+                    cumulative_required += instalment.amount;
+                    if instalment.due_date < current_time_seconds && self.deposited_by_issuer < cumulative_required {
+                        missed.push(index);
+                    }
+                }
+
+                missed
+            }
+
+            // the oldest payment the issuer owes and has not made: a missed instalment or the maturity repayment
+            fn earliest_missed_payment_date(&self, current_time_seconds: u64) -> Option<u64> {
+                let missed_instalment_date = self.missed_instalments(current_time_seconds)
+                    .first()
+                    .map(|index| self.sinking_fund_schedule[*index].due_date);
+
+                let missed_maturity_date = if current_time_seconds > self.maturity_date {
+                    Some(self.maturity_date)
+                } else {
+                    None
+                };
+
+                [missed_instalment_date, self.missed_put_date, missed_maturity_date]
+                    .into_iter()
+                    .flatten()
+                    .min()
+            }
+
+            // status as it should be right now, without writing it
+            pub fn get_status(&self) -> BondStatus {
+                match self.status {
+                    BondStatus::Repaid | BondStatus::Closed | BondStatus::Default => self.status,
+                    _ => {
+                        let current_time_seconds = Self::current_time_seconds();
+
+                        let missed_payment_date = match self.earliest_missed_payment_date(current_time_seconds) {
+                            Some(missed_payment_date) => missed_payment_date,
+                            None => return BondStatus::Performing,
+                        };
+
+                        let delayed_until = missed_payment_date + self.grace_period;
+                        let delinquent_until = delayed_until + self.delinquency_period;
+
+                        if current_time_seconds <= delayed_until {
+                            BondStatus::Delayed
+                        } else if current_time_seconds <= delinquent_until {
+                            BondStatus::Delinquent
+                        } else {
+                            BondStatus::Default
+                        }
+                    }
+                }
+            }
+
+            // status as last written, may lag behind ledger time until refreshed
+            pub fn get_recorded_status(&self) -> BondStatus {
+                self.status
+            }
+
+            // moves the stored status along with ledger time and returns the new status
+            pub fn refresh_status(&mut self) -> BondStatus {
+                let status = self.get_status();
+                self.set_status(status);
+                self.status
+            }
+
+            // keeps the status in the resource and component metadata in line with the stored status
+            fn set_status(&mut self, status: BondStatus) {
+                if self.status == status {
+                    return;
+                }
+
+                let previous_status = self.status;
+                self.status = status;
+
+                let status_text = format!("{:?}", status);
+
+                ResourceManager::from(self.bond_resourse_address).set_metadata("status", status_text.clone());
+                Runtime::global_component().set_metadata("status", status_text);
+
+                let bond_component_address = Runtime::global_address();
+
+                Self::emit_bond_event(
+                    EventType::BOND_STATUS_CHANGED,
+                    DaoEvent::BondStatusChanged(BondStatusChanged {
+                        bond_component_address,
+                        previous_status,
+                        current_status: status,
+                    }),
+                );
+
+                if status == BondStatus::Repaid {
+                    let repaid_at = self.repaid_at.unwrap_or_else(Self::current_time_seconds);
+
+                    Self::emit_bond_event(
+                        EventType::BOND_REPAID,
+                        DaoEvent::BondRepaid(BondRepaid {
+                            bond_component_address,
+                            deposited_by_issuer: self.deposited_by_issuer,
+                            repaid_at,
+                            repaid_on_time: repaid_at <= self.maturity_date,
+                        }),
+                    );
                 }
             }
 
@@ -170,13 +715,38 @@
             // ONLY COMMUNITY CAN CALL THIS
             pub fn liquidate_collateral(&mut self) -> Bucket {
 
-                // AFTER MATURITY DATE
-                let now: Instant = Clock::current_time_rounded_to_seconds();
-                let current_time_seconds: u64 = now.seconds_since_unix_epoch as u64;
-                //CHECK IF MATURITY DATE PASSED
-                assert!(self.maturity_date < current_time_seconds, "you cannot redeem the collateral because maturity date is not passed yet");
+                // ONLY ONCE THE BOND IS IN DEFAULT
+                assert!(self.refresh_status() == BondStatus::Default, "you cannot redeem the collateral because the bond is not in default");
                 
-                self.collateral.take(1)
+                let seized_collateral = self.collateral.take_all();
+
+                self.emit_collateral_movement(EventType::BOND_COLLATERAL_SEIZED, &seized_collateral);
+
+                seized_collateral
+            }
+
+            // LIQUIDATE COLLATERAL BEFORE MATURITY
+            // ONLY COMMUNITY CAN CALL THIS, AFTER AN UNCURED MARGIN CALL
+            pub fn liquidate_collateral_on_margin_call(&mut self) -> Bucket {
+
+                let status = self.refresh_status();
+
+                assert!(
+                    status != BondStatus::Default && status != BondStatus::Repaid && status != BondStatus::Closed,
+                    "collateral can only be liquidated on a margin call while the bond is still owed"
+                );
+
+                self.set_status(BondStatus::Default);
+
+                let seized_collateral = self.collateral.take_all();
+
+                self.emit_collateral_movement(EventType::BOND_COLLATERAL_SEIZED, &seized_collateral);
+
+                seized_collateral
+            }
+
+            pub fn get_collateral_amount(&self) -> Decimal {
+                self.collateral.amount()
             }
 
             pub fn get_maturity_data(&self) -> u64{
@@ -185,58 +755,348 @@
 
             pub fn get_back_the_collateral(&mut self) -> Bucket {
                 
-                // if self.successful_claim_by_community == true{
-                    //now that community claimed their interest plus principal amount
-                    //now we can let the creator take back his collateral
-                    self.collateral.take(1)
-                // }else{
-                //     Bucket::new(self.collateral.resource_address())
-                // }
+                //only after the issuer has repaid principal plus interest
+                assert!(self.status == BondStatus::Repaid, "collateral can only be taken back once the bond is repaid");
+
+                self.set_status(BondStatus::Closed);
+
+                let collateral = self.collateral.take_all();
+
+                self.emit_collateral_movement(EventType::BOND_COLLATERAL_RETURNED, &collateral);
+
+                collateral
             }
 
             pub fn get_money_claim_status(&self) -> bool{
                 self.successful_claim_by_community
             }
 
+            // bonds that are held outside this component and were not converted
+            pub fn get_outstanding_bonds(&self) -> Decimal {
+                self.number_of_bonds
+                    - self.bonds_converted
+                    - self.bonds_redeemed_on_call
+                    - self.bonds_redeemed_at_maturity
+                    - self.bonds.amount()
+            }
+
+            // ISSUER CALL
+            // issuer funds the redemption of every outstanding bond at the call price and gets the collateral back
+            pub fn exercise_call(&mut self, mut payment: Bucket) -> (Bucket, Bucket) {
+
+                let call_option = self.call_option.clone().expect("this bond has no call option");
+
+                assert!(self.called_at.is_none(), "bond has already been called");
+                assert_eq!(payment.resource_address(), XRD, "call must be paid in XRD");
+
+                let current_time_seconds = Self::current_time_seconds();
+
+                assert!(
+                    current_time_seconds >= call_option.lockout_date && current_time_seconds <= self.maturity_date,
+                    "bond can only be called between the lockout date and maturity"
+                );
+
+                let status = self.refresh_status();
+
+                assert!(
+                    status == BondStatus::Performing || status == BondStatus::Delayed,
+                    "bond can not be called once it is delinquent, in default, repaid or closed"
+                );
+
+                let required_amount = call_option.call_price * self.get_outstanding_bonds();
+
+                // sinking fund deposits not yet paid out go towards the call, so they are not left behind
+                let unspent_deposits = (self.deposited_by_issuer - self.paid_from_issuer_deposits)
+                    .min(self.collected_xrd.amount())
+                    .min(required_amount)
+                    .max(Decimal::zero());
+
+                let required_from_payment = required_amount - unspent_deposits;
+
+                assert!(payment.amount() >= required_from_payment, "you are paying an insufficient amount to call the bond");
+
+                self.paid_from_issuer_deposits += unspent_deposits;
+                self.redemption_xrd.put(self.collected_xrd.take(unspent_deposits));
+
+                self.deposited_by_issuer += required_from_payment;
+                self.redemption_xrd.put(payment.take(required_from_payment));
+
+                self.called_at = Some(current_time_seconds);
+                self.repaid_at = Some(current_time_seconds);
+
+                // the call settles the bond in full, so the collateral goes straight back to the issuer
+                self.set_status(BondStatus::Repaid);
+                self.set_status(BondStatus::Closed);
+
+                let collateral = self.collateral.take_all();
+
+                self.emit_collateral_movement(EventType::BOND_COLLATERAL_RETURNED, &collateral);
+
+                (payment, collateral)
+            }
+
+            // holder hands back called bonds and is paid the call price out of the funded redemption vault
+            pub fn redeem_called_bond(&mut self, bond: Bucket) -> Bucket {
+
+                let call_option = self.call_option.clone().expect("this bond has no call option");
+
+                assert!(self.called_at.is_some(), "bond has not been called");
+                assert!(
+                    bond.resource_address() == self.bonds.resource_address(),
+                    "Invalid bond resource."
+                );
+
+                let bonds_redeemed = bond.amount();
+                let amount_to_pay = call_option.call_price * bonds_redeemed;
+
+                // called bonds are settled for good, they are burnt rather than put back up for sale
+                bond.burn();
+                self.bonds_redeemed_on_call += bonds_redeemed;
+
+                let payout = self.redemption_xrd.take(amount_to_pay);
+
+                self.emit_redeemed(BondRedemptionKind::Call, bonds_redeemed, &payout);
+
+                payout
+            }
+
+            // put date whose exercise window contains `current_time_seconds`
+            fn open_put_date(&self, current_time_seconds: u64) -> Option<u64> {
+                let put_option = self.put_option.as_ref()?;
+
+                put_option.put_dates
+                    .iter()
+                    .find(|put_date| {
+                        current_time_seconds >= **put_date
+                            && current_time_seconds <= **put_date + put_option.exercise_window
+                    })
+                    .copied()
+            }
+
+            // HOLDER PUT
+            // bonds are paid at the put price out of the issuer's deposits
+            // if the deposits do not cover it the bonds are handed back, the collateral stays and the put counts as missed
+            pub fn exercise_put(&mut self, bond: Bucket) -> (Bucket, Bucket) {
+
+                let put_option = self.put_option.clone().expect("this bond has no put option");
+
+                assert!(self.called_at.is_none(), "bond has been called, redeem it at the call price instead");
+                assert!(
+                    bond.resource_address() == self.bonds.resource_address(),
+                    "Invalid bond resource."
+                );
+
+                let current_time_seconds = Self::current_time_seconds();
+
+                let put_date = self.open_put_date(current_time_seconds)
+                    .expect("there is no open put window right now");
+
+                let status = self.refresh_status();
+
+                assert!(
+                    status != BondStatus::Default && status != BondStatus::Closed,
+                    "bond can not be put once it is in default or closed"
+                );
+
+                let amount_to_pay = put_option.put_price * bond.amount();
+
+                // holders' purchase money in the vault does not pay for puts
+                let unspent_deposits = (self.deposited_by_issuer - self.paid_from_issuer_deposits)
+                    .min(self.collected_xrd.amount());
+
+                if unspent_deposits < amount_to_pay {
+                    if self.missed_put_date.is_none() {
+                        self.missed_put_date = Some(put_date);
+                    }
+                    self.refresh_status();
+
+                    return (Bucket::new(XRD), bond);
+                }
+
+                self.missed_put_date = None;
+
+                let bonds_redeemed = bond.amount();
+                self.bonds.put(bond);
+
+                let payout = self.collected_xrd.take(amount_to_pay);
+
+                self.paid_from_issuer_deposits += payout.amount();
+
+                self.emit_redeemed(BondRedemptionKind::Put, bonds_redeemed, &payout);
+
+                // every bond has been put back, nothing is owed any more and the collateral can be taken back
+                if self.get_outstanding_bonds() == Decimal::zero() && self.status != BondStatus::Repaid {
+                    self.repaid_at = Some(current_time_seconds);
+                    self.set_status(BondStatus::Repaid);
+                } else {
+                    self.refresh_status();
+                }
+
+                (payout, Bucket::new(self.bonds.resource_address()))
+            }
+
+            // CONVERSION
+            // holder swaps bonds for governance tokens at the fixed ratio, the bonds are burnt
+            pub fn convert(&mut self, bond: Bucket) -> Bucket {
+
+                let conversion_terms = self.conversion_terms.clone().expect("this bond is not convertible");
+
+                assert!(
+                    bond.resource_address() == self.bonds.resource_address(),
+                    "Invalid bond resource."
+                );
+                assert!(!bond.is_empty(), "there are no bonds to convert");
+                assert!(self.called_at.is_none(), "bond has been called, redeem it at the call price instead");
+
+                let current_time_seconds = Self::current_time_seconds();
+
+                assert!(
+                    current_time_seconds <= conversion_terms.conversion_deadline,
+                    "conversion deadline has passed"
+                );
+                assert!(self.status != BondStatus::Closed, "bond is closed");
+
+                let bonds_to_convert = bond.amount();
+                let tokens_to_deliver = conversion_terms.conversion_ratio * bonds_to_convert;
+
+                bond.burn();
+
+                self.bonds_converted += bonds_to_convert;
+                self.tokens_delivered += tokens_to_deliver;
+
+                let tokens = self.conversion_tokens
+                    .as_mut()
+                    .expect("this bond is not convertible")
+                    .take(tokens_to_deliver);
+
+                self.emit_redeemed(BondRedemptionKind::Conversion, bonds_to_convert, &tokens);
+
+                // once every bond is converted or back with the bond, nothing is owed and the collateral can be taken back
+                if self.get_outstanding_bonds() == Decimal::zero()
+                    && self.status != BondStatus::Repaid
+                    && self.status != BondStatus::Default
+                {
+                    self.repaid_at = Some(current_time_seconds);
+                    self.set_status(BondStatus::Repaid);
+                }
+
+                tokens
+            }
+
+            pub fn get_conversion_stats(&self) -> Option<ConversionStats> {
+                let conversion_terms = self.conversion_terms.as_ref()?;
+                let conversion_tokens = self.conversion_tokens.as_ref()?;
+
+                Some(ConversionStats {
+                    conversion_resource_address: conversion_tokens.resource_address(),
+                    conversion_ratio: conversion_terms.conversion_ratio,
+                    conversion_deadline: conversion_terms.conversion_deadline,
+                    bonds_converted: self.bonds_converted,
+                    tokens_delivered: self.tokens_delivered,
+                    tokens_remaining: conversion_tokens.amount(),
+                })
+            }
+
+            // tokens nobody can convert any more go back to whoever funded the conversion
+            pub fn take_back_conversion_tokens(&mut self) -> Bucket {
+
+                let conversion_terms = self.conversion_terms.clone().expect("this bond is not convertible");
+
+                assert!(
+                    Self::current_time_seconds() > conversion_terms.conversion_deadline
+                        || self.called_at.is_some()
+                        || self.status == BondStatus::Closed,
+                    "conversion tokens can only be taken back once conversion has ended"
+                );
+
+                self.conversion_tokens
+                    .as_mut()
+                    .expect("this bond is not convertible")
+                    .take_all()
+            }
+
             pub fn get_resource_address_of_collateral(&self) -> ResourceAddress{
                 self.collateral.resource_address()
             }
 
             //GET BACK THE INVESTED XRD + INTEREST RATE
             //FOR A COMMUNITY
-            pub fn claim_the_invested_XRDs_plus_interest(&mut self) -> Bucket{
+            // repaid bonds, closed afterwards when the issuer took the collateral back, can be redeemed
+            // called bonds are redeemed at the call price and a bond closed after a default was never repaid
+            pub fn is_redeemable(&self) -> bool {
+                let status = self.get_status();
+
+                self.called_at.is_none()
+                    && self.repaid_at.is_some()
+                    && (status == BondStatus::Repaid || status == BondStatus::Closed)
+            }
 
-                let bond_price = self.price;
+            // holder hands back bonds and is paid principal plus interest out of the issuer's deposits
+            pub fn claim_the_invested_XRDs_plus_interest(&mut self, bond: Bucket) -> Bucket{
 
-                let interest_amount = (self.nominal_interest_rate/100)*bond_price;
+                assert!(
+                    bond.resource_address() == self.bonds.resource_address(),
+                    "Invalid bond resource."
+                );
+                assert!(self.is_redeemable(), "bonds can only be redeemed once the issuer has repaid them");
+
+                let bonds_redeemed = bond.amount();
+                let total_amount = bonds_redeemed
+                    * schedule::redemption_value(Decimal::from(self.price), self.nominal_interest_rate);
+
+                // redeemed bonds are settled for good, they are burnt so they can not be claimed again
+                bond.burn();
+                self.bonds_redeemed_at_maturity += bonds_redeemed;
+
+                let payout = self.collected_xrd.take(total_amount);
 
-                let total_amount = bond_price + interest_amount;
+                self.paid_from_issuer_deposits += payout.amount();
 
-                self.collected_xrd.take(total_amount)
+                self.emit_redeemed(BondRedemptionKind::Maturity, bonds_redeemed, &payout);
 
+                payout
             }
 
             //XRDs required by the community
             pub fn balance_required_by_the_community(&self) -> Decimal{
                 
-                let bond_price = self.price;
-
-                let interest_amount = (self.nominal_interest_rate/100)*bond_price;
+                schedule::redemption_value(Decimal::from(self.price), self.nominal_interest_rate)
+            }
 
-                let total_amount = bond_price + interest_amount;
+            // what the issuer still has to deposit so every outstanding bond can be redeemed,
+            // holders' purchase money in the same vault does not count towards it
+            pub fn get_amount_owed_by_issuer(&self) -> Decimal {
+                let owed = self.get_outstanding_bonds() * self.balance_required_by_the_community();
+                let covered = self.deposited_by_issuer - self.paid_from_issuer_deposits;
 
-                total_amount
+                if covered >= owed {
+                    Decimal::zero()
+                } else {
+                    owed - covered
+                }
             }
 
             pub fn change_community_claim_status(&mut self, value : bool){
                 self.successful_claim_by_community = value;
             }
 
+            // the issuer takes the holders' purchase money once, while the bond is performing
             pub fn take_out_the_invested_XRDs_by_the_community(&mut self)
             -> Bucket
             {
-                let bond_price = self.price;
-                self.collected_xrd.take(bond_price)
+                assert!(!self.money_taken_by_creator, "the invested XRDs have already been taken out");
+                assert!(
+                    self.refresh_status() == BondStatus::Performing,
+                    "the invested XRDs can only be taken out while the bond is performing"
+                );
+
+                self.money_taken_by_creator = true;
+
+                // the issuer's own deposits stay for the holders
+                let unspent_deposits = self.deposited_by_issuer - self.paid_from_issuer_deposits;
+                let purchase_money = (self.collected_xrd.amount() - unspent_deposits).max(Decimal::zero());
+
+                self.collected_xrd.take(purchase_money)
             }
 
             pub fn bond_creator_money_status(&self) -> bool{
@@ -246,47 +1106,152 @@
 
             pub fn put_in_money_plus_interest_for_the_community_to_redeem(&mut self, mut borrowed_xrd_with_interest : Bucket) -> Bucket {
 
-                let required_amount_by_the_community = self.balance_required_by_the_community();
+                let required_amount_by_the_community = self.get_amount_owed_by_issuer();
 
                 let resource_address_of_xrds = borrowed_xrd_with_interest.resource_address();
 
                 let amount_getting_deposited = borrowed_xrd_with_interest.amount();
 
-                if amount_getting_deposited >= required_amount_by_the_community{
+                let status = self.refresh_status();
+
+                assert!(
+                    status != BondStatus::Default && status != BondStatus::Closed,
+                    "bond can not be repaid once it is in default or closed"
+                );
+
+                let change = if amount_getting_deposited >= required_amount_by_the_community{
 
                     let taken_out_required_amount = borrowed_xrd_with_interest.take(required_amount_by_the_community);
 
+                    self.deposited_by_issuer += taken_out_required_amount.amount();
                     self.collected_xrd.put(taken_out_required_amount);
     
                     borrowed_xrd_with_interest
 
                 }else{
 
+                    self.deposited_by_issuer += amount_getting_deposited;
                     self.collected_xrd.put(borrowed_xrd_with_interest);
 
                     // this is an emtpy bucket 
                     Bucket::new(resource_address_of_xrds)
+                };
+
+                self.mark_repaid_if_covered();
+
+                change
+            }
+
+            fn mark_repaid_if_covered(&mut self) {
+                // a bond nobody holds owes nothing, selling out and redeeming is what makes it repaid
+                if self.get_outstanding_bonds() > Decimal::zero()
+                    && self.get_amount_owed_by_issuer() == Decimal::zero()
+                    && self.status != BondStatus::Repaid
+                {
+                    self.repaid_at = Some(Self::current_time_seconds());
+                    self.set_status(BondStatus::Repaid);
                 }
+            }
+
+            // SINKING FUND
+            // issuer pays back part of the bond ahead of maturity
+            pub fn deposit_to_sinking_fund(&mut self, deposit: Bucket) {
+
+                assert_eq!(deposit.resource_address(), XRD, "sinking fund deposits must be in XRD");
+                assert!(!deposit.is_empty(), "sinking fund deposit must be greater than zero");
 
+                let status = self.refresh_status();
+
+                assert!(
+                    status != BondStatus::Default && status != BondStatus::Closed,
+                    "bond can not be repaid once it is in default or closed"
+                );
+
+                self.deposited_by_issuer += deposit.amount();
+                self.collected_xrd.put(deposit);
+
+                self.mark_repaid_if_covered();
+
+                // deposits can bring a delayed bond back to performing
+                self.refresh_status();
+            }
+
+            pub fn get_sinking_fund_status(&self) -> SinkingFundStatus {
+
+                let current_time_seconds = Self::current_time_seconds();
+
+                let required_to_date = self.sinking_fund_schedule
+                    .iter()
+                    .filter(|instalment| instalment.due_date < current_time_seconds)
+                    .fold(Decimal::zero(), |total, instalment| total + instalment.amount);
+
+                let coverage_ratio = if required_to_date == Decimal::zero() {
+                    None
+                } else {
+                    Some(self.deposited_by_issuer / required_to_date)
+                };
+
+                SinkingFundStatus {
+                    schedule: self.sinking_fund_schedule.clone(),
+                    deposited_by_issuer: self.deposited_by_issuer,
+                    required_to_date,
+                    coverage_ratio,
+                    missed_instalments: self.missed_instalments(current_time_seconds),
+                    next_instalment: self.sinking_fund_schedule
+                        .iter()
+                        .find(|instalment| instalment.due_date >= current_time_seconds)
+                        .cloned(),
+                }
+            }
+
+            // returns the instalments that were missed since the last call, so each one is reported once
+            pub fn report_missed_instalments(&mut self) -> Vec<SinkingFundInstalment> {
+
+                let missed = self.missed_instalments(Self::current_time_seconds());
+
+                let newly_missed: Vec<SinkingFundInstalment> = missed
+                    .iter()
+                    .filter(|index| **index >= self.missed_instalments_reported)
+                    .map(|index| self.sinking_fund_schedule[*index].clone())
+                    .collect();
+
+                if let Some(last) = missed.last() {
+                    self.missed_instalments_reported = self.missed_instalments_reported.max(last + 1);
+                }
+
+                newly_missed
+            }
+
+            // XRD recovered from selling seized collateral, kept here so bondholders can redeem
+            pub fn deposit_recovery_proceeds(&mut self, proceeds: Bucket) {
+                self.collected_xrd.put(proceeds);
             }
 
             pub fn check_the_balance_of_bond_issuer(&self) 
             -> Decimal
             {
-                let balance = self.collected_xrd.amount();
-                balance
+                self.collected_xrd.amount()
             }
 
             pub fn get_bond_u_id(&self) -> String{
-                let bond_u_id = self.contract_identifier.clone();
-                bond_u_id
+                self.contract_identifier.clone()
             }
 
             pub fn force_transfer_deposited_xrds(&mut self) -> Bucket{
+
+                assert!(self.refresh_status() == BondStatus::Default, "deposited XRDs can only be force transferred once the bond is in default");
                 
                 let balance = self.check_the_balance_of_bond_issuer();
 
-                self.collected_xrd.take(balance)
+                if self.collateral.is_empty() {
+                    self.set_status(BondStatus::Closed);
+                }
+
+                let deposited_xrds = self.collected_xrd.take(balance);
+
+                self.emit_redeemed(BondRedemptionKind::ForcedTransfer, Decimal::zero(), &deposited_xrds);
+
+                deposited_xrds
             }
         }
     }
@@ -1,68 +1,1974 @@
 use scrypto_test::prelude::*;
 
-use actus::hello_test::*;
+// Mirrors of the blueprint's argument types, SBOR encodes enums by variant index so only the order matters.
+#[derive(ManifestSbor)]
+#[allow(dead_code)]
+enum ProposalCreationRight {
+    Everyone,
+    TokenHolderThreshold(Decimal),
+    Admin,
+}
 
-#[test]
-fn test_hello() {
-    // Setup the environment
-    let mut ledger = LedgerSimulatorBuilder::new().build();
+#[derive(ManifestSbor)]
+#[allow(dead_code)]
+enum VotingType {
+    ResourceHold,
+    Equality,
+    Quadratic,
+}
+
+#[derive(ManifestSbor)]
+#[allow(dead_code)]
+enum ConfigChange {
+    ProposalCreationRight(ProposalCreationRight),
+}
+
+#[derive(ManifestSbor)]
+#[allow(dead_code)]
+enum ProposalAction {
+    PurchaseBond {
+        bond_creator_address: ComponentAddress,
+        target_xrd_amount: Decimal,
+    },
+    MintTokens {
+        amount: usize,
+    },
+    ChangePrice {
+        token_price: Decimal,
+        buy_back_price: Decimal,
+    },
+    TreasuryTransfer {
+        recipient: ComponentAddress,
+        amount: Decimal,
+    },
+    ConfigChange(ConfigChange),
+    OnboardIssuer {
+        application_id: u64,
+    },
+    AdmitMember {
+        application_id: u64,
+    },
+    InsuranceClaim {
+        claim_id: u64,
+        amount: Decimal,
+    },
+}
+
+#[derive(ManifestSbor)]
+#[allow(dead_code)]
+enum DecayCurve {
+    Linear,
+    Exponential { rate: Decimal, interval: u64 },
+    Step { drop: Decimal, interval: u64 },
+}
+
+#[derive(ManifestSbor)]
+#[allow(dead_code)]
+enum AuctionProceedsDestination {
+    Treasury,
+    Bondholders(ComponentAddress),
+}
+
+#[derive(ScryptoSbor, Debug, PartialEq)]
+#[allow(dead_code)]
+enum BondStatus {
+    Performing,
+    Delayed,
+    Delinquent,
+    Default,
+    Repaid,
+    Closed,
+}
+
+#[derive(ScryptoSbor, Debug)]
+#[allow(dead_code)]
+struct IssuerReputation {
+    issuer_address: ComponentAddress,
+    bonds_issued: usize,
+    outstanding: usize,
+    repaid_on_time: usize,
+    repaid_late: usize,
+    defaulted: usize,
+    collateral_liquidated: usize,
+    total_amount_issued: Decimal,
+    total_amount_repaid: Decimal,
+    score: Decimal,
+}
+
+#[derive(ScryptoSbor, Debug)]
+#[allow(dead_code)]
+struct SinkingFundInstalment {
+    due_date: u64,
+    amount: Decimal,
+}
+
+#[derive(ScryptoSbor, Debug)]
+#[allow(dead_code)]
+struct SinkingFundStatus {
+    schedule: Vec<SinkingFundInstalment>,
+    deposited_by_issuer: Decimal,
+    required_to_date: Decimal,
+    coverage_ratio: Option<Decimal>,
+    missed_instalments: Vec<usize>,
+    next_instalment: Option<SinkingFundInstalment>,
+}
+
+#[derive(ScryptoSbor, Debug)]
+#[allow(dead_code)]
+struct BondHolding {
+    bond_creator_address: ComponentAddress,
+    bond_component_address: ComponentAddress,
+    bond_resource_address: ResourceAddress,
+    contract_identifier: String,
+    quantity: Decimal,
+    cost_basis: Decimal,
+    acquired_at: u64,
+    accrued_interest: Decimal,
+    expected_redemption_value: Decimal,
+    carrying_value: Decimal,
+    market_value: Option<Decimal>,
+    days_to_maturity: i64,
+    status: BondStatus,
+}
+
+#[derive(ScryptoSbor, Debug)]
+#[allow(dead_code)]
+struct PortfolioReport {
+    holdings: Vec<BondHolding>,
+    total_quantity: Decimal,
+    total_cost_basis: Decimal,
+    total_accrued_interest: Decimal,
+    total_expected_redemption_value: Decimal,
+    total_carrying_value: Decimal,
+    net_asset_value: Decimal,
+    holdings_marked_to_market: usize,
+}
+
+#[derive(ScryptoSbor, ManifestSbor, Debug)]
+#[allow(dead_code)]
+enum LadderInterval {
+    Weekly,
+    Monthly,
+}
+
+#[derive(ScryptoSbor, Debug)]
+#[allow(dead_code)]
+struct LadderRung {
+    start_time: u64,
+    end_time: u64,
+    inflows: Decimal,
+    outflows: Decimal,
+    net_flow: Decimal,
+    closing_balance: Decimal,
+}
+
+#[derive(ScryptoSbor, Debug)]
+#[allow(dead_code)]
+struct LiquidityLadder {
+    interval: LadderInterval,
+    opening_balance: Decimal,
+    rungs: Vec<LadderRung>,
+    lowest_balance: Decimal,
+    flows_beyond_horizon: Decimal,
+}
+
+#[derive(ManifestSbor)]
+#[allow(dead_code)]
+enum ProposalCategory {
+    PurchaseBond,
+    MintTokens,
+    ChangePrice,
+    TreasuryTransfer,
+    ConfigChange,
+    OnboardIssuer,
+    AdmitMember,
+    InsuranceClaim,
+}
+
+#[derive(ManifestSbor)]
+#[allow(dead_code)]
+enum ApprovalThreshold {
+    SimpleMajority,
+    Supermajority(Decimal),
+}
+
+#[derive(ManifestSbor)]
+struct PassingRule {
+    approval: ApprovalThreshold,
+    minimum_turnout: Decimal,
+}
+
+#[derive(ManifestSbor)]
+#[allow(dead_code)]
+enum CountingMethod {
+    Plurality,
+    Approval,
+    InstantRunoff,
+}
+
+#[derive(ManifestSbor)]
+struct ProposalOption {
+    label: String,
+    action: ProposalAction,
+}
+
+#[derive(ManifestSbor)]
+#[allow(dead_code)]
+enum JoinPolicy {
+    Open,
+    TokenThreshold(Decimal),
+    Vote,
+}
+
+// ledger time the DAO tests start at, proposals vote for one day from here
+const START: u64 = 1_700_000_000;
+const DAY: u64 = 86400;
+
+fn utc(seconds: u64) -> UtcDateTime {
+    UtcDateTime::from_instant(&Instant::new(seconds as i64)).unwrap()
+}
+
+struct DaoSetup {
+    ledger: DefaultLedgerSimulator,
+    package_address: PackageAddress,
+    public_key: Secp256k1PublicKey,
+    account: ComponentAddress,
+    owner_badge: ResourceAddress,
+    issuer_badge: ResourceAddress,
+    vote_receipt: ResourceAddress,
+    member_badge: ResourceAddress,
+    dao_token: ResourceAddress,
+    dao: ComponentAddress,
+    round: u64,
+    now: u64,
+    // proposals and issuer applications created so far, both count up from 1
+    proposals: usize,
+    issuer_applications: u64,
+}
+
+// an account the DAO voted in as a bond issuer
+struct Issuer {
+    public_key: Secp256k1PublicKey,
+    account: ComponentAddress,
+    badge_id: NonFungibleLocalId,
+}
+
+impl DaoSetup {
+    fn set_time(&mut self, seconds: u64) {
+        self.round += 1;
+        self.now = seconds;
+        set_time(&mut self.ledger, self.round, seconds);
+    }
 
-    // Create an account
+    fn with_owner_badge(&self) -> ManifestBuilder {
+        ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .create_proof_from_account_of_amount(self.account, self.owner_badge, dec!("1"))
+    }
+
+    // a new account buys `amount` governance tokens at one XRD each
+    fn new_token_holder(&mut self, amount: Decimal) -> (Secp256k1PublicKey, ComponentAddress) {
+        let (public_key, _private_key, account) = self.ledger.new_allocated_account();
+
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(account, XRD, amount)
+            .take_all_from_worktop(XRD, "payment")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(
+                    self.dao,
+                    "obtain_community_token",
+                    manifest_args!(lookup.bucket("payment"), amount, account),
+                )
+            })
+            .deposit_entire_worktop(account)
+            .build();
+        self.run(manifest, &public_key).expect_commit_success();
+
+        (public_key, account)
+    }
+
+    // proposes `action` with a vote from `start` to `end`, proposal ids count up from 1
+    fn create_proposal(
+        &mut self,
+        public_key: &Secp256k1PublicKey,
+        account: ComponentAddress,
+        voting_type: VotingType,
+        action: ProposalAction,
+        start: u64,
+        end: u64,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(account, self.dao_token, dec!("1"))
+            .take_all_from_worktop(self.dao_token, "token")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(
+                    self.dao,
+                    "create_praposal",
+                    manifest_args!(
+                        "a proposal",
+                        "for tests",
+                        1u8,
+                        utc(start),
+                        utc(end),
+                        account,
+                        lookup.bucket("token"),
+                        voting_type,
+                        action
+                    ),
+                )
+            })
+            .deposit_entire_worktop(account)
+            .build();
+        let receipt = self.run(manifest, public_key);
+        if receipt.is_commit_success() {
+            self.proposals += 1;
+        }
+        receipt
+    }
+
+    fn vote(
+        &mut self,
+        public_key: &Secp256k1PublicKey,
+        account: ComponentAddress,
+        proposal_id: usize,
+        amount: Decimal,
+        against: bool,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(account, self.dao_token, amount)
+            .take_all_from_worktop(self.dao_token, "tokens")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(
+                    self.dao,
+                    "vote",
+                    manifest_args!(lookup.bucket("tokens"), against, account, proposal_id),
+                )
+            })
+            .deposit_entire_worktop(account)
+            .build();
+        self.run(manifest, public_key)
+    }
+
+    // like `create_proposal`, the winning option's action is executed
+    fn create_proposal_with_options(
+        &mut self,
+        public_key: &Secp256k1PublicKey,
+        account: ComponentAddress,
+        options: Vec<ProposalOption>,
+        counting_method: CountingMethod,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(account, self.dao_token, dec!("1"))
+            .take_all_from_worktop(self.dao_token, "token")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(
+                    self.dao,
+                    "create_praposal_with_options",
+                    manifest_args!(
+                        "a proposal",
+                        "for tests",
+                        1u8,
+                        utc(START),
+                        utc(START + DAY),
+                        account,
+                        lookup.bucket("token"),
+                        VotingType::ResourceHold,
+                        options,
+                        counting_method
+                    ),
+                )
+            })
+            .deposit_entire_worktop(account)
+            .build();
+        let receipt = self.run(manifest, public_key);
+        if receipt.is_commit_success() {
+            self.proposals += 1;
+        }
+        receipt
+    }
+
+    fn vote_on_options(
+        &mut self,
+        public_key: &Secp256k1PublicKey,
+        account: ComponentAddress,
+        proposal_id: usize,
+        amount: Decimal,
+        choices: Vec<usize>,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(account, self.dao_token, amount)
+            .take_all_from_worktop(self.dao_token, "tokens")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(
+                    self.dao,
+                    "vote_on_options",
+                    manifest_args!(lookup.bucket("tokens"), choices, account, proposal_id),
+                )
+            })
+            .deposit_entire_worktop(account)
+            .build();
+        self.run(manifest, public_key)
+    }
+
+    // votes with a proof of the account's member badge, as quadratic proposals require
+    fn vote_quadratic(
+        &mut self,
+        public_key: &Secp256k1PublicKey,
+        account: ComponentAddress,
+        proposal_id: usize,
+        amount: Decimal,
+        against: bool,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(account, self.dao_token, amount)
+            .take_all_from_worktop(self.dao_token, "tokens")
+            .create_proof_from_account_of_amount(account, self.member_badge, dec!("1"))
+            .pop_from_auth_zone("badge")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(
+                    self.dao,
+                    "vote_quadratic",
+                    manifest_args!(lookup.bucket("tokens"), against, lookup.proof("badge"), proposal_id),
+                )
+            })
+            .deposit_entire_worktop(account)
+            .build();
+        self.run(manifest, public_key)
+    }
+
+    fn claim_back(&mut self, public_key: &Secp256k1PublicKey, account: ComponentAddress) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(account, self.vote_receipt, dec!("1"))
+            .take_all_from_worktop(self.vote_receipt, "receipt")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(
+                    self.dao,
+                    "claim_back_vote_tokens",
+                    manifest_args!(lookup.bucket("receipt")),
+                )
+            })
+            .deposit_entire_worktop(account)
+            .build();
+        self.run(manifest, public_key)
+    }
+
+    // joins with a proof of all of the account's governance tokens
+    fn join(&mut self, public_key: &Secp256k1PublicKey, account: ComponentAddress) -> TransactionReceipt {
+        let tokens = self.ledger.get_component_balance(account, self.dao_token);
+
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .create_proof_from_account_of_amount(account, self.dao_token, tokens)
+            .pop_from_auth_zone("tokens")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(
+                    self.dao,
+                    "become_a_dao_member",
+                    manifest_args!(account, "a member", Some(lookup.proof("tokens"))),
+                )
+            })
+            .deposit_entire_worktop(account)
+            .build();
+        self.run(manifest, public_key)
+    }
+
+    // `execute_proposal` commits even when the action fails, so this returns what it reported
+    fn execute_proposal(&mut self, proposal_id: usize) -> Result<String, String> {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(self.dao, "execute_proposal", manifest_args!(proposal_id))
+            .build();
+        self.ledger
+            .execute_manifest(manifest, vec![])
+            .expect_commit_success()
+            .output(1)
+    }
+
+    // `voter` proposes `action`, votes it through with half of its 100 tokens and it is executed a day later
+    fn pass_proposal(
+        &mut self,
+        voter_key: &Secp256k1PublicKey,
+        voter: ComponentAddress,
+        action: ProposalAction,
+    ) -> Result<String, String> {
+        let now = self.now;
+        self.create_proposal(voter_key, voter, VotingType::ResourceHold, action, now, now + DAY)
+            .expect_commit_success();
+        let proposal_id = self.proposals;
+
+        self.vote(voter_key, voter, proposal_id, dec!("50"), false)
+            .expect_commit_success();
+
+        self.set_time(now + DAY + 1);
+        self.execute_proposal(proposal_id)
+    }
+
+    fn onboard_issuer(&mut self) -> Issuer {
+        let (voter_key, voter) = self.new_token_holder(dec!("100"));
+        let (public_key, _private_key, account) = self.ledger.new_allocated_account();
+
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(
+                self.dao,
+                "apply_as_issuer",
+                manifest_args!(account, "Test Issuer", "issues bonds in tests"),
+            )
+            .build();
+        self.run(manifest, &public_key).expect_commit_success();
+        self.issuer_applications += 1;
+        let application_id = self.issuer_applications;
+
+        self.pass_proposal(&voter_key, voter, ProposalAction::OnboardIssuer { application_id })
+            .expect("issuer onboarding failed");
+
+        Issuer {
+            public_key,
+            account,
+            badge_id: NonFungibleLocalId::integer(application_id),
+        }
+    }
+
+    // 100 bonds of 40 XRD at 2% interest issued now, backed by 10 XRD of collateral, a minute of grace and of delinquency
+    fn create_bond(&mut self, issuer: &Issuer, maturity_date: u64) -> ComponentAddress {
+        self.create_bond_with(issuer, maturity_date, Vec::new(), None::<(Decimal, u64)>)
+    }
+
+    fn create_bond_with(
+        &mut self,
+        issuer: &Issuer,
+        maturity_date: u64,
+        sinking_fund_schedule: Vec<(u64, Decimal)>,
+        call_option: Option<(Decimal, u64)>,
+    ) -> ComponentAddress {
+        let now = self.now;
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .create_proof_from_account_of_non_fungibles(
+                issuer.account,
+                self.issuer_badge,
+                [issuer.badge_id.clone()],
+            )
+            .pop_from_auth_zone("badge")
+            .withdraw_from_account(issuer.account, XRD, dec!("10"))
+            .take_all_from_worktop(XRD, "collateral")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(
+                    self.dao,
+                    "create_zero_coupon_bond",
+                    manifest_args!(
+                        "Principal at Maturity",
+                        "PAM",
+                        "#1",
+                        dec!("2"),
+                        "xrd",
+                        now,
+                        maturity_date,
+                        dec!("40"),
+                        0u64,
+                        "long",
+                        40u64,
+                        dec!("100"),
+                        lookup.proof("badge"),
+                        lookup.bucket("collateral"),
+                        (60u64, 60u64, sinking_fund_schedule),
+                        (call_option, None::<(Decimal, Vec<u64>, u64)>),
+                        None::<((Decimal, u64), ManifestBucket)>
+                    ),
+                )
+            })
+            .build();
+        let public_key = issuer.public_key;
+        self.run(manifest, &public_key)
+            .expect_commit_success()
+            .new_component_addresses()[0]
+    }
+
+    // a new account buys one bond straight from the bond component
+    fn buy_bond(&mut self, bond: ComponentAddress) -> (Secp256k1PublicKey, ComponentAddress) {
+        let (public_key, _private_key, account) = self.ledger.new_allocated_account();
+
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(account, XRD, dec!("40"))
+            .take_all_from_worktop(XRD, "payment")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(bond, "purchase_bond", manifest_args!(lookup.bucket("payment")))
+            })
+            .deposit_entire_worktop(account)
+            .build();
+        self.run(manifest, &public_key).expect_commit_success();
+
+        (public_key, account)
+    }
+
+    // the issuer pays `amount` into its latest bond through the DAO
+    fn repay(&mut self, issuer: &Issuer, amount: Decimal) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .create_proof_from_account_of_non_fungibles(
+                issuer.account,
+                self.issuer_badge,
+                [issuer.badge_id.clone()],
+            )
+            .pop_from_auth_zone("badge")
+            .withdraw_from_account(issuer.account, XRD, amount)
+            .take_all_from_worktop(XRD, "repayment")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(
+                    self.dao,
+                    "put_in_money_plus_interest_for_the_community_to_redeem",
+                    manifest_args!(lookup.proof("badge"), lookup.bucket("repayment")),
+                )
+            })
+            .deposit_entire_worktop(issuer.account)
+            .build();
+        let public_key = issuer.public_key;
+        self.run(manifest, &public_key)
+    }
+
+    // a new account pays for one bond of the issuer's latest issue that goes to the DAO
+    fn buy_bond_for_dao(&mut self, bond_creator_address: ComponentAddress) -> TransactionReceipt {
+        let (public_key, _private_key, account) = self.ledger.new_allocated_account();
+
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(account, XRD, dec!("40"))
+            .take_all_from_worktop(XRD, "payment")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(
+                    self.dao,
+                    "purchase_bond",
+                    manifest_args!(bond_creator_address, lookup.bucket("payment")),
+                )
+            })
+            .deposit_entire_worktop(account)
+            .build();
+        self.run(manifest, &public_key)
+    }
+
+    // a price oracle whose owner and feeder badges sit in the DAO owner's account
+    fn price_oracle(&mut self, max_price_age: u64) -> (ComponentAddress, ResourceAddress) {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_function(
+                self.package_address,
+                "PriceOracle",
+                "instantiate_price_oracle",
+                manifest_args!(max_price_age),
+            )
+            .deposit_entire_worktop(self.account)
+            .build();
+        let receipt = self.run_as_owner(manifest);
+        let commit = receipt.expect_commit_success();
+
+        (commit.new_component_addresses()[0], commit.new_resource_addresses()[1])
+    }
+
+    fn feed_price(&mut self, oracle: ComponentAddress, feeder_badge: ResourceAddress, price: Decimal) {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .create_proof_from_account_of_amount(self.account, feeder_badge, dec!("1"))
+            .call_method(oracle, "update_price", manifest_args!(XRD, price))
+            .build();
+        self.run_as_owner(manifest).expect_commit_success();
+    }
+
+    // calls a public method without signing
+    fn call(&mut self, component: ComponentAddress, method: &str, args: ManifestArgs) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(component, method, args)
+            .build();
+        self.ledger.execute_manifest(manifest, vec![])
+    }
+
+    fn query<T: ScryptoDecode>(&mut self, component: ComponentAddress, method: &str, args: ManifestArgs) -> T {
+        self.call(component, method, args).expect_commit_success().output(1)
+    }
+
+    fn bond_balance(&mut self, bond: ComponentAddress) -> Decimal {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(bond, "check_the_balance_of_bond_issuer", manifest_args!())
+            .build();
+        self.ledger
+            .execute_manifest(manifest, vec![])
+            .expect_commit_success()
+            .output(1)
+    }
+
+    fn run(&mut self, manifest: TransactionManifestV1, public_key: &Secp256k1PublicKey) -> TransactionReceipt {
+        self.ledger.execute_manifest(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(public_key)],
+        )
+    }
+
+    fn run_as_owner(&mut self, manifest: TransactionManifestV1) -> TransactionReceipt {
+        let public_key = self.public_key;
+        self.run(manifest, &public_key)
+    }
+}
+
+// Publishes the package and instantiates a DAO whose owner badge sits in `account`, at ledger time START.
+fn setup_dao() -> DaoSetup {
+    let mut ledger = LedgerSimulatorBuilder::new().build();
     let (public_key, _private_key, account) = ledger.new_allocated_account();
+    let package_address = ledger.compile_and_publish(this_package!());
+    set_time(&mut ledger, 1, START);
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            package_address,
+            "TokenWeigtedDao",
+            "initiate",
+            manifest_args!(
+                "Test DAO",
+                1000i32,
+                0u8,
+                dec!("1"),
+                dec!("1"),
+                "https://example.com/dao.png",
+                "https://example.com/token.png",
+                "a DAO for tests",
+                Vec::<String>::new(),
+                "testing",
+                ProposalCreationRight::Everyone,
+                "TST"
+            ),
+        )
+        .deposit_entire_worktop(account)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+    let commit = receipt.expect_commit(true);
+    let dao = commit.new_component_addresses()[0];
+    // `initiate` creates the owner badge, executive, issuer, vote receipt and member badges, then the governance token
+    let resources = commit.new_resource_addresses();
+
+    DaoSetup {
+        ledger,
+        package_address,
+        public_key,
+        account,
+        owner_badge: resources[0],
+        issuer_badge: resources[2],
+        vote_receipt: resources[3],
+        member_badge: resources[4],
+        dao_token: resources[5],
+        dao,
+        round: 1,
+        now: START,
+        proposals: 0,
+        issuer_applications: 0,
+    }
+}
+
+#[test]
+fn test_issuer_wrappers_reject_a_stranger() {
+    let mut setup = setup_dao();
+    let (stranger_key, _stranger_private_key, stranger) = setup.ledger.new_allocated_account();
+    // a badge the stranger minted for themselves, not one the DAO issued
+    let fake_badge = setup.ledger.create_non_fungible_resource(stranger);
 
-    // Publish package
+    for method in [
+        "get_back_the_collateral",
+        "take_out_the_invested_XRDs_by_the_community",
+    ] {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .create_proof_from_account_of_non_fungibles(
+                stranger,
+                fake_badge,
+                [NonFungibleLocalId::integer(1)],
+            )
+            .pop_from_auth_zone("badge")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(setup.dao, method, manifest_args!(lookup.proof("badge")))
+            })
+            .deposit_entire_worktop(stranger)
+            .build();
+        setup.run(manifest, &stranger_key).expect_commit_failure();
+    }
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            stranger,
+            fake_badge,
+            [NonFungibleLocalId::integer(1)],
+        )
+        .pop_from_auth_zone("badge")
+        .withdraw_from_account(stranger, XRD, dec!("10"))
+        .take_all_from_worktop(XRD, "repayment")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                setup.dao,
+                "put_in_money_plus_interest_for_the_community_to_redeem",
+                manifest_args!(lookup.proof("badge"), lookup.bucket("repayment")),
+            )
+        })
+        .deposit_entire_worktop(stranger)
+        .build();
+    setup.run(manifest, &stranger_key).expect_commit_failure();
+}
+
+struct OracleSetup {
+    ledger: DefaultLedgerSimulator,
+    public_key: Secp256k1PublicKey,
+    account: ComponentAddress,
+    oracle: ComponentAddress,
+    owner_badge: ResourceAddress,
+    feeder_badge: ResourceAddress,
+}
+
+fn setup_oracle(max_price_age: u64) -> OracleSetup {
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (public_key, _private_key, account) = ledger.new_allocated_account();
     let package_address = ledger.compile_and_publish(this_package!());
 
-    // Test the `instantiate_hello` function.
     let manifest = ManifestBuilder::new()
         .lock_fee_from_faucet()
         .call_function(
             package_address,
-            "Hello",
-            "instantiate_hello",
-            manifest_args!(),
+            "PriceOracle",
+            "instantiate_price_oracle",
+            manifest_args!(max_price_age),
         )
+        .deposit_entire_worktop(account)
         .build();
     let receipt = ledger.execute_manifest(
         manifest,
         vec![NonFungibleGlobalId::from_public_key(&public_key)],
     );
-    println!("{:?}\n", receipt);
-    let component = receipt.expect_commit(true).new_component_addresses()[0];
+    let commit = receipt.expect_commit(true);
+    let oracle = commit.new_component_addresses()[0];
+    let owner_badge = commit.new_resource_addresses()[0];
+    let feeder_badge = commit.new_resource_addresses()[1];
+
+    OracleSetup {
+        ledger,
+        public_key,
+        account,
+        oracle,
+        owner_badge,
+        feeder_badge,
+    }
+}
+
+impl OracleSetup {
+    fn call_with_badge(&mut self, badge: ResourceAddress, method: &str, args: ManifestArgs) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .create_proof_from_account_of_amount(self.account, badge, dec!("1"))
+            .call_method(self.oracle, method, args)
+            .build();
+        self.ledger.execute_manifest(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&self.public_key)],
+        )
+    }
+}
+
+#[test]
+fn test_oracle_feeder_and_owner_have_separate_roles() {
+    let mut setup = setup_oracle(3600);
+    let (owner_badge, feeder_badge) = (setup.owner_badge, setup.feeder_badge);
+
+    setup
+        .call_with_badge(feeder_badge, "update_price", manifest_args!(XRD, dec!("2")))
+        .expect_commit_success();
+    setup
+        .call_with_badge(feeder_badge, "set_max_price_age", manifest_args!(60u64))
+        .expect_specific_failure(is_unauthorized);
+
+    setup
+        .call_with_badge(owner_badge, "set_max_price_age", manifest_args!(60u64))
+        .expect_commit_success();
+    setup
+        .call_with_badge(owner_badge, "update_price", manifest_args!(XRD, dec!("3")))
+        .expect_specific_failure(is_unauthorized);
+}
+
+#[test]
+fn test_oracle_price_age_does_not_overflow() {
+    let mut setup = setup_oracle(u64::MAX);
+    let feeder_badge = setup.feeder_badge;
+
+    setup
+        .call_with_badge(feeder_badge, "update_price", manifest_args!(XRD, dec!("2")))
+        .expect_commit_success();
 
-    // Test the `free_token` method.
     let manifest = ManifestBuilder::new()
         .lock_fee_from_faucet()
-        .call_method(component, "free_token", manifest_args!())
-        .call_method(
-            account,
-            "deposit_batch",
-            manifest_args!(ManifestExpression::EntireWorktop),
+        .call_method(setup.oracle, "get_price", manifest_args!(XRD))
+        .build();
+    let receipt = setup.ledger.execute_manifest(manifest, vec![]);
+    let price: Option<Decimal> = receipt.expect_commit_success().output(1);
+    assert_eq!(price, Some(dec!("2")));
+}
+
+fn is_unauthorized(error: &RuntimeError) -> bool {
+    matches!(
+        error,
+        RuntimeError::SystemModuleError(SystemModuleError::AuthError(AuthError::Unauthorized(..)))
+    )
+}
+
+#[derive(ManifestSbor)]
+enum ClaimVoters {
+    Members,
+    #[allow(dead_code)]
+    Assessors,
+}
+
+const SECONDS_IN_YEAR: u64 = 365 * 24 * 60 * 60;
+
+fn set_time(ledger: &mut DefaultLedgerSimulator, round: u64, seconds: u64) {
+    ledger.advance_to_round_at_timestamp(Round::of(round), (seconds * 1000) as i64);
+}
+
+struct InsuranceSetup {
+    ledger: DefaultLedgerSimulator,
+    insurance: ComponentAddress,
+    policy_resource: ResourceAddress,
+    member_token: ResourceAddress,
+    vote_receipt: ResourceAddress,
+}
+
+// an insurance DAO where policy holders vote on claims, with a one day voting period
+fn setup_insurance() -> InsuranceSetup {
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (public_key, _private_key, account) = ledger.new_allocated_account();
+    let package_address = ledger.compile_and_publish(this_package!());
+    set_time(&mut ledger, 1, 1_700_000_000);
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            package_address,
+            "InsuranceDao",
+            "instantiate_insurance_dao",
+            manifest_args!(
+                "Test Mutual",
+                dec!("0.1"),
+                dec!("0"),
+                ClaimVoters::Members,
+                1u8,
+                86400u64
+            ),
         )
+        .deposit_entire_worktop(account)
         .build();
     let receipt = ledger.execute_manifest(
         manifest,
         vec![NonFungibleGlobalId::from_public_key(&public_key)],
     );
-    println!("{:?}\n", receipt);
-    receipt.expect_commit_success();
+    let commit = receipt.expect_commit(true);
+    // resources are created in this order: owner badge, policy, member token, assessor badge, vote receipt
+    let resources = commit.new_resource_addresses();
+
+    InsuranceSetup {
+        insurance: commit.new_component_addresses()[0],
+        policy_resource: resources[1],
+        member_token: resources[2],
+        vote_receipt: resources[4],
+        ledger,
+    }
+}
+
+impl InsuranceSetup {
+    // a new account buys a year of 1000 XRD cover for 100 XRD and gets 100 member tokens
+    fn new_policy_holder(&mut self) -> (Secp256k1PublicKey, ComponentAddress) {
+        let (public_key, _private_key, account) = self.ledger.new_allocated_account();
+
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(account, XRD, dec!("100"))
+            .take_all_from_worktop(XRD, "premium")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(
+                    self.insurance,
+                    "buy_policy",
+                    manifest_args!(lookup.bucket("premium"), dec!("1000"), SECONDS_IN_YEAR),
+                )
+            })
+            .deposit_entire_worktop(account)
+            .build();
+        self.ledger
+            .execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&public_key)])
+            .expect_commit_success();
+
+        (public_key, account)
+    }
+
+    fn vote(&mut self, public_key: &Secp256k1PublicKey, account: ComponentAddress, claim_id: u64) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(account, self.member_token, dec!("100"))
+            .take_all_from_worktop(self.member_token, "tokens")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(
+                    self.insurance,
+                    "vote_on_claim",
+                    manifest_args!(claim_id, lookup.bucket("tokens"), false, account),
+                )
+            })
+            .deposit_entire_worktop(account)
+            .build();
+        self.ledger
+            .execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(public_key)])
+    }
+
+    fn claim_back(&mut self, public_key: &Secp256k1PublicKey, account: ComponentAddress) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(account, self.vote_receipt, dec!("1"))
+            .take_all_from_worktop(self.vote_receipt, "receipt")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(
+                    self.insurance,
+                    "claim_back_vote_tokens",
+                    manifest_args!(lookup.bucket("receipt")),
+                )
+            })
+            .deposit_entire_worktop(account)
+            .build();
+        self.ledger
+            .execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(public_key)])
+    }
 }
 
 #[test]
-fn test_hello_with_test_environment() -> Result<(), RuntimeError> {
-    // Arrange
-    let mut env = TestEnvironment::new();
-    let package_address = 
-        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+fn test_claim_votes_are_escrowed_and_the_claimant_can_not_vote() {
+    let mut setup = setup_insurance();
+    let (claimant_key, claimant) = setup.new_policy_holder();
+    let (voter_key, voter) = setup.new_policy_holder();
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            claimant,
+            setup.policy_resource,
+            [NonFungibleLocalId::integer(1)],
+        )
+        .pop_from_auth_zone("policy")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                setup.insurance,
+                "file_claim",
+                manifest_args!(
+                    lookup.proof("policy"),
+                    dec!("50"),
+                    "roof damage",
+                    vec!["https://example.com/photo.png".to_string()],
+                    claimant
+                ),
+            )
+        })
+        .build();
+    setup
+        .ledger
+        .execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&claimant_key)])
+        .expect_commit_success();
+
+    setup.vote(&claimant_key, claimant, 1).expect_commit_failure();
+
+    setup.vote(&voter_key, voter, 1).expect_commit_success();
+    assert_eq!(setup.ledger.get_component_balance(voter, setup.member_token), dec!("0"));
+
+    // the tokens stay locked while the vote is open
+    setup.claim_back(&voter_key, voter).expect_commit_failure();
+
+    set_time(&mut setup.ledger, 2, 1_700_000_000 + 86400 + 1);
+
+    setup.claim_back(&voter_key, voter).expect_commit_success();
+    assert_eq!(setup.ledger.get_component_balance(voter, setup.member_token), dec!("100"));
+}
+
+#[test]
+fn test_token_supply_and_price_only_change_through_proposals() {
+    let mut setup = setup_dao();
+    let (holder_key, holder) = setup.new_token_holder(dec!("100"));
+
+    setup
+        .create_proposal(&holder_key, holder, VotingType::ResourceHold, ProposalAction::MintTokens { amount: 1000 }, START, START + DAY)
+        .expect_commit_success();
+    setup
+        .create_proposal(
+            &holder_key,
+            holder,
+            VotingType::ResourceHold,
+            ProposalAction::ChangePrice {
+                token_price: dec!("2"),
+                buy_back_price: dec!("1"),
+            },
+            START,
+            START + DAY,
+        )
+        .expect_commit_success();
 
-    let mut hello = Hello::instantiate_hello(package_address, &mut env)?;
+    setup.vote(&holder_key, holder, 1, dec!("40"), false).expect_commit_success();
+    setup.vote(&holder_key, holder, 2, dec!("40"), false).expect_commit_success();
 
-    // Act
-    let bucket = hello.free_token(&mut env)?;
+    let unsold_before = setup.ledger.get_component_balance(setup.dao, setup.dao_token);
 
-    // Assert
-    let amount = bucket.amount(&mut env)?;
-    assert_eq!(amount, dec!("1"));
+    setup.set_time(START + DAY + 1);
+    assert_eq!(setup.execute_proposal(1), Ok("1000 tokens minted".to_string()));
+    setup.execute_proposal(2).expect("the price change failed");
+
+    assert_eq!(
+        setup.ledger.get_component_balance(setup.dao, setup.dao_token),
+        unsold_before + dec!("1000")
+    );
+
+    // tokens now cost 2 XRD each
+    let buy_tokens = |dao: ComponentAddress, payment: Decimal| {
+        ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(holder, XRD, payment)
+            .take_all_from_worktop(XRD, "payment")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(
+                    dao,
+                    "obtain_community_token",
+                    manifest_args!(lookup.bucket("payment"), dec!("10"), holder),
+                )
+            })
+            .deposit_entire_worktop(holder)
+            .build()
+    };
+    setup.run(buy_tokens(setup.dao, dec!("10")), &holder_key).expect_commit_failure();
+    setup.run(buy_tokens(setup.dao, dec!("20")), &holder_key).expect_commit_success();
+}
+
+#[test]
+fn test_proposals_created_in_the_same_epoch_get_their_own_ids() {
+    let mut setup = setup_dao();
+    let (holder_key, holder) = setup.new_token_holder(dec!("100"));
+
+    for token_price in [dec!("2"), dec!("3")] {
+        setup
+            .create_proposal(
+                &holder_key,
+                holder,
+                VotingType::ResourceHold,
+                ProposalAction::ChangePrice {
+                    token_price,
+                    buy_back_price: dec!("1"),
+                },
+                START,
+                START + DAY,
+            )
+            .expect_commit_success();
+    }
+
+    setup.vote(&holder_key, holder, 1, dec!("40"), false).expect_commit_success();
+    setup.vote(&holder_key, holder, 2, dec!("40"), true).expect_commit_success();
+
+    setup.set_time(START + DAY + 1);
+    setup.execute_proposal(1).expect("the price change failed");
+
+    // the other proposal is still open, so voting power can not be sold yet
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(holder, setup.dao_token, dec!("10"))
+        .take_all_from_worktop(setup.dao_token, "power")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(setup.dao, "withdraw_power", manifest_args!(lookup.bucket("power")))
+        })
+        .deposit_entire_worktop(holder)
+        .build();
+    setup.run(manifest, &holder_key).expect_commit_failure();
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(setup.dao, "close_proposal", manifest_args!(2usize))
+        .build();
+    setup.run(manifest, &holder_key).expect_commit_success();
+
+    // once nothing is open the empty per-creator map is gone and voting power can be sold
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(holder, setup.dao_token, dec!("10"))
+        .take_all_from_worktop(setup.dao_token, "power")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(setup.dao, "withdraw_power", manifest_args!(lookup.bucket("power")))
+        })
+        .deposit_entire_worktop(holder)
+        .build();
+    setup.run(manifest, &holder_key).expect_commit_success();
+}
+
+#[test]
+fn test_auction_proceeds_go_to_the_bond_the_collateral_came_from() {
+    let mut setup = setup_dao();
+    let issuer = setup.onboard_issuer();
+    let maturity_date = setup.now + DAY;
+    let defaulted_bond = setup.create_bond(&issuer, maturity_date);
+
+    // past maturity, grace and delinquency without a repayment
+    setup.set_time(maturity_date + 200);
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(setup.dao, "liquidate_collateral", manifest_args!(issuer.account))
+        .build();
+    setup.ledger.execute_manifest(manifest, vec![]).expect_commit_success();
+
+    // the issuer's next bond is now its latest one
+    let later_bond = setup.create_bond(&issuer, maturity_date + 10 * DAY);
+
+    // all 10 XRD of collateral were seized
+    assert_eq!(setup.ledger.get_component_balance(defaulted_bond, XRD), dec!("0"));
+
+    // collateral can only be routed to the bond it was seized from
+    let manifest = setup
+        .with_owner_badge()
+        .call_method(
+            setup.dao,
+            "start_collateral_auction",
+            manifest_args!(
+                XRD,
+                dec!("10"),
+                dec!("2"),
+                dec!("1"),
+                DecayCurve::Linear,
+                3600u64,
+                AuctionProceedsDestination::Bondholders(issuer.account)
+            ),
+        )
+        .build();
+    setup.run_as_owner(manifest).expect_commit_failure();
+
+    let manifest = setup
+        .with_owner_badge()
+        .call_method(
+            setup.dao,
+            "start_collateral_auction",
+            manifest_args!(
+                XRD,
+                dec!("10"),
+                dec!("2"),
+                dec!("1"),
+                DecayCurve::Linear,
+                3600u64,
+                AuctionProceedsDestination::Bondholders(defaulted_bond)
+            ),
+        )
+        .build();
+    let auction = setup.run_as_owner(manifest).expect_commit_success().new_component_addresses()[0];
+
+    let (bidder_key, _bidder_private_key, bidder) = setup.ledger.new_allocated_account();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(bidder, XRD, dec!("20"))
+        .take_all_from_worktop(XRD, "payment")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(auction, "bid", manifest_args!(lookup.bucket("payment"), dec!("10")))
+        })
+        .deposit_entire_worktop(bidder)
+        .build();
+    setup.run(manifest, &bidder_key).expect_commit_success();
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(setup.dao, "settle_collateral_auction", manifest_args!(auction))
+        .build();
+    setup.ledger.execute_manifest(manifest, vec![]).expect_commit_success();
+
+    assert_eq!(setup.bond_balance(defaulted_bond), dec!("20"));
+    assert_eq!(setup.bond_balance(later_bond), dec!("0"));
+}
+
+#[test]
+fn test_proposal_voting_window_must_be_valid() {
+    let mut setup = setup_dao();
+    let (holder_key, holder) = setup.new_token_holder(dec!("100"));
+    let action = || ProposalAction::MintTokens { amount: 10 };
+
+    // ends before it starts
+    setup
+        .create_proposal(&holder_key, holder, VotingType::ResourceHold, action(), START + DAY, START)
+        .expect_commit_failure();
+
+    // already over
+    setup
+        .create_proposal(&holder_key, holder, VotingType::ResourceHold, action(), START - 2 * DAY, START - DAY)
+        .expect_commit_failure();
+
+    setup
+        .create_proposal(&holder_key, holder, VotingType::ResourceHold, action(), START, START + DAY)
+        .expect_commit_success();
+}
+
+#[test]
+fn test_vote_tokens_are_escrowed_until_voting_ends() {
+    let mut setup = setup_dao();
+    let (holder_key, holder) = setup.new_token_holder(dec!("100"));
+
+    setup
+        .create_proposal(&holder_key, holder, VotingType::ResourceHold, ProposalAction::MintTokens { amount: 10 }, START, START + DAY)
+        .expect_commit_success();
+    let proposal_id = setup.proposals;
+    let before_vote = setup.ledger.get_component_balance(holder, setup.dao_token);
+
+    setup.vote(&holder_key, holder, proposal_id, dec!("50"), false).expect_commit_success();
+    assert_eq!(
+        setup.ledger.get_component_balance(holder, setup.dao_token),
+        before_vote - dec!("50")
+    );
+
+    // the vote is still open
+    setup.claim_back(&holder_key, holder).expect_commit_failure();
+
+    setup.set_time(START + DAY + 1);
+    setup.claim_back(&holder_key, holder).expect_commit_success();
+    assert_eq!(setup.ledger.get_component_balance(holder, setup.dao_token), before_vote);
+    assert_eq!(setup.ledger.get_component_balance(holder, setup.vote_receipt), dec!("0"));
+}
+
+#[test]
+fn test_margin_call_liquidates_collateral_left_uncured() {
+    let mut setup = setup_dao();
+    let issuer = setup.onboard_issuer();
+    let maturity_date = setup.now + 30 * DAY;
+    let bond = setup.create_bond(&issuer, maturity_date);
+    setup.buy_bond(bond);
+
+    // nothing to price the collateral with yet
+    setup
+        .call(setup.dao, "monitor_margin", manifest_args!(issuer.account))
+        .expect_commit_failure();
+
+    let (oracle, feeder_badge) = setup.price_oracle(30 * DAY);
+
+    let (stranger_key, _stranger_private_key, _stranger) = setup.ledger.new_allocated_account();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(setup.dao, "set_price_oracle", manifest_args!(oracle, dec!("1"), 3600u64))
+        .build();
+    setup.run(manifest, &stranger_key).expect_specific_failure(is_unauthorized);
+
+    let manifest = setup
+        .with_owner_badge()
+        .call_method(setup.dao, "set_price_oracle", manifest_args!(oracle, dec!("1"), 3600u64))
+        .build();
+    setup.run_as_owner(manifest).expect_commit_success();
+
+    // 10 XRD of collateral at 5 covers the 40.8 owed on the one bond sold
+    setup.feed_price(oracle, feeder_badge, dec!("5"));
+    setup
+        .call(setup.dao, "monitor_margin", manifest_args!(issuer.account))
+        .expect_commit_success();
+    let margin_call_start: Option<u64> =
+        setup.query(setup.dao, "get_margin_call_start", manifest_args!(issuer.account));
+    assert_eq!(margin_call_start, None);
+
+    setup.feed_price(oracle, feeder_badge, dec!("1"));
+    setup
+        .call(setup.dao, "monitor_margin", manifest_args!(issuer.account))
+        .expect_commit_success();
+    let margin_call_start: Option<u64> =
+        setup.query(setup.dao, "get_margin_call_start", manifest_args!(issuer.account));
+    assert_eq!(margin_call_start, Some(setup.now));
+
+    // still inside the window
+    let issued_at = setup.now;
+    setup.set_time(issued_at + 1800);
+    setup
+        .call(setup.dao, "monitor_margin", manifest_args!(issuer.account))
+        .expect_commit_success();
+    let collateral: Decimal = setup.query(bond, "get_collateral_amount", manifest_args!());
+    assert_eq!(collateral, dec!("10"));
+
+    setup.set_time(issued_at + 3601);
+    setup
+        .call(setup.dao, "monitor_margin", manifest_args!(issuer.account))
+        .expect_commit_success();
+    let collateral: Decimal = setup.query(bond, "get_collateral_amount", manifest_args!());
+    assert_eq!(collateral, Decimal::zero());
+}
+
+#[test]
+fn test_bond_status_moves_through_grace_and_delinquency_to_default() {
+    let mut setup = setup_dao();
+    let issuer = setup.onboard_issuer();
+    let maturity_date = setup.now + DAY;
+    let bond = setup.create_bond(&issuer, maturity_date);
+    setup.buy_bond(bond);
+
+    // a minute of grace, then a minute of delinquency
+    for (time, expected) in [
+        (maturity_date, BondStatus::Performing),
+        (maturity_date + 1, BondStatus::Delayed),
+        (maturity_date + 60, BondStatus::Delayed),
+        (maturity_date + 61, BondStatus::Delinquent),
+        (maturity_date + 120, BondStatus::Delinquent),
+    ] {
+        setup.set_time(time);
+        let status: BondStatus = setup.query(setup.dao, "refresh_bond_status", manifest_args!(issuer.account));
+        assert_eq!(status, expected);
+    }
+
+    // collateral is only seized from a bond in default
+    setup
+        .call(setup.dao, "liquidate_collateral", manifest_args!(issuer.account))
+        .expect_commit_success();
+    let collateral: Decimal = setup.query(bond, "get_collateral_amount", manifest_args!());
+    assert_eq!(collateral, dec!("10"));
+
+    setup.set_time(maturity_date + 121);
+    let status: BondStatus = setup.query(setup.dao, "refresh_bond_status", manifest_args!(issuer.account));
+    assert_eq!(status, BondStatus::Default);
+
+    setup
+        .call(setup.dao, "liquidate_collateral", manifest_args!(issuer.account))
+        .expect_commit_success();
+    let collateral: Decimal = setup.query(bond, "get_collateral_amount", manifest_args!());
+    assert_eq!(collateral, Decimal::zero());
+}
+
+#[test]
+fn test_issuer_reputation_follows_bond_outcomes() {
+    let mut setup = setup_dao();
+    let punctual = setup.onboard_issuer();
+    let defaulter = setup.onboard_issuer();
+
+    // nothing settled yet
+    let reputation: IssuerReputation =
+        setup.query(setup.dao, "get_issuer_reputation", manifest_args!(punctual.account));
+    assert_eq!(reputation.score, dec!("50"));
+
+    let maturity_date = setup.now + DAY;
+    let repaid_bond = setup.create_bond(&punctual, maturity_date);
+    let defaulted_bond = setup.create_bond(&defaulter, maturity_date);
+    setup.buy_bond(repaid_bond);
+    setup.buy_bond(defaulted_bond);
+
+    setup.repay(&punctual, dec!("40.8")).expect_commit_success();
+
+    setup.set_time(maturity_date + 200);
+    setup
+        .call(setup.dao, "liquidate_collateral", manifest_args!(defaulter.account))
+        .expect_commit_success();
+
+    let reputation: IssuerReputation =
+        setup.query(setup.dao, "get_issuer_reputation", manifest_args!(punctual.account));
+    assert_eq!(reputation.bonds_issued, 1);
+    assert_eq!(reputation.repaid_on_time, 1);
+    assert_eq!(reputation.total_amount_repaid, dec!("40.8"));
+    assert_eq!(reputation.score, dec!("100"));
+
+    let reputation: IssuerReputation =
+        setup.query(setup.dao, "get_issuer_reputation", manifest_args!(defaulter.account));
+    assert_eq!(reputation.collateral_liquidated, 1);
+    assert_eq!(reputation.score, Decimal::zero());
+
+    // an address that never issued a bond has no record
+    let (_stranger_key, _stranger_private_key, stranger) = setup.ledger.new_allocated_account();
+    let record: Result<ScryptoValue, String> =
+        setup.query(setup.dao, "get_issuer_record", manifest_args!(stranger));
+    assert!(record.is_err());
+}
+
+#[test]
+fn test_only_issuers_voted_in_get_a_badge() {
+    let mut setup = setup_dao();
+    let (voter_key, voter) = setup.new_token_holder(dec!("100"));
+    let (applicant_key, _applicant_private_key, applicant) = setup.ledger.new_allocated_account();
+
+    let apply = |dao: ComponentAddress| {
+        ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(
+                dao,
+                "apply_as_issuer",
+                manifest_args!(applicant, "Test Issuer", "issues bonds in tests"),
+            )
+            .build()
+    };
+    setup.run(apply(setup.dao), &applicant_key).expect_commit_success();
+
+    // one application at a time
+    setup.run(apply(setup.dao), &applicant_key).expect_commit_failure();
+
+    let now = setup.now;
+    setup
+        .create_proposal(&voter_key, voter, VotingType::ResourceHold, ProposalAction::OnboardIssuer { application_id: 1 }, now, now + DAY)
+        .expect_commit_success();
+    let proposal_id = setup.proposals;
+    setup.vote(&voter_key, voter, proposal_id, dec!("50"), true).expect_commit_success();
+
+    setup.set_time(now + DAY + 1);
+    assert!(setup.execute_proposal(proposal_id).is_err());
+    assert_eq!(setup.ledger.get_component_balance(applicant, setup.issuer_badge), dec!("0"));
+
+    // a rejected applicant may apply again
+    setup.run(apply(setup.dao), &applicant_key).expect_commit_success();
+    let (voter_key, voter) = setup.new_token_holder(dec!("100"));
+    setup
+        .pass_proposal(&voter_key, voter, ProposalAction::OnboardIssuer { application_id: 2 })
+        .expect("issuer onboarding failed");
+    assert_eq!(setup.ledger.get_component_balance(applicant, setup.issuer_badge), dec!("1"));
+
+    let issuer = Issuer {
+        public_key: applicant_key,
+        account: applicant,
+        badge_id: NonFungibleLocalId::integer(2),
+    };
+    let maturity_date = setup.now + DAY;
+    setup.create_bond(&issuer, maturity_date);
+}
+
+#[test]
+fn test_missed_sinking_fund_instalments_delay_the_bond() {
+    let mut setup = setup_dao();
+    let diligent = setup.onboard_issuer();
+    let negligent = setup.onboard_issuer();
+
+    let due_date = setup.now + DAY / 2;
+    let maturity_date = setup.now + DAY;
+    setup.create_bond_with(&diligent, maturity_date, vec![(due_date, dec!("20"))], None);
+    setup.create_bond_with(&negligent, maturity_date, vec![(due_date, dec!("20"))], None);
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(diligent.account, XRD, dec!("20"))
+        .take_all_from_worktop(XRD, "deposit")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                setup.dao,
+                "deposit_to_sinking_fund",
+                manifest_args!(diligent.account, lookup.bucket("deposit")),
+            )
+        })
+        .build();
+    let public_key = diligent.public_key;
+    setup.run(manifest, &public_key).expect_commit_success();
+
+    setup.set_time(due_date + 1);
+
+    let status: SinkingFundStatus = setup.query(setup.dao, "check_sinking_fund", manifest_args!(diligent.account));
+    assert!(status.missed_instalments.is_empty());
+    assert_eq!(status.coverage_ratio, Some(dec!("1")));
+    let bond_status: BondStatus = setup.query(setup.dao, "refresh_bond_status", manifest_args!(diligent.account));
+    assert_eq!(bond_status, BondStatus::Performing);
+
+    let status: SinkingFundStatus = setup.query(setup.dao, "check_sinking_fund", manifest_args!(negligent.account));
+    assert_eq!(status.missed_instalments, vec![0]);
+    assert_eq!(status.required_to_date, dec!("20"));
+    assert_eq!(status.coverage_ratio, Some(Decimal::zero()));
+    let bond_status: BondStatus = setup.query(setup.dao, "refresh_bond_status", manifest_args!(negligent.account));
+    assert_eq!(bond_status, BondStatus::Delayed);
+}
+
+#[test]
+fn test_portfolio_accrues_interest_from_the_purchase() {
+    let mut setup = setup_dao();
+    let issuer = setup.onboard_issuer();
+
+    // the DAO can only buy bonds that were issued
+    setup.buy_bond_for_dao(issuer.account).expect_commit_failure();
+
+    let maturity_date = setup.now + 100 * DAY;
+    let bond = setup.create_bond(&issuer, maturity_date);
+
+    // bought half way to maturity
+    setup.set_time(maturity_date - 50 * DAY);
+    let acquired_at = setup.now;
+    setup.buy_bond_for_dao(issuer.account).expect_commit_success();
+
+    let report: PortfolioReport = setup.query(setup.dao, "get_bond_portfolio", manifest_args!());
+    assert_eq!(report.holdings.len(), 1);
+    assert_eq!(report.holdings[0].bond_component_address, bond);
+    assert_eq!(report.holdings[0].acquired_at, acquired_at);
+    assert_eq!(report.holdings[0].accrued_interest, Decimal::zero());
+
+    // half of the 0.8 earned between purchase and maturity
+    setup.set_time(acquired_at + 25 * DAY);
+    let report: PortfolioReport = setup.query(setup.dao, "get_bond_portfolio", manifest_args!());
+    assert_eq!(report.total_cost_basis, dec!("40"));
+    assert_eq!(report.total_accrued_interest, dec!("0.4"));
+    assert_eq!(report.total_carrying_value, dec!("40.4"));
+    assert_eq!(report.holdings[0].market_value, None);
+    assert_eq!(report.net_asset_value, dec!("40.4"));
+}
+
+#[test]
+fn test_bond_purchases_keep_the_minimum_liquidity() {
+    let mut setup = setup_dao();
+    let issuer = setup.onboard_issuer();
+    let maturity_date = setup.now + 100 * DAY;
+    setup.create_bond(&issuer, maturity_date);
+    let (voter_key, voter) = setup.new_token_holder(dec!("100"));
+
+    let ladder: LiquidityLadder =
+        setup.query(setup.dao, "get_liquidity_ladder", manifest_args!(LadderInterval::Weekly, 4u32));
+    let treasury = ladder.opening_balance;
+
+    let set_minimum_liquidity = |setup: &mut DaoSetup, minimum_liquidity: Decimal| {
+        let manifest = setup
+            .with_owner_badge()
+            .call_method(
+                setup.dao,
+                "set_liquidity_policy",
+                manifest_args!(minimum_liquidity, LadderInterval::Weekly, 4u32),
+            )
+            .build();
+        setup.run_as_owner(manifest).expect_commit_success();
+    };
+
+    let now = setup.now;
+    setup
+        .create_proposal(
+            &voter_key,
+            voter,
+            VotingType::ResourceHold,
+            ProposalAction::PurchaseBond {
+                bond_creator_address: issuer.account,
+                target_xrd_amount: dec!("40"),
+            },
+            now,
+            now + DAY,
+        )
+        .expect_commit_success();
+    let proposal_id = setup.proposals;
+    setup.vote(&voter_key, voter, proposal_id, dec!("50"), false).expect_commit_success();
+    setup.set_time(now + DAY + 1);
+
+    // one XRD short of the minimum after paying for the bond
+    set_minimum_liquidity(&mut setup, treasury - dec!("39"));
+    setup
+        .call(setup.dao, "execute_proposal", manifest_args!(proposal_id))
+        .expect_commit_failure();
+
+    set_minimum_liquidity(&mut setup, treasury - dec!("40"));
+    setup.execute_proposal(proposal_id).expect("the bond purchase failed");
+
+    let report: PortfolioReport = setup.query(setup.dao, "get_bond_portfolio", manifest_args!());
+    assert_eq!(report.total_quantity, dec!("1"));
+}
+
+#[test]
+fn test_proposals_must_meet_their_category_passing_rule() {
+    let mut setup = setup_dao();
+    let (supporter_key, supporter) = setup.new_token_holder(dec!("100"));
+    let (opponent_key, opponent) = setup.new_token_holder(dec!("100"));
+
+    let rule = PassingRule {
+        approval: ApprovalThreshold::Supermajority(dec!("75")),
+        minimum_turnout: Decimal::zero(),
+    };
+    let manifest = setup
+        .with_owner_badge()
+        .call_method(setup.dao, "set_passing_rule", manifest_args!(ProposalCategory::MintTokens, rule))
+        .build();
+    setup.run_as_owner(manifest).expect_commit_success();
+
+    for _ in 0..2 {
+        setup
+            .create_proposal(&supporter_key, supporter, VotingType::ResourceHold, ProposalAction::MintTokens { amount: 10 }, START, START + DAY)
+            .expect_commit_success();
+    }
+
+    // 60% in favour
+    setup.vote(&supporter_key, supporter, 1, dec!("60"), false).expect_commit_success();
+    setup.vote(&opponent_key, opponent, 1, dec!("40"), true).expect_commit_success();
+
+    // 80% in favour
+    setup.vote(&supporter_key, supporter, 2, dec!("40"), false).expect_commit_success();
+    setup.vote(&opponent_key, opponent, 2, dec!("10"), true).expect_commit_success();
+
+    setup.set_time(START + DAY + 1);
+    assert!(setup.execute_proposal(1).is_err());
+    setup.execute_proposal(2).expect("the supermajority proposal failed");
+}
+
+#[test]
+fn test_votes_are_cast_for_the_signing_account_only() {
+    let mut setup = setup_dao();
+    let (holder_key, holder) = setup.new_token_holder(dec!("100"));
+    let (_victim_key, victim) = setup.new_token_holder(dec!("100"));
+
+    setup
+        .create_proposal(&holder_key, holder, VotingType::ResourceHold, ProposalAction::MintTokens { amount: 10 }, START, START + DAY)
+        .expect_commit_success();
+
+    // the holder's own tokens, voted in the name of an account the holder can not sign for
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(holder, setup.dao_token, dec!("10"))
+        .take_all_from_worktop(setup.dao_token, "tokens")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                setup.dao,
+                "vote",
+                manifest_args!(lookup.bucket("tokens"), false, victim, 1usize),
+            )
+        })
+        .deposit_entire_worktop(holder)
+        .build();
+    setup.run(manifest, &holder_key).expect_commit_failure();
+
+    setup.vote(&holder_key, holder, 1, dec!("10"), false).expect_commit_success();
+}
+
+#[test]
+fn test_membership_needs_the_token_threshold() {
+    let mut setup = setup_dao();
+    let (small_key, small) = setup.new_token_holder(dec!("10"));
+    let (large_key, large) = setup.new_token_holder(dec!("100"));
+
+    let manifest = setup
+        .with_owner_badge()
+        .call_method(setup.dao, "set_join_policy", manifest_args!(JoinPolicy::TokenThreshold(dec!("50"))))
+        .build();
+    setup.run_as_owner(manifest).expect_commit_success();
+
+    setup.join(&small_key, small).expect_commit_failure();
+    assert_eq!(setup.ledger.get_component_balance(small, setup.member_badge), dec!("0"));
+
+    setup.join(&large_key, large).expect_commit_success();
+    assert_eq!(setup.ledger.get_component_balance(large, setup.member_badge), dec!("1"));
+
+    // one badge per account
+    setup.join(&large_key, large).expect_commit_failure();
+}
+
+#[test]
+fn test_member_votes_count_towards_turnout() {
+    let mut setup = setup_dao();
+    let (alice_key, alice) = setup.new_token_holder(dec!("100"));
+    let (bob_key, bob) = setup.new_token_holder(dec!("100"));
+    setup.join(&alice_key, alice).expect_commit_success();
+    setup.join(&bob_key, bob).expect_commit_success();
+
+    let rule = PassingRule {
+        approval: ApprovalThreshold::SimpleMajority,
+        minimum_turnout: dec!("50"),
+    };
+    let manifest = setup
+        .with_owner_badge()
+        .call_method(setup.dao, "set_passing_rule", manifest_args!(ProposalCategory::MintTokens, rule))
+        .call_method(setup.dao, "set_one_vote_per_member", manifest_args!(true))
+        .build();
+    setup.run_as_owner(manifest).expect_commit_success();
+
+    setup
+        .create_proposal(&alice_key, alice, VotingType::Equality, ProposalAction::MintTokens { amount: 10 }, START, START + DAY)
+        .expect_commit_success();
+
+    let member_badge = setup.member_badge;
+    let dao = setup.dao;
+    let vote_as_member = |account: ComponentAddress| {
+        ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .create_proof_from_account_of_amount(account, member_badge, dec!("1"))
+            .pop_from_auth_zone("badge")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(dao, "vote_as_member", manifest_args!(lookup.proof("badge"), false, 1usize))
+            })
+            .build()
+    };
+
+    setup.run(vote_as_member(alice), &alice_key).expect_commit_success();
+    // one vote per member
+    setup.run(vote_as_member(alice), &alice_key).expect_commit_failure();
+    setup.run(vote_as_member(bob), &bob_key).expect_commit_success();
+
+    // the 200 tokens the members hold are all the tokens in circulation
+    setup.set_time(START + DAY + 1);
+    setup.execute_proposal(1).expect("the member vote did not meet the turnout");
+}
+
+#[test]
+fn test_a_delegators_own_vote_overrides_the_delegate() {
+    let mut setup = setup_dao();
+    let (delegate_key, delegate) = setup.new_token_holder(dec!("100"));
+    let (delegator_key, delegator) = setup.new_token_holder(dec!("100"));
+    let (_stranger_key, _stranger_private_key, stranger) = setup.ledger.new_allocated_account();
+    setup.join(&delegate_key, delegate).expect_commit_success();
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(delegator, setup.dao_token, dec!("60"))
+        .take_all_from_worktop(setup.dao_token, "tokens")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                setup.dao,
+                "lock_tokens_for_delegation",
+                manifest_args!(delegator, lookup.bucket("tokens")),
+            )
+        })
+        .build();
+    setup.run(manifest, &delegator_key).expect_commit_success();
+
+    let delegate_to = |dao: ComponentAddress, to: ComponentAddress| {
+        ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(dao, "delegate_votes", manifest_args!(delegator, to, None::<ProposalCategory>))
+            .build()
+    };
+
+    // only members can be delegates
+    setup.run(delegate_to(setup.dao, stranger), &delegator_key).expect_commit_failure();
+    setup.run(delegate_to(setup.dao, delegate), &delegator_key).expect_commit_success();
+
+    setup
+        .create_proposal(&delegate_key, delegate, VotingType::ResourceHold, ProposalAction::MintTokens { amount: 10 }, START, START + DAY)
+        .expect_commit_success();
+
+    // the delegate's 10 tokens carry the 60 locked by the delegator
+    setup.vote(&delegate_key, delegate, 1, dec!("10"), false).expect_commit_success();
+
+    // locked tokens a delegate voted with stay locked while the proposal is open
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(setup.dao, "unlock_delegated_tokens", manifest_args!(delegator, dec!("60")))
+        .deposit_entire_worktop(delegator)
+        .build();
+    setup.run(manifest, &delegator_key).expect_commit_failure();
+
+    // 10 for against 30, the delegated 60 no longer count
+    setup.vote(&delegator_key, delegator, 1, dec!("30"), true).expect_commit_success();
+
+    setup.set_time(START + DAY + 1);
+    assert!(setup.execute_proposal(1).is_err());
+}
+
+#[test]
+fn test_quadratic_votes_count_the_square_root_of_the_tokens() {
+    let mut setup = setup_dao();
+    let (whale_key, whale) = setup.new_token_holder(dec!("101"));
+    let (alice_key, alice) = setup.new_token_holder(dec!("36"));
+    let (bob_key, bob) = setup.new_token_holder(dec!("36"));
+
+    // under the open policy one holder could join with many accounts
+    setup
+        .create_proposal(&whale_key, whale, VotingType::Quadratic, ProposalAction::MintTokens { amount: 10 }, START, START + DAY)
+        .expect_commit_failure();
+
+    let manifest = setup
+        .with_owner_badge()
+        .call_method(setup.dao, "set_join_policy", manifest_args!(JoinPolicy::TokenThreshold(dec!("10"))))
+        .build();
+    setup.run_as_owner(manifest).expect_commit_success();
+
+    for (key, account) in [(whale_key, whale), (alice_key, alice), (bob_key, bob)] {
+        setup.join(&key, account).expect_commit_success();
+    }
+
+    setup
+        .create_proposal(&whale_key, whale, VotingType::Quadratic, ProposalAction::MintTokens { amount: 10 }, START, START + DAY)
+        .expect_commit_success();
+
+    // quadratic proposals only take votes backed by a member badge
+    setup.vote(&whale_key, whale, 1, dec!("100"), false).expect_commit_failure();
+
+    // 100 tokens count for 10, the two 36 token votes against for 6 each
+    setup.vote_quadratic(&whale_key, whale, 1, dec!("100"), false).expect_commit_success();
+    setup.vote_quadratic(&alice_key, alice, 1, dec!("36"), true).expect_commit_success();
+    setup.vote_quadratic(&bob_key, bob, 1, dec!("36"), true).expect_commit_success();
+
+    setup.set_time(START + DAY + 1);
+    assert!(setup.execute_proposal(1).is_err());
+}
+
+fn mint_option(label: &str, amount: usize) -> ProposalOption {
+    ProposalOption {
+        label: label.to_string(),
+        action: ProposalAction::MintTokens { amount },
+    }
+}
+
+#[test]
+fn test_the_winning_option_of_a_proposal_is_executed() {
+    let mut setup = setup_dao();
+    let (alice_key, alice) = setup.new_token_holder(dec!("100"));
+    let (bob_key, bob) = setup.new_token_holder(dec!("100"));
+
+    // labels must be unique
+    setup
+        .create_proposal_with_options(&alice_key, alice, vec![mint_option("mint", 10), mint_option("mint", 20)], CountingMethod::Plurality)
+        .expect_commit_failure();
+
+    // applications are decided one at a time
+    let admissions = vec![
+        ProposalOption {
+            label: "first".to_string(),
+            action: ProposalAction::AdmitMember { application_id: 1 },
+        },
+        ProposalOption {
+            label: "second".to_string(),
+            action: ProposalAction::AdmitMember { application_id: 2 },
+        },
+    ];
+    setup
+        .create_proposal_with_options(&alice_key, alice, admissions, CountingMethod::Plurality)
+        .expect_commit_failure();
+
+    setup
+        .create_proposal_with_options(&alice_key, alice, vec![mint_option("ten", 10), mint_option("twenty", 20)], CountingMethod::Plurality)
+        .expect_commit_success();
+
+    // plurality ballots name one existing option
+    setup.vote_on_options(&alice_key, alice, 1, dec!("30"), vec![2]).expect_commit_failure();
+    setup.vote_on_options(&alice_key, alice, 1, dec!("30"), vec![0, 1]).expect_commit_failure();
+
+    setup.vote_on_options(&alice_key, alice, 1, dec!("30"), vec![0]).expect_commit_success();
+    setup.vote_on_options(&bob_key, bob, 1, dec!("40"), vec![1]).expect_commit_success();
+
+    setup.set_time(START + DAY + 1);
+    assert_eq!(setup.execute_proposal(1), Ok("20 tokens minted".to_string()));
+}
+
+#[test]
+fn test_member_badges_stay_with_their_account_and_their_threshold() {
+    let mut setup = setup_dao();
+    let (alice_key, alice) = setup.new_token_holder(dec!("20"));
+    let (bob_key, bob) = setup.new_token_holder(dec!("20"));
+
+    let manifest = setup
+        .with_owner_badge()
+        .call_method(setup.dao, "set_join_policy", manifest_args!(JoinPolicy::TokenThreshold(dec!("10"))))
+        .build();
+    setup.run_as_owner(manifest).expect_commit_success();
+
+    setup.join(&alice_key, alice).expect_commit_success();
+    setup.join(&bob_key, bob).expect_commit_success();
+
+    // a badge can not be handed to another account
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(alice, setup.member_badge, dec!("1"))
+        .deposit_entire_worktop(bob)
+        .build();
+    setup.run(manifest, &alice_key).expect_commit_failure();
+
+    setup
+        .create_proposal(&alice_key, alice, VotingType::Quadratic, ProposalAction::MintTokens { amount: 10 }, START, START + DAY)
+        .expect_commit_success();
+
+    // bob hands 15 tokens to alice and is left below the threshold
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(bob, setup.dao_token, dec!("15"))
+        .deposit_entire_worktop(alice)
+        .build();
+    setup.run(manifest, &bob_key).expect_commit_success();
 
-    Ok(())
+    setup.vote_quadratic(&bob_key, bob, 1, dec!("5"), false).expect_commit_failure();
+    setup.vote_quadratic(&alice_key, alice, 1, dec!("35"), false).expect_commit_success();
 }
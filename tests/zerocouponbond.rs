@@ -0,0 +1,583 @@
+use scrypto_test::prelude::*;
+
+struct BondSetup {
+    ledger: DefaultLedgerSimulator,
+    public_key: Secp256k1PublicKey,
+    account: ComponentAddress,
+    issuer_badge_resource: ResourceAddress,
+    bond: ComponentAddress,
+    // tokens bonds convert into, when the bond is convertible
+    conversion_resource: Option<ResourceAddress>,
+}
+
+// Publishes the package and instantiates a bond directly, funded by a DAO address nobody here can act as.
+fn setup_bond() -> BondSetup {
+    setup_bond_with_options(None, None, None)
+}
+
+// same bond, callable at (call price, lockout date)
+fn setup_bond_with_call_option(call_option: Option<(Decimal, u64)>) -> BondSetup {
+    setup_bond_with_options(call_option, None, None)
+}
+
+// same bond with a call option, a put option at (put price, put dates, exercise window)
+// and conversion terms at (conversion ratio, conversion deadline), backed by a new token
+fn setup_bond_with_options(
+    call_option: Option<(Decimal, u64)>,
+    put_option: Option<(Decimal, Vec<u64>, u64)>,
+    conversion_terms: Option<(Decimal, u64)>,
+) -> BondSetup {
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (public_key, _private_key, account) = ledger.new_allocated_account();
+    let package_address = ledger.compile_and_publish(this_package!());
+
+    let issuer_badge_resource = ledger.create_non_fungible_resource(account);
+    let issuer_badge = NonFungibleGlobalId::new(issuer_badge_resource, NonFungibleLocalId::integer(1));
+
+    let conversion_resource = conversion_terms
+        .map(|_| ledger.create_fungible_resource(dec!("1000"), DIVISIBILITY_MAXIMUM, account));
+
+    let builder = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(account, XRD, dec!("10"))
+        .take_all_from_worktop(XRD, "collateral");
+    let builder = match (conversion_terms, conversion_resource) {
+        (Some((conversion_ratio, _)), Some(conversion_resource)) => builder
+            .withdraw_from_account(account, conversion_resource, conversion_ratio * dec!("100"))
+            .take_all_from_worktop(conversion_resource, "conversion tokens"),
+        _ => builder,
+    };
+    let manifest = builder
+        .with_name_lookup(|builder, lookup| {
+            let conversion = conversion_terms.map(|terms| (terms, lookup.bucket("conversion tokens")));
+            builder.call_function(
+                package_address,
+                "ZeroCouponBond",
+                "instantiate_zerocouponbond",
+                manifest_args!(
+                    "Principal at Maturity",
+                    "PAM",
+                    "#1",
+                    dec!("2"),
+                    "xrd",
+                    0u64,
+                    u32::MAX as u64,
+                    dec!("40"),
+                    0u64,
+                    "long",
+                    40u64,
+                    dec!("100"),
+                    lookup.bucket("collateral"),
+                    (604800u64, 2592000u64, Vec::<(u64, Decimal)>::new()),
+                    NonFungibleLocalId::integer(1),
+                    (call_option, put_option),
+                    conversion,
+                    account,
+                    issuer_badge,
+                    FAUCET
+                ),
+            )
+        })
+        .build();
+
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+    let bond = receipt.expect_commit(true).new_component_addresses()[0];
+
+    BondSetup {
+        ledger,
+        public_key,
+        account,
+        issuer_badge_resource,
+        bond,
+        conversion_resource,
+    }
+}
+
+impl BondSetup {
+    fn run(&mut self, manifest: TransactionManifestV1, public_key: &Secp256k1PublicKey) -> TransactionReceipt {
+        self.ledger.execute_manifest(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(public_key)],
+        )
+    }
+
+    // a new account buys one bond at the price of 40 XRD
+    fn purchase_from_new_holder(&mut self) -> (Secp256k1PublicKey, ComponentAddress) {
+        let (holder_key, _holder_private_key, holder) = self.ledger.new_allocated_account();
+
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(holder, XRD, dec!("40"))
+            .take_all_from_worktop(XRD, "payment")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(self.bond, "purchase_bond", manifest_args!(lookup.bucket("payment")))
+            })
+            .deposit_entire_worktop(holder)
+            .build();
+        self.run(manifest, &holder_key).expect_commit_success();
+
+        (holder_key, holder)
+    }
+
+    fn bond_resource(&mut self) -> ResourceAddress {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(self.bond, "get_resource_address", manifest_args!())
+            .build();
+        self.ledger
+            .execute_manifest(manifest, vec![])
+            .expect_commit_success()
+            .output(1)
+    }
+
+    fn issuer_deposit(&mut self, method: &str, amount: Decimal) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .create_proof_from_account_of_non_fungibles(
+                self.account,
+                self.issuer_badge_resource,
+                [NonFungibleLocalId::integer(1)],
+            )
+            .withdraw_from_account(self.account, XRD, amount)
+            .take_all_from_worktop(XRD, "deposit")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(self.bond, method, manifest_args!(lookup.bucket("deposit")))
+            })
+            .deposit_entire_worktop(self.account)
+            .build();
+        let public_key = self.public_key;
+        self.run(manifest, &public_key)
+    }
+
+    fn issuer_call(&mut self, method: &str) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .create_proof_from_account_of_non_fungibles(
+                self.account,
+                self.issuer_badge_resource,
+                [NonFungibleLocalId::integer(1)],
+            )
+            .call_method(self.bond, method, manifest_args!())
+            .deposit_entire_worktop(self.account)
+            .build();
+        let public_key = self.public_key;
+        self.run(manifest, &public_key)
+    }
+}
+
+fn is_unauthorized(error: &RuntimeError) -> bool {
+    matches!(
+        error,
+        RuntimeError::SystemModuleError(SystemModuleError::AuthError(AuthError::Unauthorized(..)))
+    )
+}
+
+#[test]
+fn test_direct_calls_to_dao_methods_are_rejected() {
+    let mut setup = setup_bond();
+
+    for method in [
+        "force_transfer_deposited_xrds",
+        "liquidate_collateral",
+        "liquidate_collateral_on_margin_call",
+        "report_missed_instalments",
+    ] {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(setup.bond, method, manifest_args!())
+            .deposit_entire_worktop(setup.account)
+            .build();
+        let receipt = setup.ledger.execute_manifest(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&setup.public_key)],
+        );
+        receipt.expect_specific_failure(is_unauthorized);
+    }
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(setup.account, XRD, dec!("1"))
+        .take_all_from_worktop(XRD, "bond")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                setup.bond,
+                "claim_the_invested_XRDs_plus_interest",
+                manifest_args!(lookup.bucket("bond")),
+            )
+        })
+        .deposit_entire_worktop(setup.account)
+        .build();
+    let receipt = setup.ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&setup.public_key)],
+    );
+    receipt.expect_specific_failure(is_unauthorized);
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(
+            setup.bond,
+            "change_community_claim_status",
+            manifest_args!(true),
+        )
+        .build();
+    let receipt = setup.ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&setup.public_key)],
+    );
+    receipt.expect_specific_failure(is_unauthorized);
+}
+
+#[test]
+fn test_issuer_methods_require_the_issuer_badge() {
+    let mut setup = setup_bond();
+
+    // without the badge
+    for method in [
+        "get_back_the_collateral",
+        "take_out_the_invested_XRDs_by_the_community",
+    ] {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(setup.bond, method, manifest_args!())
+            .deposit_entire_worktop(setup.account)
+            .build();
+        let receipt = setup.ledger.execute_manifest(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&setup.public_key)],
+        );
+        receipt.expect_specific_failure(is_unauthorized);
+    }
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(setup.account, XRD, dec!("5"))
+        .take_all_from_worktop(XRD, "deposit")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                setup.bond,
+                "deposit_to_sinking_fund",
+                manifest_args!(lookup.bucket("deposit")),
+            )
+        })
+        .build();
+    let receipt = setup.ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&setup.public_key)],
+    );
+    receipt.expect_specific_failure(is_unauthorized);
+
+    // with the badge
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            setup.account,
+            setup.issuer_badge_resource,
+            [NonFungibleLocalId::integer(1)],
+        )
+        .withdraw_from_account(setup.account, XRD, dec!("5"))
+        .take_all_from_worktop(XRD, "deposit")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                setup.bond,
+                "deposit_to_sinking_fund",
+                manifest_args!(lookup.bucket("deposit")),
+            )
+        })
+        .build();
+    let receipt = setup.ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&setup.public_key)],
+    );
+    receipt.expect_commit_success();
+}
+
+#[test]
+fn test_read_and_holder_methods_are_public() {
+    let mut setup = setup_bond();
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(setup.bond, "get_bond_details", manifest_args!())
+        .call_method(setup.bond, "refresh_status", manifest_args!())
+        .withdraw_from_account(setup.account, XRD, dec!("40"))
+        .take_all_from_worktop(XRD, "payment")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                setup.bond,
+                "purchase_bond",
+                manifest_args!(lookup.bucket("payment")),
+            )
+        })
+        .deposit_entire_worktop(setup.account)
+        .build();
+    let receipt = setup.ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&setup.public_key)],
+    );
+    receipt.expect_commit_success();
+}
+
+#[test]
+fn test_holders_purchase_money_does_not_count_as_repayment() {
+    let mut setup = setup_bond();
+    setup.purchase_from_new_holder();
+
+    // 40 XRD from the holder plus 5 from the issuer used to cover the 40.8 redemption value
+    setup
+        .issuer_deposit("deposit_to_sinking_fund", dec!("5"))
+        .expect_commit_success();
+    setup
+        .issuer_call("get_back_the_collateral")
+        .expect_commit_failure();
+
+    setup
+        .issuer_deposit("put_in_money_plus_interest_for_the_community_to_redeem", dec!("40"))
+        .expect_commit_success();
+    setup
+        .issuer_call("get_back_the_collateral")
+        .expect_commit_success();
+}
+
+#[test]
+fn test_called_bonds_are_burnt_and_no_longer_sold() {
+    let mut setup = setup_bond_with_call_option(Some((dec!("45"), 0u64)));
+    let (holder_key, holder) = setup.purchase_from_new_holder();
+
+    // the earlier sinking fund deposit pays for part of the call
+    setup
+        .issuer_deposit("deposit_to_sinking_fund", dec!("10"))
+        .expect_commit_success();
+    setup
+        .issuer_deposit("exercise_call", dec!("35"))
+        .expect_commit_success();
+
+    // a called bond can not be bought any more
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(holder, XRD, dec!("40"))
+        .take_all_from_worktop(XRD, "payment")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(setup.bond, "purchase_bond", manifest_args!(lookup.bucket("payment")))
+        })
+        .deposit_entire_worktop(holder)
+        .build();
+    setup.run(manifest, &holder_key).expect_commit_failure();
+
+    let bond_resource = setup
+        .ledger
+        .get_component_resources(holder)
+        .into_iter()
+        .find(|(resource, amount)| *resource != XRD && *amount == dec!("1"))
+        .map(|(resource, _)| resource)
+        .expect("holder has no bond");
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(holder, bond_resource, dec!("1"))
+        .take_all_from_worktop(bond_resource, "bond")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(setup.bond, "redeem_called_bond", manifest_args!(lookup.bucket("bond")))
+        })
+        .deposit_entire_worktop(holder)
+        .build();
+    setup.run(manifest, &holder_key).expect_commit_success();
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(setup.bond, "get_outstanding_bonds", manifest_args!())
+        .build();
+    let outstanding: Decimal = setup.run(manifest, &holder_key).expect_commit_success().output(1);
+    assert_eq!(outstanding, Decimal::zero());
+    assert_eq!(
+        setup.ledger.get_component_balance(setup.bond, bond_resource),
+        dec!("99")
+    );
+}
+
+#[test]
+fn test_puts_are_only_paid_from_issuer_deposits() {
+    let put_date = 1_700_000_000;
+    let mut setup = setup_bond_with_options(None, Some((dec!("40"), vec![put_date], 86400)), None);
+    setup
+        .ledger
+        .advance_to_round_at_timestamp(Round::of(1), ((put_date + 1) * 1000) as i64);
+    let (holder_key, holder) = setup.purchase_from_new_holder();
+
+    let bond_resource = setup
+        .ledger
+        .get_component_resources(holder)
+        .into_iter()
+        .find(|(resource, amount)| *resource != XRD && *amount == dec!("1"))
+        .map(|(resource, _)| resource)
+        .expect("holder has no bond");
+
+    let put = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(holder, bond_resource, dec!("1"))
+        .take_all_from_worktop(bond_resource, "bond")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(setup.bond, "exercise_put", manifest_args!(lookup.bucket("bond")))
+        })
+        .deposit_entire_worktop(holder)
+        .build();
+
+    // the holder's own 40 XRD are in the vault, but the issuer has not deposited anything
+    setup.run(put.clone(), &holder_key).expect_commit_success();
+    assert_eq!(setup.ledger.get_component_balance(holder, bond_resource), dec!("1"));
+
+    setup
+        .issuer_deposit("deposit_to_sinking_fund", dec!("40"))
+        .expect_commit_success();
+
+    let xrd_before = setup.ledger.get_component_balance(holder, XRD);
+    setup.run(put, &holder_key).expect_commit_success();
+    assert_eq!(setup.ledger.get_component_balance(holder, bond_resource), dec!("0"));
+    assert!(setup.ledger.get_component_balance(holder, XRD) > xrd_before);
+}
+
+#[test]
+fn test_bonds_convert_into_tokens_until_the_deadline() {
+    let deadline = 1_700_000_000;
+    let mut setup = setup_bond_with_options(None, None, Some((dec!("2"), deadline)));
+    setup
+        .ledger
+        .advance_to_round_at_timestamp(Round::of(1), ((deadline - 1) * 1000) as i64);
+    let bond_resource = setup.bond_resource();
+    let conversion_resource = setup.conversion_resource.unwrap();
+
+    let (early_key, early) = setup.purchase_from_new_holder();
+    let (late_key, late) = setup.purchase_from_new_holder();
+
+    let convert = |holder: ComponentAddress, bond: ComponentAddress| {
+        ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(holder, bond_resource, dec!("1"))
+            .take_all_from_worktop(bond_resource, "bond")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(bond, "convert", manifest_args!(lookup.bucket("bond")))
+            })
+            .deposit_entire_worktop(holder)
+            .build()
+    };
+
+    setup.run(convert(early, setup.bond), &early_key).expect_commit_success();
+    assert_eq!(setup.ledger.get_component_balance(early, conversion_resource), dec!("2"));
+    assert_eq!(setup.ledger.get_component_balance(early, bond_resource), dec!("0"));
+
+    setup
+        .ledger
+        .advance_to_round_at_timestamp(Round::of(2), ((deadline + 1) * 1000) as i64);
+
+    setup.run(convert(late, setup.bond), &late_key).expect_commit_failure();
+    assert_eq!(setup.ledger.get_component_balance(late, bond_resource), dec!("1"));
+}
+
+#[test]
+fn test_term_sheet_is_on_the_bond_resource_and_only_the_bond_updates_its_status() {
+    let put_date = 1_700_000_000;
+    let mut setup = setup_bond_with_options(None, Some((dec!("40"), vec![put_date], 86400)), None);
+    let bond_resource = setup.bond_resource();
+
+    let metadata = |setup: &mut BondSetup, key: &str| setup.ledger.get_metadata(bond_resource.into(), key);
+    assert_eq!(metadata(&mut setup, "symbol"), Some(MetadataValue::String("ZCB".to_string())));
+    assert_eq!(metadata(&mut setup, "contract_identifier"), Some(MetadataValue::String("#1".to_string())));
+    assert_eq!(metadata(&mut setup, "interest_rate"), Some(MetadataValue::Decimal(dec!("2"))));
+    assert_eq!(metadata(&mut setup, "status"), Some(MetadataValue::String("Performing".to_string())));
+
+    // the issuer can not rewrite the status
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .set_metadata(bond_resource, "status", "Repaid")
+        .build();
+    let public_key = setup.public_key;
+    setup.run(manifest, &public_key).expect_specific_failure(is_unauthorized);
+
+    // a put the issuer has not funded delays the bond
+    setup
+        .ledger
+        .advance_to_round_at_timestamp(Round::of(1), ((put_date + 1) * 1000) as i64);
+    let (holder_key, holder) = setup.purchase_from_new_holder();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(holder, bond_resource, dec!("1"))
+        .take_all_from_worktop(bond_resource, "bond")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(setup.bond, "exercise_put", manifest_args!(lookup.bucket("bond")))
+        })
+        .deposit_entire_worktop(holder)
+        .build();
+    setup.run(manifest, &holder_key).expect_commit_success();
+
+    assert_eq!(metadata(&mut setup, "status"), Some(MetadataValue::String("Delayed".to_string())));
+}
+
+// the metadata fields of each event the bond component emitted itself
+fn bond_event_fields(bond: ComponentAddress, receipt: &TransactionReceipt) -> Vec<Vec<ScryptoValue>> {
+    receipt
+        .expect_commit_success()
+        .application_events
+        .iter()
+        .filter(|(EventTypeIdentifier(emitter, _), _)| {
+            matches!(emitter, Emitter::Method(node_id, ModuleId::Main) if node_id == bond.as_node_id())
+        })
+        .map(|(_, payload)| {
+            // PandaoEvent { event_type, dao_type, component_address, meta_data }
+            let event: ScryptoValue = scrypto_decode(payload).unwrap();
+            match event {
+                ScryptoValue::Tuple { mut fields } => match fields.remove(3) {
+                    ScryptoValue::Enum { mut fields, .. } => match fields.remove(0) {
+                        ScryptoValue::Tuple { fields } => fields,
+                        other => panic!("unexpected event metadata {:?}", other),
+                    },
+                    other => panic!("unexpected event metadata {:?}", other),
+                },
+                other => panic!("unexpected event {:?}", other),
+            }
+        })
+        .collect()
+}
+
+fn decimal(value: &ScryptoValue) -> Decimal {
+    match value {
+        ScryptoValue::Custom {
+            value: ScryptoCustomValue::Decimal(decimal),
+        } => *decimal,
+        other => panic!("{:?} is not a decimal", other),
+    }
+}
+
+#[test]
+fn test_the_bond_emits_its_own_lifecycle_events() {
+    let mut setup = setup_bond();
+    let (holder_key, _holder_private_key, holder) = setup.ledger.new_allocated_account();
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(holder, XRD, dec!("40"))
+        .take_all_from_worktop(XRD, "payment")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(setup.bond, "purchase_bond", manifest_args!(lookup.bucket("payment")))
+        })
+        .deposit_entire_worktop(holder)
+        .build();
+    let receipt = setup.run(manifest, &holder_key);
+
+    // BondPurchased { bond_component_address, bonds_purchased, amount_paid, bonds_left }
+    let events = bond_event_fields(setup.bond, &receipt);
+    assert_eq!(events.len(), 1);
+    assert_eq!(decimal(&events[0][1]), dec!("1"));
+    assert_eq!(decimal(&events[0][2]), dec!("40"));
+    assert_eq!(decimal(&events[0][3]), dec!("99"));
+
+    // a refresh that does not change the status has nothing to report
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(setup.bond, "refresh_status", manifest_args!())
+        .build();
+    let receipt = setup.run(manifest, &holder_key);
+    assert!(bond_event_fields(setup.bond, &receipt).is_empty());
+}